@@ -1,18 +1,27 @@
 use crate::CurveHooks;
 
-use ark_bls12_377::g1::Config as ArkConfig;
+use ark_bls12_377::{g1::Config as ArkConfig, Fq};
+use ark_ec::scalar_mul::glv::GLVConfig;
+use ark_ff::PrimeField;
 use ark_models_ext::{
+    batch,
+    batch::MsmJob,
     bls12,
+    bls12::Bls12Config,
+    checked::{self, HookError},
     short_weierstrass::{Affine as SWAffine, Projective as SWProjective, SWCurveConfig},
     twisted_edwards::{
         Affine as TEAffine, MontCurveConfig, Projective as TEProjective, TECurveConfig,
     },
-    CurveConfig,
+    validation, AffineRepr, CurveConfig, PrimeGroup,
 };
-use ark_std::marker::PhantomData;
+use ark_std::{marker::PhantomData, ops::Neg, vec::Vec, One};
 
 pub use ark_bls12_377::g1::{G1_GENERATOR_X, G1_GENERATOR_Y, TE_GENERATOR_X, TE_GENERATOR_Y};
 
+/// Non-trivial cube root of unity in `Fq`, defining the G1 endomorphism.
+pub const BETA: Fq = <ArkConfig as GLVConfig>::ENDO_COEFFS[0];
+
 pub type G1Affine<H> = bls12::G1Affine<crate::Config<H>>;
 pub type G1Projective<H> = bls12::G1Projective<crate::Config<H>>;
 
@@ -39,21 +48,23 @@ impl<H: CurveHooks> SWCurveConfig for Config<H> {
     const GENERATOR: G1SWAffine<H> = G1SWAffine::<H>::new_unchecked(G1_GENERATOR_X, G1_GENERATOR_Y);
 
     /// Multi scalar multiplication jumping into the user-defined `msm_g1` hook.
+    ///
+    /// Fails on any `checked_msm` failure, see [`checked::upstream_msm`].
     #[inline(always)]
     fn msm(
         bases: &[G1SWAffine<H>],
         scalars: &[Self::ScalarField],
     ) -> Result<G1SWProjective<H>, usize> {
-        if bases.len() != scalars.len() {
-            return Err(bases.len().min(scalars.len()));
-        }
-        Ok(H::msm_g1(bases, scalars))
+        checked::upstream_msm(bases.len(), Self::checked_msm(bases, scalars))
     }
 
     /// Projective multiplication jumping into the user-defined `mul_projective_g1` hook.
+    ///
+    /// The identity if the scalar exceeds the hooks limits, see
+    /// [`checked::upstream_mul`].
     #[inline(always)]
     fn mul_projective(base: &G1SWProjective<H>, scalar: &[u64]) -> G1SWProjective<H> {
-        H::mul_projective_g1(base, scalar)
+        checked::upstream_mul(base, scalar, Self::checked_mul(base, scalar))
     }
 
     /// Affine multiplication jumping into the user-defined `mul_projective_g1` hook.
@@ -67,22 +78,159 @@ impl<H: CurveHooks> SWCurveConfig for Config<H> {
         <ArkConfig as SWCurveConfig>::mul_by_a(elem)
     }
 
+    /// Subgroup check via the endomorphism, i.e. `endomorphism(P) = -[x^2]P`.
+    ///
+    /// Based on Section 6 of https://eprint.iacr.org/2021/1130.pdf
     #[inline(always)]
-    fn is_in_correct_subgroup_assuming_on_curve(item: &G1SWAffine<H>) -> bool {
-        if Self::cofactor_is_one() {
-            true
-        } else {
-            // Workaround for: https://github.com/arkworks-rs/algebra/issues/948
-            use ark_ff::Field;
-            use ark_std::Zero;
-            let char = Self::ScalarField::characteristic();
-            let l1 = [0, 0, char[2], char[3]];
-            let l2 = [char[0], char[1], 0, 0];
-            (<Self as SWCurveConfig>::mul_affine(item, &l1)
-                + <Self as SWCurveConfig>::mul_affine(item, &l2))
-            .is_zero()
+    fn is_in_correct_subgroup_assuming_on_curve(p: &G1SWAffine<H>) -> bool {
+        let x_times_p = p.mul_bigint(crate::Config::<H>::X);
+        if x_times_p.eq(p) && !p.infinity {
+            return false;
         }
+
+        let minus_x_squared_times_p = x_times_p.mul_bigint(crate::Config::<H>::X).neg();
+        let endomorphism_p = endomorphism(p);
+        minus_x_squared_times_p.eq(&endomorphism_p)
+    }
+
+    // Verbatim copy of upstream implementation.
+    //
+    // Can't call it directly because of different `Affine` configuration.
+    #[inline(always)]
+    fn clear_cofactor(p: &G1SWAffine<H>) -> G1SWAffine<H> {
+        // Using the effective cofactor.
+        //
+        // It is enough to multiply by (x - 1), instead of (x - 1)^2 / 3
+        let h_eff =
+            x_minus_one(crate::Config::<H>::X_IS_NEGATIVE, crate::Config::<H>::X).into_bigint();
+        <Self as SWCurveConfig>::mul_affine(p, h_eff.as_ref()).into()
+    }
+}
+
+impl<H: CurveHooks> Config<H> {
+    /// Multi scalar multiplication jumping into the user-defined `msm_g1` hook.
+    ///
+    /// Fails if the lengths differ or exceed the hooks limits, or if the hook
+    /// output is not on the curve.
+    pub fn checked_msm(
+        bases: &[G1SWAffine<H>],
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<G1SWProjective<H>, HookError> {
+        checked::msm(
+            bases,
+            scalars,
+            &H::LIMITS,
+            H::msm_g1,
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Projective multiplication jumping into the user-defined `mul_projective_g1` hook.
+    ///
+    /// Fails if the scalar exceeds the hooks limits or if the hook output is not
+    /// on the curve.
+    pub fn checked_mul(
+        base: &G1SWProjective<H>,
+        scalar: &[u64],
+    ) -> Result<G1SWProjective<H>, HookError> {
+        checked::mul(
+            scalar,
+            &H::LIMITS,
+            |scalar| H::mul_projective_g1(base, scalar),
+            validation::sw_projective_is_on_curve,
+        )
     }
+
+    /// Batch normalization jumping into the user-defined `normalize_batch_g1` hook.
+    ///
+    /// See [`checked::normalize_batch`].
+    pub fn normalize_batch(points: &[G1Projective<H>]) -> Vec<G1Affine<H>> {
+        checked::normalize_batch(
+            points,
+            H::normalize_batch_g1,
+            validation::sw_is_normalization_of,
+        )
+    }
+
+    /// Multi scalar multiplication with 128-bit scalars jumping into the
+    /// user-defined `msm_small_g1` hook.
+    ///
+    /// Same checks and errors as `checked_msm`.
+    pub fn msm_small(
+        bases: &[G1Affine<H>],
+        scalars: &[u128],
+    ) -> Result<G1Projective<H>, HookError> {
+        checked::msm(
+            bases,
+            scalars,
+            &H::LIMITS,
+            H::msm_small_g1,
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Multi scalar multiplication jumping into the user-defined `msm_small_g1` hook
+    /// if all the scalars fit 128 bits, into the `msm_g1` hook otherwise.
+    pub fn msm_auto(
+        bases: &[G1Affine<H>],
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<G1Projective<H>, HookError> {
+        checked::msm_auto(
+            scalars,
+            |scalars| Self::msm_small(bases, scalars),
+            |scalars| Self::checked_msm(bases, scalars),
+        )
+    }
+
+    /// Independent multi scalar multiplications jumping into the user-defined
+    /// `multi_msm_g1` hook, with a single call for all the jobs.
+    ///
+    /// Each job is checked as by `checked_msm`, with the limits applied to the
+    /// total number of bases. See [`batch::multi_msm`] for the errors.
+    pub fn multi_msm(
+        jobs: &[MsmJob<G1Affine<H>, <Self as CurveConfig>::ScalarField>],
+    ) -> Vec<Result<G1Projective<H>, HookError>> {
+        batch::multi_msm(
+            jobs,
+            &H::LIMITS,
+            H::multi_msm_g1,
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Multiplication of one base by many scalars jumping into the user-defined
+    /// `batch_mul_g1` hook, with a single call for all the scalars.
+    ///
+    /// The products are returned in affine form. See [`batch::batch_mul`] for the
+    /// errors.
+    pub fn batch_mul(
+        base: &G1Projective<H>,
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<Vec<G1Affine<H>>, HookError> {
+        batch::batch_mul(
+            scalars,
+            &H::LIMITS,
+            |scalars| H::batch_mul_g1(base, scalars),
+            |p| p.is_on_curve(),
+        )
+    }
+}
+
+fn x_minus_one(
+    x_is_negative: bool,
+    x_value: &'static [u64],
+) -> <ArkConfig as CurveConfig>::ScalarField {
+    let x = <ArkConfig as CurveConfig>::ScalarField::from_sign_and_limbs(!x_is_negative, x_value);
+    x - <ArkConfig as CurveConfig>::ScalarField::one()
+}
+
+pub fn endomorphism<H: CurveHooks>(p: &G1SWAffine<H>) -> G1SWAffine<H> {
+    // Endomorphism of the points on the curve.
+    // endomorphism_p(x,y) = (BETA * x, y)
+    // where BETA is a non-trivial cubic root of unity in fq.
+    let mut res = *p;
+    res.x *= BETA;
+    res
 }
 
 impl<H: CurveHooks> TECurveConfig for Config<H> {
@@ -1,14 +1,48 @@
 use crate::CurveHooks;
 
-use ark_bls12_377::g2::Config as ArkConfig;
-use ark_models_ext::{bls12, short_weierstrass::SWCurveConfig, CurveConfig};
-use ark_std::marker::PhantomData;
+use ark_bls12_377::{g2::Config as ArkConfig, Fq, Fq2};
+use ark_ec::AdditiveGroup;
+use ark_ff::{Field, MontFp};
+use ark_models_ext::{
+    batch,
+    batch::MsmJob,
+    bls12,
+    bls12::Bls12Config,
+    checked::{self, HookError},
+    short_weierstrass::SWCurveConfig,
+    validation, AffineRepr, CurveConfig, CurveGroup, PrimeGroup,
+};
+use ark_std::{marker::PhantomData, ops::Neg, vec::Vec};
 
 pub use ark_bls12_377::g2::{
     G2_GENERATOR_X, G2_GENERATOR_X_C0, G2_GENERATOR_X_C1, G2_GENERATOR_Y, G2_GENERATOR_Y_C0,
     G2_GENERATOR_Y_C1,
 };
 
+// PSI_X = u^((p-1)/3)
+const P_POWER_ENDOMORPHISM_COEFF_0: Fq2 = Fq2::new(
+    MontFp!(
+        "80949648264912719408558363140637477264845294720710499478137287262712535938301461879813459410946"
+    ),
+    Fq::ZERO,
+);
+
+// PSI_Y = u^((p-1)/2)
+const P_POWER_ENDOMORPHISM_COEFF_1: Fq2 = Fq2::new(
+    MontFp!(
+        "216465761340224619389371505802605247630151569547285782856803747159100223055385581585702401816380679166954762214499"
+    ),
+    Fq::ZERO,
+);
+
+// PSI_2_X = u^((p^2 - 1)/3)
+const DOUBLE_P_POWER_ENDOMORPHISM_COEFF_0: Fq2 = Fq2::new(
+    MontFp!(
+        "80949648264912719408558363140637477264845294720710499478137287262712535938301461879813459410945"
+    ),
+    Fq::ZERO,
+);
+
 pub type G2Affine<H> = bls12::G2Affine<crate::curves::Config<H>>;
 pub type G2Projective<H> = bls12::G2Projective<crate::curves::Config<H>>;
 
@@ -31,21 +65,19 @@ impl<H: CurveHooks> SWCurveConfig for Config<H> {
 
     /// Multi scalar multiplication jumping into the user-defined `msm_g2` hook.
     ///
-    /// On any internal error returns `Err(0)`.
+    /// Fails on any `checked_msm` failure, see [`checked::upstream_msm`].
     #[inline(always)]
     fn msm(bases: &[G2Affine<H>], scalars: &[Self::ScalarField]) -> Result<G2Projective<H>, usize> {
-        if bases.len() != scalars.len() {
-            return Err(bases.len().min(scalars.len()));
-        }
-        Ok(H::msm_g2(bases, scalars))
+        checked::upstream_msm(bases.len(), Self::checked_msm(bases, scalars))
     }
 
     /// Projective multiplication jumping into the user-defined `mul_projective_g2` hook.
     ///
-    /// On any internal error returns `Projective::zero()`.
+    /// The identity if the scalar exceeds the hooks limits, see
+    /// [`checked::upstream_mul`].
     #[inline(always)]
     fn mul_projective(base: &G2Projective<H>, scalar: &[u64]) -> G2Projective<H> {
-        H::mul_projective_g2(base, scalar)
+        checked::upstream_mul(base, scalar, Self::checked_mul(base, scalar))
     }
 
     /// Affine multiplication jumping into the user-defined `mul_projective_g2` hook.
@@ -60,4 +92,198 @@ impl<H: CurveHooks> SWCurveConfig for Config<H> {
     fn mul_by_a(elem: Self::BaseField) -> Self::BaseField {
         <ArkConfig as SWCurveConfig>::mul_by_a(elem)
     }
+
+    /// Subgroup check via the p-power endomorphism, i.e. `psi(P) = [x]P`.
+    ///
+    /// Based on Section 4 of https://eprint.iacr.org/2021/1130.pdf
+    #[inline(always)]
+    fn is_in_correct_subgroup_assuming_on_curve(point: &G2Affine<H>) -> bool {
+        let mut x_times_point = point.mul_bigint(crate::Config::<H>::X);
+        if crate::Config::<H>::X_IS_NEGATIVE {
+            x_times_point = -x_times_point;
+        }
+
+        let p_times_point = p_power_endomorphism(point);
+
+        x_times_point.eq(&p_times_point)
+    }
+
+    // Verbatim copy of upstream implementation.
+    //
+    // Can't call it directly because of different `Affine` configuration.
+    #[inline]
+    fn clear_cofactor(p: &G2Affine<H>) -> G2Affine<H> {
+        // Based on Section 4.1 of https://eprint.iacr.org/2017/419.pdf
+        // [h(ψ)]P = [x^2 − x − 1]P + [x − 1]ψ(P) + (ψ^2)(2P)
+
+        let x: &'static [u64] = crate::Config::<H>::X;
+        let p_projective = p.into_group();
+
+        // [x]P
+        let x_p = Config::mul_affine(p, x);
+        // ψ(P)
+        let psi_p = p_power_endomorphism(p);
+        // (ψ^2)(2P)
+        let mut psi2_p2 = double_p_power_endomorphism(&p_projective.double());
+
+        // tmp = [x]P + ψ(P)
+        let mut tmp = x_p;
+        tmp += &psi_p;
+
+        // tmp2 = [x^2]P + [x]ψ(P)
+        let mut tmp2: G2Projective<H> = tmp;
+        tmp2 = tmp2.mul_bigint(x);
+
+        // add up all the terms
+        psi2_p2 += tmp2;
+        psi2_p2 -= x_p;
+        psi2_p2 += &-psi_p;
+        (psi2_p2 - p_projective).into_affine()
+    }
+}
+
+impl<H: CurveHooks> Config<H> {
+    /// Multi scalar multiplication jumping into the user-defined `msm_g2` hook.
+    ///
+    /// Fails if the lengths differ or exceed the hooks limits, or if the hook
+    /// output is not on the curve.
+    pub fn checked_msm(
+        bases: &[G2Affine<H>],
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<G2Projective<H>, HookError> {
+        checked::msm(
+            bases,
+            scalars,
+            &H::LIMITS,
+            H::msm_g2,
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Projective multiplication jumping into the user-defined `mul_projective_g2` hook.
+    ///
+    /// Fails if the scalar exceeds the hooks limits or if the hook output is not
+    /// on the curve.
+    pub fn checked_mul(
+        base: &G2Projective<H>,
+        scalar: &[u64],
+    ) -> Result<G2Projective<H>, HookError> {
+        checked::mul(
+            scalar,
+            &H::LIMITS,
+            |scalar| H::mul_projective_g2(base, scalar),
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Batch normalization jumping into the user-defined `normalize_batch_g2` hook.
+    ///
+    /// See [`checked::normalize_batch`].
+    pub fn normalize_batch(points: &[G2Projective<H>]) -> Vec<G2Affine<H>> {
+        checked::normalize_batch(
+            points,
+            H::normalize_batch_g2,
+            validation::sw_is_normalization_of,
+        )
+    }
+
+    /// Multi scalar multiplication with 128-bit scalars jumping into the
+    /// user-defined `msm_small_g2` hook.
+    ///
+    /// Same checks and errors as `checked_msm`.
+    pub fn msm_small(
+        bases: &[G2Affine<H>],
+        scalars: &[u128],
+    ) -> Result<G2Projective<H>, HookError> {
+        checked::msm(
+            bases,
+            scalars,
+            &H::LIMITS,
+            H::msm_small_g2,
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Multi scalar multiplication jumping into the user-defined `msm_small_g2` hook
+    /// if all the scalars fit 128 bits, into the `msm_g2` hook otherwise.
+    pub fn msm_auto(
+        bases: &[G2Affine<H>],
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<G2Projective<H>, HookError> {
+        checked::msm_auto(
+            scalars,
+            |scalars| Self::msm_small(bases, scalars),
+            |scalars| Self::checked_msm(bases, scalars),
+        )
+    }
+
+    /// Independent multi scalar multiplications jumping into the user-defined
+    /// `multi_msm_g2` hook, with a single call for all the jobs.
+    ///
+    /// Each job is checked as by `checked_msm`, with the limits applied to the
+    /// total number of bases. See [`batch::multi_msm`] for the errors.
+    pub fn multi_msm(
+        jobs: &[MsmJob<G2Affine<H>, <Self as CurveConfig>::ScalarField>],
+    ) -> Vec<Result<G2Projective<H>, HookError>> {
+        batch::multi_msm(
+            jobs,
+            &H::LIMITS,
+            H::multi_msm_g2,
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Multiplication of one base by many scalars jumping into the user-defined
+    /// `batch_mul_g2` hook, with a single call for all the scalars.
+    ///
+    /// The products are returned in affine form. See [`batch::batch_mul`] for the
+    /// errors.
+    pub fn batch_mul(
+        base: &G2Projective<H>,
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<Vec<G2Affine<H>>, HookError> {
+        batch::batch_mul(
+            scalars,
+            &H::LIMITS,
+            |scalars| H::batch_mul_g2(base, scalars),
+            |p| p.is_on_curve(),
+        )
+    }
+}
+
+/// psi(P) is the untwist-Frobenius-twist endomorhism on E'(Fq2)
+fn p_power_endomorphism<H: CurveHooks>(p: &G2Affine<H>) -> G2Affine<H> {
+    // The p-power endomorphism for G2 is defined as follows:
+    // 1. Note that G2 is defined on curve E': y^2 = x^3 + 1/u.
+    //    To map a point (x, y) in E' to (s, t) in E,
+    //    set s = x * (u ^ (1/3)), t = y * (u ^ (1/2)),
+    //    because E: y^2 = x^3 + 1.
+    // 2. Apply the Frobenius endomorphism (s, t) => (s', t'),
+    //    another point on curve E, where s' = s^p, t' = t^p.
+    // 3. Map the point from E back to E'; that is,
+    //    set x' = s' / (u ^ (1/3)), y' = t' / (u ^ (1/2)).
+    //
+    // To sum up, it maps
+    // (x,y) -> (x^p * (u ^ ((p-1)/3)), y^p * (u ^ ((p-1)/2)))
+    // as implemented in the code as follows.
+
+    let mut res = *p;
+    res.x.frobenius_map_in_place(1);
+    res.y.frobenius_map_in_place(1);
+
+    res.x *= P_POWER_ENDOMORPHISM_COEFF_0;
+    res.y *= P_POWER_ENDOMORPHISM_COEFF_1;
+
+    res
+}
+
+/// For a p-power endomorphism psi(P), compute psi(psi(P))
+fn double_p_power_endomorphism<H: CurveHooks>(p: &G2Projective<H>) -> G2Projective<H> {
+    let mut res = *p;
+
+    res.x *= DOUBLE_P_POWER_ENDOMORPHISM_COEFF_0;
+    // u^((p^2 - 1)/2) == -1
+    res.y = res.y.neg();
+
+    res
 }
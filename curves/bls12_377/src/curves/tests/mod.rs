@@ -0,0 +1,674 @@
+use crate::CurveHooks;
+use ark_algebra_test_templates::*;
+use ark_bls12_377::{
+    g1::Config as ArkG1Config, g2::Config as ArkG2Config, Bls12_377 as ArkBls12_377,
+};
+use ark_ff::{BigInteger, Field, One, PrimeField, Zero};
+use ark_models_ext::{
+    pairing::{Pairing, PairingOutput},
+    short_weierstrass::{Affine, SWCurveConfig},
+    AffineRepr, CurveConfig, CurveGroup,
+};
+use ark_std::{rand::Rng, test_rng, vec::Vec, UniformRand};
+use test_utils::mul_plain;
+
+mod eip2539_snapshots;
+
+struct TestHooks;
+
+type Bls12_377 = crate::Bls12_377<TestHooks>;
+type G1Projective = crate::G1Projective<TestHooks>;
+type G2Projective = crate::G2Projective<TestHooks>;
+type G1Affine = crate::G1Affine<TestHooks>;
+type G2Affine = crate::G2Affine<TestHooks>;
+type G1Config = crate::g1::Config<TestHooks>;
+type G2Config = crate::g2::Config<TestHooks>;
+
+impl CurveHooks for TestHooks {
+    fn multi_miller_loop(
+        g1: &[<Bls12_377 as Pairing>::G1Prepared],
+        g2: &[<Bls12_377 as Pairing>::G2Prepared],
+    ) -> <Bls12_377 as Pairing>::TargetField {
+        test_utils::multi_miller_loop_generic::<Bls12_377, ArkBls12_377>(g1, g2)
+    }
+
+    fn final_exponentiation(
+        target: <Bls12_377 as Pairing>::TargetField,
+    ) -> <Bls12_377 as Pairing>::TargetField {
+        test_utils::final_exponentiation_generic::<Bls12_377, ArkBls12_377>(target)
+    }
+
+    fn msm_g1(
+        bases: &[G1Affine],
+        scalars: &[<G1Config as CurveConfig>::ScalarField],
+    ) -> G1Projective {
+        test_utils::msm_sw_generic::<G1Config, ArkG1Config>(bases, scalars)
+    }
+
+    fn msm_g2(
+        bases: &[G2Affine],
+        scalars: &[<G2Config as CurveConfig>::ScalarField],
+    ) -> G2Projective {
+        test_utils::msm_sw_generic::<G2Config, ArkG2Config>(bases, scalars)
+    }
+
+    fn mul_projective_g1(base: &G1Projective, scalar: &[u64]) -> G1Projective {
+        test_utils::mul_projective_sw_generic::<G1Config, ArkG1Config>(base, scalar)
+    }
+
+    fn mul_projective_g2(base: &G2Projective, scalar: &[u64]) -> G2Projective {
+        test_utils::mul_projective_sw_generic::<G2Config, ArkG2Config>(base, scalar)
+    }
+
+    fn normalize_batch_g1(points: &[G1Projective]) -> Vec<G1Affine> {
+        test_utils::normalize_batch_sw_generic::<G1Config, ArkG1Config>(points)
+    }
+
+    fn normalize_batch_g2(points: &[G2Projective]) -> Vec<G2Affine> {
+        test_utils::normalize_batch_sw_generic::<G2Config, ArkG2Config>(points)
+    }
+}
+
+test_group!(g1; G1Projective; sw);
+test_group!(g2; G2Projective; sw);
+test_group!(pairing_output; PairingOutput<Bls12_377>; msm);
+test_pairing!(pairing; crate::Bls12_377<super::TestHooks>);
+
+#[test]
+fn differential() {
+    use test_utils::differential as diff;
+
+    diff::group::<G1Projective, ark_bls12_377::G1Projective>(&diff::sw_samples::<ArkG1Config>());
+    diff::group::<G2Projective, ark_bls12_377::G2Projective>(&diff::sw_samples::<ArkG2Config>());
+    diff::pairing::<Bls12_377, ArkBls12_377>();
+    diff::miller_loop_accumulator::<Bls12_377, ArkBls12_377>();
+    diff::torus::<Bls12_377, ArkBls12_377, _>();
+    diff::gt_subgroup::<Bls12_377, ArkBls12_377, _>();
+    diff::raw_field::<<Bls12_377 as Pairing>::TargetField, <ArkBls12_377 as Pairing>::TargetField>(
+    );
+    diff::final_exponentiation_batch::<Bls12_377, ArkBls12_377>(
+        Bls12_377::final_exponentiation_batch,
+    );
+    diff::normalize_batch::<G1Projective, ark_bls12_377::G1Projective>(
+        &diff::sw_samples::<ArkG1Config>(),
+        G1Config::normalize_batch,
+    );
+    diff::normalize_batch::<G2Projective, ark_bls12_377::G2Projective>(
+        &diff::sw_samples::<ArkG2Config>(),
+        G2Config::normalize_batch,
+    );
+    diff::msm_small::<G1Projective, ark_bls12_377::G1Projective>(
+        &diff::sw_samples::<ArkG1Config>(),
+        G1Config::msm_small,
+        G1Config::msm_auto,
+    );
+    diff::multi_msm::<G1Projective, ark_bls12_377::G1Projective>(
+        &diff::sw_samples::<ArkG1Config>(),
+        G1Config::multi_msm,
+    );
+    diff::batch_mul::<G1Projective, ark_bls12_377::G1Projective>(
+        &diff::sw_samples::<ArkG1Config>(),
+        G1Config::batch_mul,
+    );
+    diff::msm_small::<G2Projective, ark_bls12_377::G2Projective>(
+        &diff::sw_samples::<ArkG2Config>(),
+        G2Config::msm_small,
+        G2Config::msm_auto,
+    );
+    diff::multi_msm::<G2Projective, ark_bls12_377::G2Projective>(
+        &diff::sw_samples::<ArkG2Config>(),
+        G2Config::multi_msm,
+    );
+    diff::batch_mul::<G2Projective, ark_bls12_377::G2Projective>(
+        &diff::sw_samples::<ArkG2Config>(),
+        G2Config::batch_mul,
+    );
+}
+
+#[test]
+fn hooks_conformance() {
+    use ark_models_ext::metering::{HookOp, LocalMeter, MeteredHooks};
+
+    type Hooks = MeteredHooks<TestHooks, LocalMeter>;
+
+    test_utils::conformance::miller_loop_accumulator::<crate::Bls12_377<Hooks>>();
+    test_utils::conformance::final_exponentiation_batch(
+        crate::Bls12_377::<Hooks>::final_exponentiation_batch,
+    );
+    test_utils::conformance::gt_is_in_subgroup::<crate::Bls12_377<Hooks>>();
+    test_utils::conformance::sw::<crate::g1::Config<Hooks>>(HookOp::MsmG1, HookOp::MulProjectiveG1);
+    test_utils::conformance::sw::<crate::g2::Config<Hooks>>(HookOp::MsmG2, HookOp::MulProjectiveG2);
+    test_utils::conformance::normalize_batch(
+        HookOp::NormalizeBatchG1,
+        crate::g1::Config::<Hooks>::normalize_batch,
+    );
+    test_utils::conformance::normalize_batch(
+        HookOp::NormalizeBatchG2,
+        crate::g2::Config::<Hooks>::normalize_batch,
+    );
+    test_utils::conformance::msm_small(
+        HookOp::MsmSmallG1,
+        HookOp::MsmG1,
+        crate::g1::Config::<Hooks>::msm_small,
+        crate::g1::Config::<Hooks>::msm_auto,
+    );
+    test_utils::conformance::multi_msm(HookOp::MultiMsmG1, crate::g1::Config::<Hooks>::multi_msm);
+    test_utils::conformance::batch_mul(HookOp::BatchMulG1, crate::g1::Config::<Hooks>::batch_mul);
+    test_utils::conformance::msm_small(
+        HookOp::MsmSmallG2,
+        HookOp::MsmG2,
+        crate::g2::Config::<Hooks>::msm_small,
+        crate::g2::Config::<Hooks>::msm_auto,
+    );
+    test_utils::conformance::multi_msm(HookOp::MultiMsmG2, crate::g2::Config::<Hooks>::multi_msm);
+    test_utils::conformance::batch_mul(HookOp::BatchMulG2, crate::g2::Config::<Hooks>::batch_mul);
+}
+
+#[test]
+fn hook_free_decoding() {
+    use ark_models_ext::metering::{LocalMeter, MeteredHooks};
+    use test_utils::differential as diff;
+
+    type Hooks = MeteredHooks<TestHooks, LocalMeter>;
+
+    test_utils::conformance::decoding::<crate::G1Projective<Hooks>, ark_bls12_377::G1Projective>(
+        &diff::sw_samples::<ArkG1Config>(),
+    );
+    test_utils::conformance::decoding::<crate::G2Projective<Hooks>, ark_bls12_377::G2Projective>(
+        &diff::sw_samples::<ArkG2Config>(),
+    );
+}
+
+#[test]
+fn no_panic_decoding() {
+    use ark_models_ext::{pairing::PairingOutput, torus::TorusOutput, AffineRepr};
+
+    let p = G1Affine::generator();
+    test_utils::no_panic::deserialize(&[p, G1Affine::zero()]);
+    test_utils::no_panic::raw(&[p, G1Affine::zero()]);
+    test_utils::no_panic::deserialize(&[p.into_group(), G1Projective::default()]);
+    test_utils::no_panic::raw(&[p.into_group(), G1Projective::default()]);
+    let q = G2Affine::generator();
+    test_utils::no_panic::deserialize(&[q, G2Affine::zero()]);
+    test_utils::no_panic::raw(&[q, G2Affine::zero()]);
+    test_utils::no_panic::deserialize(&[q.into_group(), G2Projective::default()]);
+    test_utils::no_panic::raw(&[q.into_group(), G2Projective::default()]);
+    let out = Bls12_377::pairing(p, q);
+    test_utils::no_panic::deserialize(&[out, PairingOutput::default()]);
+    test_utils::no_panic::raw(&[out.0, Default::default()]);
+    test_utils::no_panic::deserialize(&[TorusOutput(out), TorusOutput(PairingOutput::default())]);
+    use crate::zcash::Zcash;
+    test_utils::no_panic::deserialize(&[Zcash(p), Zcash(G1Affine::zero())]);
+    test_utils::no_panic::deserialize(&[Zcash(q), Zcash(G2Affine::zero())]);
+}
+
+fn sample_unchecked<P: SWCurveConfig>() -> Affine<P> {
+    let mut rng = test_rng();
+    loop {
+        let x = P::BaseField::rand(&mut rng);
+        let greatest = rng.gen();
+        if let Some(p) = Affine::get_point_from_x_unchecked(x, greatest) {
+            return p;
+        }
+    }
+}
+
+fn in_subgroup_naive<P: SWCurveConfig>(p: &Affine<P>) -> bool {
+    mul_plain(p.into_group(), P::ScalarField::MODULUS).is_zero()
+}
+
+#[test]
+fn eip2539() {
+    use crate::eip2539::{self, Error};
+    type Fr = <G1Config as CurveConfig>::ScalarField;
+
+    let mut rng = test_rng();
+    let scalar = |s: &Fr| {
+        let be = s.into_bigint().to_bytes_be();
+        let mut res = [0; eip2539::SCALAR_LEN];
+        res[eip2539::SCALAR_LEN - be.len()..].copy_from_slice(&be);
+        res
+    };
+    let p: Vec<G1Affine> = (0..3).map(|_| G1Affine::rand(&mut rng)).collect();
+    let q: Vec<G2Affine> = (0..3).map(|_| G2Affine::rand(&mut rng)).collect();
+    let s: Vec<Fr> = (0..3).map(|_| Fr::rand(&mut rng)).collect();
+    let g1 = |p: &G1Affine| eip2539::encode_g1(p);
+    let g2 = |q: &G2Affine| eip2539::encode_g2(q);
+
+    // Addition, including the identity.
+    let zero = G1Affine::zero();
+    let input = [g1(&p[0]), g1(&p[1])].concat();
+    let expected = g1(&(p[0] + p[1]).into_affine());
+    assert_eq!(eip2539::g1_add::<TestHooks>(&input), Ok(expected));
+    let input = [g1(&p[0]), g1(&zero)].concat();
+    assert_eq!(eip2539::g1_add::<TestHooks>(&input), Ok(g1(&p[0])));
+    let input = [g2(&q[0]), g2(&q[1])].concat();
+    let expected = g2(&(q[0] + q[1]).into_affine());
+    assert_eq!(eip2539::g2_add::<TestHooks>(&input), Ok(expected));
+
+    // MSM, with a scalar not less than the group order.
+    let mut input: Vec<u8> = (0..3)
+        .flat_map(|i| [&g1(&p[i])[..], &scalar(&s[i])].concat())
+        .collect();
+    input.extend(g1(&p[0]));
+    input.extend([0xff; eip2539::SCALAR_LEN]);
+    let big = Fr::from_be_bytes_mod_order(&[0xff; eip2539::SCALAR_LEN]);
+    let expected = (0..3).fold(mul_plain(p[0].into_group(), big.into_bigint()), |acc, i| {
+        acc + mul_plain(p[i].into_group(), s[i].into_bigint())
+    });
+    assert_eq!(
+        eip2539::g1_msm::<TestHooks>(&input),
+        Ok(g1(&expected.into_affine()))
+    );
+    let input: Vec<u8> = (0..3)
+        .flat_map(|i| [&g2(&q[i])[..], &scalar(&s[i])].concat())
+        .collect();
+    let expected = (0..3).fold(G2Projective::zero(), |acc, i| {
+        acc + mul_plain(q[i].into_group(), s[i].into_bigint())
+    });
+    assert_eq!(
+        eip2539::g2_msm::<TestHooks>(&input),
+        Ok(g2(&expected.into_affine()))
+    );
+
+    // Pairing check.
+    let mut success = [0; eip2539::PAIRING_LEN];
+    success[eip2539::PAIRING_LEN - 1] = 1;
+    let input = [&g1(&p[0])[..], &g2(&q[0]), &g1(&-p[0]), &g2(&q[0])].concat();
+    assert_eq!(eip2539::pairing::<TestHooks>(&input), Ok(success));
+    let input = [
+        &g1(&p[0])[..],
+        &g2(&q[0]),
+        &g1(&p[1]),
+        &g2(&G2Affine::zero()),
+    ]
+    .concat();
+    assert_eq!(
+        eip2539::pairing::<TestHooks>(&input),
+        Ok([0; eip2539::PAIRING_LEN])
+    );
+
+    // Failures.
+    assert_eq!(eip2539::g1_add::<TestHooks>(&[]), Err(Error::InputLength));
+    assert_eq!(eip2539::g1_msm::<TestHooks>(&[]), Err(Error::InputLength));
+    assert_eq!(eip2539::pairing::<TestHooks>(&[]), Err(Error::InputLength));
+    assert_eq!(
+        eip2539::g1_add::<TestHooks>(&[0; 2 * eip2539::G1_LEN + 1]),
+        Err(Error::InputLength)
+    );
+    let modulus = <crate::Fq as PrimeField>::MODULUS.to_bytes_be();
+    let mut input = [0; 2 * eip2539::G1_LEN];
+    input[eip2539::FP_LEN - modulus.len()..eip2539::FP_LEN].copy_from_slice(&modulus);
+    assert_eq!(
+        eip2539::g1_add::<TestHooks>(&input),
+        Err(Error::FieldEncoding)
+    );
+    let mut input = [0; 2 * eip2539::G1_LEN];
+    input[0] = 1;
+    assert_eq!(
+        eip2539::g1_add::<TestHooks>(&input),
+        Err(Error::FieldPadding)
+    );
+    let mut input = [0; 2 * eip2539::G1_LEN];
+    input[eip2539::FP_LEN - 1] = 1;
+    input[eip2539::G1_LEN - 1] = 1;
+    assert_eq!(eip2539::g1_add::<TestHooks>(&input), Err(Error::NotOnCurve));
+
+    // Points outside of the subgroup are only accepted by the addition.
+    let outside = (1u64..)
+        .filter_map(|x| Affine::<G1Config>::get_point_from_x_unchecked(x.into(), false))
+        .find(|p| !p.is_in_correct_subgroup_assuming_on_curve())
+        .unwrap();
+    let input = [g1(&outside), g1(&zero)].concat();
+    assert_eq!(eip2539::g1_add::<TestHooks>(&input), Ok(g1(&outside)));
+    let input = [&g1(&outside)[..], &scalar(&s[0])].concat();
+    assert_eq!(
+        eip2539::g1_msm::<TestHooks>(&input),
+        Err(Error::NotInSubgroup)
+    );
+    let input = [&g1(&outside)[..], &g2(&q[0])].concat();
+    assert_eq!(
+        eip2539::pairing::<TestHooks>(&input),
+        Err(Error::NotInSubgroup)
+    );
+}
+
+#[test]
+fn zcash_encoding() {
+    use crate::zcash::Zcash;
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+    fn bytes<T>(p: T, compress: Compress) -> Vec<u8>
+    where
+        Zcash<T>: CanonicalSerialize,
+    {
+        let mut buf = Vec::new();
+        Zcash(p).serialize_with_mode(&mut buf, compress).unwrap();
+        buf
+    }
+    fn read<T>(buf: &[u8], compress: Compress) -> Option<T>
+    where
+        Zcash<T>: CanonicalDeserialize,
+    {
+        Zcash::<T>::deserialize_with_mode(buf, compress, Validate::Yes)
+            .ok()
+            .map(|p| p.0)
+    }
+
+    // Generators and points at infinity.
+    const G1: &str = "008848defe740a67c8fc6225bf87ff5485951e2caa9d41bb188282c8bd37cb5cd5481512ffcd394eeab9b16eb21be9ef";
+    const G1_Y: &str = "01914a69c5102eff1f674f5d30afeec4bd7fb348ca3e52d96d182ad44fb82305c2fe3d3634a9591afd82de55559c8ea6";
+    const G2: &str = "00ea6040e700403170dc5a51b1b140d5532777ee6651cecbe7223ece0799c9de5cf89984bff76fe6b26bfefa6ea16afe018480be71c785fec89630a2a3841d01c565f071203e50317ea501f557db6b9b71889f52bb53540274e3e48f7c005196";
+    const G2_Y: &str = "00f8169fd28355189e549da3151a70aa61ef11ac3d591bf12463b01acee304c24279b83f5e52270bd9a1cdd185eb8f9300690d665d446f7bd960736bcbb2efb4de03ed7274b49a58e458c282f832d204f2cf88886d8c7c2ef094094409fd4ddf";
+    let vectors = [
+        (
+            bytes(G1Affine::generator(), Compress::Yes),
+            hex(&format!("a0{}", &G1[2..])),
+        ),
+        (
+            bytes(G1Affine::generator(), Compress::No),
+            hex(&format!("{G1}{G1_Y}")),
+        ),
+        (
+            bytes(G2Affine::generator(), Compress::Yes),
+            hex(&format!("a0{}", &G2[2..])),
+        ),
+        (
+            bytes(G2Affine::generator(), Compress::No),
+            hex(&format!("{G2}{G2_Y}")),
+        ),
+        (
+            bytes(G1Affine::zero(), Compress::Yes),
+            [vec![0xc0], vec![0; 47]].concat(),
+        ),
+        (
+            bytes(G1Affine::zero(), Compress::No),
+            [vec![0x40], vec![0; 95]].concat(),
+        ),
+    ];
+    for (res, expected) in vectors {
+        assert_eq!(res, expected);
+    }
+
+    // Round trip, including the point at infinity and both signs of `y`.
+    let mut rng = test_rng();
+    for compress in [Compress::Yes, Compress::No] {
+        for _ in 0..10 {
+            let p = G1Affine::rand(&mut rng);
+            for p in [p, -p, G1Affine::zero()] {
+                assert_eq!(read::<G1Affine>(&bytes(p, compress), compress), Some(p));
+            }
+            let q = G2Affine::rand(&mut rng);
+            for q in [q, -q, G2Affine::zero()] {
+                assert_eq!(read::<G2Affine>(&bytes(q, compress), compress), Some(q));
+            }
+        }
+    }
+
+    // Strict decoding.
+    let p = G1Affine::rand(&mut rng);
+    let compressed = bytes(p, Compress::Yes);
+    let uncompressed = bytes(p, Compress::No);
+    assert!(read::<G1Affine>(&compressed, Compress::No).is_none());
+    assert!(read::<G1Affine>(&uncompressed[..48], Compress::Yes).is_none());
+    let mut buf = uncompressed.clone();
+    buf[0] |= 0x20;
+    assert!(read::<G1Affine>(&buf, Compress::No).is_none());
+    let mut buf = bytes(G1Affine::zero(), Compress::Yes);
+    buf[47] = 1;
+    assert!(read::<G1Affine>(&buf, Compress::Yes).is_none());
+    let mut buf = bytes(G1Affine::zero(), Compress::Yes);
+    buf[0] |= 0x20;
+    assert!(read::<G1Affine>(&buf, Compress::Yes).is_none());
+    let mut buf = <crate::Fq as PrimeField>::MODULUS.to_bytes_be();
+    buf[0] |= 0x80;
+    assert!(read::<G1Affine>(&buf, Compress::Yes).is_none());
+    let mut buf = uncompressed;
+    buf[95] ^= 1;
+    assert!(read::<G1Affine>(&buf, Compress::No).is_none());
+}
+
+#[test]
+fn test_g1_endomorphism_beta() {
+    assert!(crate::g1::BETA.pow([3u64]).is_one());
+    assert!(!crate::g1::BETA.is_one());
+}
+
+#[test]
+fn test_g1_subgroup_check_matches_naive() {
+    let mut rng = test_rng();
+    let p = G1Projective::rand(&mut rng).into_affine();
+    assert!(p.is_in_correct_subgroup_assuming_on_curve());
+    assert!(G1Affine::zero().is_in_correct_subgroup_assuming_on_curve());
+
+    const SAMPLES: usize = 20;
+    for _ in 0..SAMPLES {
+        let p: G1Affine = sample_unchecked();
+        assert_eq!(
+            p.is_in_correct_subgroup_assuming_on_curve(),
+            in_subgroup_naive(&p)
+        );
+        let q = mul_plain(p.into_group(), crate::g1::Config::<TestHooks>::COFACTOR).into_affine();
+        assert!(q.is_in_correct_subgroup_assuming_on_curve());
+    }
+}
+
+#[test]
+fn test_g2_subgroup_check_matches_naive() {
+    let mut rng = test_rng();
+    let p = G2Projective::rand(&mut rng).into_affine();
+    assert!(p.is_in_correct_subgroup_assuming_on_curve());
+    assert!(G2Affine::zero().is_in_correct_subgroup_assuming_on_curve());
+
+    const SAMPLES: usize = 10;
+    for _ in 0..SAMPLES {
+        let p: G2Affine = sample_unchecked();
+        assert_eq!(
+            p.is_in_correct_subgroup_assuming_on_curve(),
+            in_subgroup_naive(&p)
+        );
+    }
+}
+
+#[test]
+fn test_cofactor_clearing_g1() {
+    // Multiplying by h_eff = x - 1 and clearing the cofactor should yield the
+    // same result, which must be in the subgroup.
+    let h_eff: &'static [u64] = &[0x8508c00000000000];
+    const SAMPLES: usize = 20;
+    for _ in 0..SAMPLES {
+        let p: G1Affine = sample_unchecked();
+        let optimised = p.clear_cofactor();
+        assert_eq!(optimised.into_group(), mul_plain(p.into_group(), h_eff));
+        assert!(optimised.is_on_curve());
+        assert!(in_subgroup_naive(&optimised));
+    }
+}
+
+#[test]
+fn test_cofactor_clearing_g2() {
+    // Multiplying by h_eff and clearing the cofactor by the efficient
+    // endomorphism-based method should yield the same result.
+    let h_eff: &'static [u64] = &[
+        0x1e34800000000000,
+        0xcf664765b0000003,
+        0x8e8e73ad8a538800,
+        0x78ba279637388559,
+        0xb85860aaaad29276,
+        0xf7ee7c4b03103b45,
+        0x8f6ade35a5c7d769,
+        0xa951764c46f4edd2,
+        0x53648d3d9502abfb,
+        0x1f60243677e306,
+    ];
+    const SAMPLES: usize = 10;
+    for _ in 0..SAMPLES {
+        let p: G2Affine = sample_unchecked();
+        let optimised = p.clear_cofactor();
+        assert_eq!(optimised.into_group(), mul_plain(p.into_group(), h_eff));
+        assert!(optimised.is_on_curve());
+        assert!(in_subgroup_naive(&optimised));
+    }
+}
+
+mod faults {
+    use super::{in_subgroup_naive, TestHooks};
+    use crate::CurveHooks;
+
+    use ark_ff::Zero;
+    use ark_models_ext::{
+        bls12::{G1Prepared, G2Prepared},
+        cast,
+        metering::HookOp,
+        pairing::Pairing,
+        short_weierstrass::{Affine, SWCurveConfig},
+        AffineRepr, CurveConfig, CurveGroup,
+    };
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use ark_std::{test_rng, vec::Vec, UniformRand};
+    use test_utils::faults::{self, Fault, FaultyHooks};
+
+    type Bls12_377 = crate::Bls12_377<FaultyHooks<TestHooks>>;
+    type G1Affine = crate::G1Affine<FaultyHooks<TestHooks>>;
+    type G2Affine = crate::G2Affine<FaultyHooks<TestHooks>>;
+
+    const FAULTS: [Fault; 5] = [
+        Fault::Identity,
+        Fault::Random,
+        Fault::OffCurve,
+        Fault::NonSubgroup,
+        Fault::Fail,
+    ];
+
+    impl<H: CurveHooks> CurveHooks for FaultyHooks<H> {
+        fn multi_miller_loop(
+            g1: &[<crate::Bls12_377<Self> as Pairing>::G1Prepared],
+            g2: &[<crate::Bls12_377<Self> as Pairing>::G2Prepared],
+        ) -> <crate::Bls12_377<Self> as Pairing>::TargetField {
+            let g1: Vec<_> = g1
+                .iter()
+                .map(|p| G1Prepared(cast::sw_affine(&p.0)))
+                .collect();
+            let g2: Vec<_> = g2
+                .iter()
+                .map(|p| G2Prepared(cast::sw_affine(&p.0)))
+                .collect();
+            let res = H::multi_miller_loop(&g1, &g2);
+            faults::faulty_target(HookOp::MultiMillerLoop, res)
+        }
+
+        fn final_exponentiation(
+            target: <crate::Bls12_377<Self> as Pairing>::TargetField,
+        ) -> <crate::Bls12_377<Self> as Pairing>::TargetField {
+            let res = H::final_exponentiation(target);
+            faults::faulty_target(HookOp::FinalExponentiation, res)
+        }
+
+        fn msm_g1(
+            bases: &[crate::G1Affine<Self>],
+            scalars: &[<crate::g1::Config<Self> as CurveConfig>::ScalarField],
+        ) -> crate::G1Projective<Self> {
+            let res = H::msm_g1(&cast::sw_affine_slice(bases), scalars);
+            faults::faulty_sw(HookOp::MsmG1, cast::sw_projective(&res))
+        }
+
+        fn msm_g2(
+            bases: &[crate::G2Affine<Self>],
+            scalars: &[<crate::g2::Config<Self> as CurveConfig>::ScalarField],
+        ) -> crate::G2Projective<Self> {
+            let res = H::msm_g2(&cast::sw_affine_slice(bases), scalars);
+            faults::faulty_sw(HookOp::MsmG2, cast::sw_projective(&res))
+        }
+
+        fn mul_projective_g1(
+            base: &crate::G1Projective<Self>,
+            scalar: &[u64],
+        ) -> crate::G1Projective<Self> {
+            let res = H::mul_projective_g1(&cast::sw_projective(base), scalar);
+            faults::faulty_sw(HookOp::MulProjectiveG1, cast::sw_projective(&res))
+        }
+
+        fn mul_projective_g2(
+            base: &crate::G2Projective<Self>,
+            scalar: &[u64],
+        ) -> crate::G2Projective<Self> {
+            let res = H::mul_projective_g2(&cast::sw_projective(base), scalar);
+            faults::faulty_sw(HookOp::MulProjectiveG2, cast::sw_projective(&res))
+        }
+    }
+
+    /// Checks `e(a G1, G2) * e(-G1, b G2) == 1`, which holds iff `a == b`.
+    fn verify(a: u64, b: u64) -> Option<bool> {
+        let g1 = G1Affine::generator();
+        let g2 = G2Affine::generator();
+        let a_g1 = (g1 * crate::Fr::from(a)).into_affine();
+        let b_g2 = (g2 * crate::Fr::from(b)).into_affine();
+        let f = Bls12_377::multi_miller_loop([a_g1, -g1], [g2, b_g2]);
+        Bls12_377::final_exponentiation(f).map(|out| out.is_zero())
+    }
+
+    fn non_subgroup_point<P: SWCurveConfig>() -> Affine<P> {
+        let mut rng = test_rng();
+        loop {
+            let x = P::BaseField::rand(&mut rng);
+            if let Some(p) = Affine::<P>::get_point_from_x_unchecked(x, false) {
+                if !in_subgroup_naive(&p) {
+                    return p;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn faulty_pairing_never_accepts_forged_equation() {
+        faults::clear();
+        assert_eq!(verify(3, 3), Some(true));
+        assert_eq!(verify(3, 5), Some(false));
+
+        for op in [HookOp::MultiMillerLoop, HookOp::FinalExponentiation] {
+            for fault in FAULTS {
+                // A final exponentiation returning one is indistinguishable from a
+                // legit output, the hooks are trusted not to do so.
+                if op == HookOp::FinalExponentiation && fault == Fault::Identity {
+                    continue;
+                }
+                faults::inject(op, fault, 0);
+                let res = verify(3, 5);
+                faults::clear();
+                assert_ne!(res, Some(true), "{op:?} {fault:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn faulty_mul_never_validates_non_subgroup_points() {
+        faults::clear();
+        let p1: G1Affine = non_subgroup_point();
+        let p2: G2Affine = non_subgroup_point();
+        let mut buf1 = Vec::new();
+        p1.serialize_compressed(&mut buf1).unwrap();
+        let mut buf2 = Vec::new();
+        p2.serialize_compressed(&mut buf2).unwrap();
+
+        let check1 = || p1.is_in_correct_subgroup_assuming_on_curve();
+        let decode1 = || G1Affine::deserialize_compressed(&buf1[..]).is_ok();
+        let check2 = || p2.is_in_correct_subgroup_assuming_on_curve();
+        let decode2 = || G2Affine::deserialize_compressed(&buf2[..]).is_ok();
+
+        for fault in FAULTS {
+            for nth in [0, 1, 2] {
+                faults::inject(HookOp::MulProjectiveG1, fault, nth);
+                assert!(!check1() && !decode1());
+                faults::inject(HookOp::MulProjectiveG2, fault, nth);
+                assert!(!check2() && !decode2());
+            }
+        }
+        faults::clear();
+    }
+}
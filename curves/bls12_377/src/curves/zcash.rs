@@ -0,0 +1,186 @@
+//! Zcash-style flagged encoding of the points.
+//!
+//! The points serialization is the upstream *Arkworks* one. [`Zcash`] wraps an
+//! affine point to serialize it in the flagged format used by *BLS12-381* in
+//! Zcash, as expected by some *BLS12-377* consumers (e.g. Celo light clients).
+//!
+//! Base field elements are encoded as 48 big endian bytes, `Fp2` elements as
+//! `c1 || c0`. A compressed point is encoded as its `x` coordinate, an uncompressed
+//! one as `x || y`. The three most significant bits of the first byte are flags:
+//! - compression, set for the compressed encoding;
+//! - infinity, set for the point at infinity, whose other bits are all zero;
+//! - sort, set for a compressed point whose `y` is the lexicographically largest
+//!   between `y` and `-y`.
+//!
+//! Decoding is strict: flags not matching the encoding, points at infinity with
+//! any other bit set and non-canonical field elements are rejected.
+
+use crate::{Fq, Fq2};
+use ark_ff::{BigInteger, Field, PrimeField};
+use ark_models_ext::{
+    short_weierstrass::{Affine, SWCurveConfig},
+    AffineRepr,
+};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+use ark_std::vec;
+
+const COMPRESSED: u8 = 1 << 7;
+const INFINITY: u8 = 1 << 6;
+const LARGEST: u8 = 1 << 5;
+const FLAGS: u8 = COMPRESSED | INFINITY | LARGEST;
+
+/// Longest supported encoding, i.e. an uncompressed G2 point.
+const MAX_LEN: usize = 2 * Fq2::LEN;
+
+/// Base fields of the points encodable by [`Zcash`].
+pub trait ZcashField: Field {
+    /// Encoded length.
+    const LEN: usize;
+
+    /// Write the big endian encoding to `out`, which is `LEN` bytes long.
+    fn write_be(&self, out: &mut [u8]);
+
+    /// Read a big endian encoding of `LEN` bytes. `None` if not canonical or if
+    /// `bytes` is not `LEN` bytes long.
+    fn read_be(bytes: &[u8]) -> Option<Self>;
+}
+
+impl ZcashField for Fq {
+    const LEN: usize = 48;
+
+    fn write_be(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.into_bigint().to_bytes_be());
+    }
+
+    fn read_be(bytes: &[u8]) -> Option<Self> {
+        let bytes: &[u8; 48] = bytes.try_into().ok()?;
+        let mut limbs = <Fq as PrimeField>::BigInt::zero();
+        for (limb, chunk) in limbs.as_mut().iter_mut().zip(bytes.rchunks_exact(8)) {
+            *limb = u64::from_be_bytes(chunk.try_into().expect("chunks of 8 bytes; qed"));
+        }
+        Fq::from_bigint(limbs)
+    }
+}
+
+impl ZcashField for Fq2 {
+    const LEN: usize = 2 * Fq::LEN;
+
+    fn write_be(&self, out: &mut [u8]) {
+        let (c1, c0) = out.split_at_mut(Fq::LEN);
+        self.c1.write_be(c1);
+        self.c0.write_be(c0);
+    }
+
+    fn read_be(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < Fq::LEN {
+            return None;
+        }
+        let (c1, c0) = bytes.split_at(Fq::LEN);
+        Some(Fq2::new(Fq::read_be(c0)?, Fq::read_be(c1)?))
+    }
+}
+
+/// Affine point serialized with the Zcash-style flagged encoding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Zcash<T>(pub T);
+
+impl<P: SWCurveConfig> Valid for Zcash<Affine<P>>
+where
+    P::BaseField: ZcashField,
+{
+    fn check(&self) -> Result<(), SerializationError> {
+        self.0.check()
+    }
+}
+
+impl<P: SWCurveConfig> CanonicalSerialize for Zcash<Affine<P>>
+where
+    P::BaseField: ZcashField,
+{
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        let len = P::BaseField::LEN;
+        let mut bytes = vec![0; self.serialized_size(compress)];
+        match self.0.xy() {
+            None => bytes[0] = INFINITY,
+            Some((x, y)) => {
+                x.write_be(&mut bytes[..len]);
+                match compress {
+                    Compress::Yes if y > -y => bytes[0] |= LARGEST,
+                    Compress::Yes => (),
+                    Compress::No => y.write_be(&mut bytes[len..]),
+                }
+            }
+        }
+        if compress == Compress::Yes {
+            bytes[0] |= COMPRESSED;
+        }
+        Ok(writer.write_all(&bytes)?)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        match compress {
+            Compress::Yes => P::BaseField::LEN,
+            Compress::No => 2 * P::BaseField::LEN,
+        }
+    }
+}
+
+impl<P: SWCurveConfig> CanonicalDeserialize for Zcash<Affine<P>>
+where
+    P::BaseField: ZcashField,
+{
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let len = P::BaseField::LEN;
+        let mut buf = [0; MAX_LEN];
+        let bytes = match compress {
+            Compress::Yes => buf.get_mut(..len),
+            Compress::No => buf.get_mut(..2 * len),
+        };
+        let bytes = bytes.ok_or(SerializationError::InvalidData)?;
+        reader.read_exact(bytes)?;
+        let [first, ..] = bytes else {
+            return Err(SerializationError::InvalidData);
+        };
+        let flags = *first & FLAGS;
+        *first &= !FLAGS;
+
+        if (flags & COMPRESSED != 0) != (compress == Compress::Yes) {
+            return Err(SerializationError::UnexpectedFlags);
+        }
+        let largest = flags & LARGEST != 0;
+        let p = if flags & INFINITY != 0 {
+            if largest || bytes.iter().any(|b| *b != 0) {
+                return Err(SerializationError::InvalidData);
+            }
+            Affine::identity()
+        } else {
+            let (x, y) = bytes.split_at(len);
+            let x = P::BaseField::read_be(x).ok_or(SerializationError::InvalidData)?;
+            match compress {
+                Compress::Yes => Affine::get_point_from_x_unchecked(x, largest)
+                    .ok_or(SerializationError::InvalidData)?,
+                Compress::No if largest => return Err(SerializationError::UnexpectedFlags),
+                Compress::No => {
+                    let y = P::BaseField::read_be(y).ok_or(SerializationError::InvalidData)?;
+                    Affine::new_unchecked(x, y)
+                }
+            }
+        };
+        let p = Zcash(p);
+        if validate == Validate::Yes {
+            p.check()?;
+        }
+        Ok(p)
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     util::{
-        read_g1_compressed, read_g1_uncompressed, serialize_fq, EncodingFlags, G1_SERIALIZED_SIZE,
+        read_g1_compressed, read_g1_uncompressed, serialize_fq, Encoding, EncodingFlags,
+        G1_SERIALIZED_SIZE,
     },
     CurveHooks,
 };
@@ -8,16 +9,20 @@ use crate::{
 use ark_bls12_381::g1::Config as ArkConfig;
 use ark_ff::PrimeField;
 use ark_models_ext::{
+    batch,
+    batch::MsmJob,
     bls12,
     bls12::Bls12Config,
+    checked::{self, HookError},
     short_weierstrass::{Affine, SWCurveConfig},
-    AffineRepr, CurveConfig, PrimeGroup,
+    validation, AffineRepr, CurveConfig, PrimeGroup,
 };
 use ark_serialize::{Compress, SerializationError, Validate};
 use ark_std::{
     io::{Read, Write},
     marker::PhantomData,
     ops::Neg,
+    vec::Vec,
     One,
 };
 
@@ -44,18 +49,20 @@ impl<H: CurveHooks> SWCurveConfig for Config<H> {
     const GENERATOR: G1Affine<H> = G1Affine::<H>::new_unchecked(G1_GENERATOR_X, G1_GENERATOR_Y);
 
     /// Multi scalar multiplication jumping into the user-defined `msm_g1` hook.
+    ///
+    /// Fails on any `checked_msm` failure, see [`checked::upstream_msm`].
     #[inline(always)]
     fn msm(bases: &[G1Affine<H>], scalars: &[Self::ScalarField]) -> Result<G1Projective<H>, usize> {
-        if bases.len() != scalars.len() {
-            return Err(bases.len().min(scalars.len()));
-        }
-        Ok(H::msm_g1(bases, scalars))
+        checked::upstream_msm(bases.len(), Self::checked_msm(bases, scalars))
     }
 
     /// Projective multiplication jumping into the user-defined `mul_projective_g1` hook.
+    ///
+    /// The identity if the scalar exceeds the hooks limits, see
+    /// [`checked::upstream_mul`].
     #[inline(always)]
     fn mul_projective(base: &G1Projective<H>, scalar: &[u64]) -> G1Projective<H> {
-        H::mul_projective_g1(base, scalar)
+        checked::upstream_mul(base, scalar, Self::checked_mul(base, scalar))
     }
 
     /// Affine multiplication jumping into the user-defined `mul_projective` hook.
@@ -135,25 +142,148 @@ impl<H: CurveHooks> SWCurveConfig for Config<H> {
         Ok(())
     }
 
-    // Verbatim copy of upstream implementation.
-    //
-    // Can't call it directly because of different `Affine` configuration.
+    // Strict decoding, see `Config::deserialize_with_encoding`.
     fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<G1Affine<H>, SerializationError> {
+        Self::deserialize_with_encoding(reader, compress, validate, Encoding::Strict)
+    }
+}
+
+impl<H: CurveHooks> Config<H> {
+    /// Deserialize a point, rejecting the non-canonical encodings if `encoding` is
+    /// [`Encoding::Strict`], as `deserialize_with_mode` does.
+    //
+    // Upstream implementation, also checking that the uncompressed points lie on
+    // the curve, as the subgroup check jumps into the hooks assuming so.
+    pub fn deserialize_with_encoding<R: Read>(
         mut reader: R,
         compress: Compress,
         validate: Validate,
+        encoding: Encoding,
     ) -> Result<G1Affine<H>, SerializationError> {
         let p = if compress == Compress::Yes {
-            read_g1_compressed(&mut reader)?
+            read_g1_compressed(&mut reader, validate, encoding)?
         } else {
-            read_g1_uncompressed(&mut reader)?
+            read_g1_uncompressed(&mut reader, encoding)?
         };
 
-        if validate == Validate::Yes && !p.is_in_correct_subgroup_assuming_on_curve() {
+        if validate == Validate::Yes
+            && !(p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve())
+        {
             return Err(SerializationError::InvalidData);
         }
         Ok(p)
     }
+
+    /// Multi scalar multiplication jumping into the user-defined `msm_g1` hook.
+    ///
+    /// Fails if the lengths differ or exceed the hooks limits, or if the hook
+    /// output is not on the curve.
+    pub fn checked_msm(
+        bases: &[G1Affine<H>],
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<G1Projective<H>, HookError> {
+        checked::msm(
+            bases,
+            scalars,
+            &H::LIMITS,
+            H::msm_g1,
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Projective multiplication jumping into the user-defined `mul_projective_g1` hook.
+    ///
+    /// Fails if the scalar exceeds the hooks limits or if the hook output is not
+    /// on the curve.
+    pub fn checked_mul(
+        base: &G1Projective<H>,
+        scalar: &[u64],
+    ) -> Result<G1Projective<H>, HookError> {
+        checked::mul(
+            scalar,
+            &H::LIMITS,
+            |scalar| H::mul_projective_g1(base, scalar),
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Batch normalization jumping into the user-defined `normalize_batch_g1` hook.
+    ///
+    /// See [`checked::normalize_batch`].
+    pub fn normalize_batch(points: &[G1Projective<H>]) -> Vec<G1Affine<H>> {
+        checked::normalize_batch(
+            points,
+            H::normalize_batch_g1,
+            validation::sw_is_normalization_of,
+        )
+    }
+
+    /// Multi scalar multiplication with 128-bit scalars jumping into the
+    /// user-defined `msm_small_g1` hook.
+    ///
+    /// Same checks and errors as `checked_msm`.
+    pub fn msm_small(
+        bases: &[G1Affine<H>],
+        scalars: &[u128],
+    ) -> Result<G1Projective<H>, HookError> {
+        checked::msm(
+            bases,
+            scalars,
+            &H::LIMITS,
+            H::msm_small_g1,
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Multi scalar multiplication jumping into the user-defined `msm_small_g1` hook
+    /// if all the scalars fit 128 bits, into the `msm_g1` hook otherwise.
+    pub fn msm_auto(
+        bases: &[G1Affine<H>],
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<G1Projective<H>, HookError> {
+        checked::msm_auto(
+            scalars,
+            |scalars| Self::msm_small(bases, scalars),
+            |scalars| Self::checked_msm(bases, scalars),
+        )
+    }
+
+    /// Independent multi scalar multiplications jumping into the user-defined
+    /// `multi_msm_g1` hook, with a single call for all the jobs.
+    ///
+    /// Each job is checked as by `checked_msm`, with the limits applied to the
+    /// total number of bases. See [`batch::multi_msm`] for the errors.
+    pub fn multi_msm(
+        jobs: &[MsmJob<G1Affine<H>, <Self as CurveConfig>::ScalarField>],
+    ) -> Vec<Result<G1Projective<H>, HookError>> {
+        batch::multi_msm(
+            jobs,
+            &H::LIMITS,
+            H::multi_msm_g1,
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Multiplication of one base by many scalars jumping into the user-defined
+    /// `batch_mul_g1` hook, with a single call for all the scalars.
+    ///
+    /// The products are returned in affine form. See [`batch::batch_mul`] for the
+    /// errors.
+    pub fn batch_mul(
+        base: &G1Projective<H>,
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<Vec<G1Affine<H>>, HookError> {
+        batch::batch_mul(
+            scalars,
+            &H::LIMITS,
+            |scalars| H::batch_mul_g1(base, scalars),
+            |p| p.is_on_curve(),
+        )
+    }
 }
 
 fn one_minus_x(
@@ -2,19 +2,26 @@ use ark_bls12_381::{fq2::Fq2, g2::Config as ArkConfig, Fq};
 use ark_ec::AdditiveGroup;
 use ark_ff::{Field, MontFp};
 use ark_models_ext::{
-    bls12, bls12::Bls12Config, short_weierstrass::SWCurveConfig, AffineRepr, CurveConfig,
-    CurveGroup, PrimeGroup,
+    batch,
+    batch::MsmJob,
+    bls12,
+    bls12::Bls12Config,
+    checked::{self, HookError},
+    short_weierstrass::SWCurveConfig,
+    validation, AffineRepr, CurveConfig, CurveGroup, PrimeGroup,
 };
 use ark_serialize::{Compress, SerializationError, Validate};
 use ark_std::{
     io::{Read, Write},
     marker::PhantomData,
     ops::Neg,
+    vec::Vec,
 };
 
 use crate::{
     util::{
-        read_g2_compressed, read_g2_uncompressed, serialize_fq, EncodingFlags, G2_SERIALIZED_SIZE,
+        read_g2_compressed, read_g2_uncompressed, serialize_fq, Encoding, EncodingFlags,
+        G2_SERIALIZED_SIZE,
     },
     CurveHooks,
 };
@@ -63,18 +70,20 @@ impl<H: CurveHooks> SWCurveConfig for Config<H> {
     const GENERATOR: G2Affine<H> = G2Affine::<H>::new_unchecked(G2_GENERATOR_X, G2_GENERATOR_Y);
 
     /// Multi scalar multiplication jumping into the user-defined `msm_g2` hook.
+    ///
+    /// Fails on any `checked_msm` failure, see [`checked::upstream_msm`].
     #[inline(always)]
     fn msm(bases: &[G2Affine<H>], scalars: &[Self::ScalarField]) -> Result<G2Projective<H>, usize> {
-        if bases.len() != scalars.len() {
-            return Err(bases.len().min(scalars.len()));
-        }
-        Ok(H::msm_g2(bases, scalars))
+        checked::upstream_msm(bases.len(), Self::checked_msm(bases, scalars))
     }
 
     /// Projective multiplication jumping into the user-defined `mul_projective_g2` hook.
+    ///
+    /// The identity if the scalar exceeds the hooks limits, see
+    /// [`checked::upstream_mul`].
     #[inline(always)]
     fn mul_projective(base: &G2Projective<H>, scalar: &[u64]) -> G2Projective<H> {
-        H::mul_projective_g2(base, scalar)
+        checked::upstream_mul(base, scalar, Self::checked_mul(base, scalar))
     }
 
     /// Affine multiplication jumping into the user-defined `mul_projective_g2` hook.
@@ -140,24 +149,13 @@ impl<H: CurveHooks> SWCurveConfig for Config<H> {
         (psi2_p2 - p_projective).into_affine()
     }
 
-    // Verbatim copy of upstream implementation.
-    //
-    // Can't call it directly because of different `Affine` configuration.
+    // Strict decoding, see `Config::deserialize_with_encoding`.
     fn deserialize_with_mode<R: Read>(
-        mut reader: R,
+        reader: R,
         compress: Compress,
         validate: Validate,
     ) -> Result<G2Affine<H>, SerializationError> {
-        let p = if compress == Compress::Yes {
-            read_g2_compressed(&mut reader)?
-        } else {
-            read_g2_uncompressed(&mut reader)?
-        };
-
-        if validate == Validate::Yes && !p.is_in_correct_subgroup_assuming_on_curve() {
-            return Err(SerializationError::InvalidData);
-        }
-        Ok(p)
+        Self::deserialize_with_encoding(reader, compress, validate, Encoding::Strict)
     }
 
     // Verbatim copy of upstream implementation.
@@ -214,6 +212,140 @@ impl<H: CurveHooks> SWCurveConfig for Config<H> {
     }
 }
 
+impl<H: CurveHooks> Config<H> {
+    /// Deserialize a point, rejecting the non-canonical encodings if `encoding` is
+    /// [`Encoding::Strict`], as `deserialize_with_mode` does.
+    //
+    // Upstream implementation, also checking that the uncompressed points lie on
+    // the curve, as the subgroup check jumps into the hooks assuming so.
+    pub fn deserialize_with_encoding<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+        encoding: Encoding,
+    ) -> Result<G2Affine<H>, SerializationError> {
+        let p = if compress == Compress::Yes {
+            read_g2_compressed(&mut reader, validate, encoding)?
+        } else {
+            read_g2_uncompressed(&mut reader, encoding)?
+        };
+
+        if validate == Validate::Yes
+            && !(p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve())
+        {
+            return Err(SerializationError::InvalidData);
+        }
+        Ok(p)
+    }
+
+    /// Multi scalar multiplication jumping into the user-defined `msm_g2` hook.
+    ///
+    /// Fails if the lengths differ or exceed the hooks limits, or if the hook
+    /// output is not on the curve.
+    pub fn checked_msm(
+        bases: &[G2Affine<H>],
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<G2Projective<H>, HookError> {
+        checked::msm(
+            bases,
+            scalars,
+            &H::LIMITS,
+            H::msm_g2,
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Projective multiplication jumping into the user-defined `mul_projective_g2` hook.
+    ///
+    /// Fails if the scalar exceeds the hooks limits or if the hook output is not
+    /// on the curve.
+    pub fn checked_mul(
+        base: &G2Projective<H>,
+        scalar: &[u64],
+    ) -> Result<G2Projective<H>, HookError> {
+        checked::mul(
+            scalar,
+            &H::LIMITS,
+            |scalar| H::mul_projective_g2(base, scalar),
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Batch normalization jumping into the user-defined `normalize_batch_g2` hook.
+    ///
+    /// See [`checked::normalize_batch`].
+    pub fn normalize_batch(points: &[G2Projective<H>]) -> Vec<G2Affine<H>> {
+        checked::normalize_batch(
+            points,
+            H::normalize_batch_g2,
+            validation::sw_is_normalization_of,
+        )
+    }
+
+    /// Multi scalar multiplication with 128-bit scalars jumping into the
+    /// user-defined `msm_small_g2` hook.
+    ///
+    /// Same checks and errors as `checked_msm`.
+    pub fn msm_small(
+        bases: &[G2Affine<H>],
+        scalars: &[u128],
+    ) -> Result<G2Projective<H>, HookError> {
+        checked::msm(
+            bases,
+            scalars,
+            &H::LIMITS,
+            H::msm_small_g2,
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Multi scalar multiplication jumping into the user-defined `msm_small_g2` hook
+    /// if all the scalars fit 128 bits, into the `msm_g2` hook otherwise.
+    pub fn msm_auto(
+        bases: &[G2Affine<H>],
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<G2Projective<H>, HookError> {
+        checked::msm_auto(
+            scalars,
+            |scalars| Self::msm_small(bases, scalars),
+            |scalars| Self::checked_msm(bases, scalars),
+        )
+    }
+
+    /// Independent multi scalar multiplications jumping into the user-defined
+    /// `multi_msm_g2` hook, with a single call for all the jobs.
+    ///
+    /// Each job is checked as by `checked_msm`, with the limits applied to the
+    /// total number of bases. See [`batch::multi_msm`] for the errors.
+    pub fn multi_msm(
+        jobs: &[MsmJob<G2Affine<H>, <Self as CurveConfig>::ScalarField>],
+    ) -> Vec<Result<G2Projective<H>, HookError>> {
+        batch::multi_msm(
+            jobs,
+            &H::LIMITS,
+            H::multi_msm_g2,
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Multiplication of one base by many scalars jumping into the user-defined
+    /// `batch_mul_g2` hook, with a single call for all the scalars.
+    ///
+    /// The products are returned in affine form. See [`batch::batch_mul`] for the
+    /// errors.
+    pub fn batch_mul(
+        base: &G2Projective<H>,
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<Vec<G2Affine<H>>, HookError> {
+        batch::batch_mul(
+            scalars,
+            &H::LIMITS,
+            |scalars| H::batch_mul_g2(base, scalars),
+            |p| p.is_on_curve(),
+        )
+    }
+}
+
 /// psi(P) is the untwist-Frobenius-twist endomorhism on E'(Fq2)
 fn p_power_endomorphism<H: CurveHooks>(p: &G2Affine<H>) -> G2Affine<H> {
     // The p-power endomorphism for G2 is defined as follows:
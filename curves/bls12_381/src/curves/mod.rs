@@ -1,12 +1,21 @@
-use ark_bls12_381::Config as ArkConfig;
+use ark_bls12_381::{Config as ArkConfig, Fq, Fq2};
 use ark_ec::bls12::Bls12Config as ArkBls12Config;
+use ark_ff::Field;
 use ark_models_ext::{
+    batch::{self, MsmJob},
     bls12::{Bls12, Bls12Config, G1Prepared, G2Prepared, TwistType},
+    cast,
+    checked::HookError,
+    field_hooks::FieldHooks,
+    gt,
+    limits::HookLimits,
+    metering::{HookOp, HookUsage, Meter, MeteredHooks},
     pairing::{MillerLoopOutput, Pairing, PairingOutput},
-    CurveConfig,
+    pairs, scalars, validation, CurveConfig, CurveGroup,
 };
-use ark_std::marker::PhantomData;
+use ark_std::{marker::PhantomData, vec::Vec, One, Zero};
 
+pub mod eip2537;
 pub mod g1;
 pub mod g2;
 pub(crate) mod util;
@@ -14,17 +23,31 @@ pub(crate) mod util;
 #[cfg(test)]
 mod tests;
 
+pub use self::util::{check_encoding, Encoding, EncodingError};
 pub use self::{
     g1::{G1Affine, G1Projective},
     g2::{G2Affine, G2Projective},
 };
+pub use ark_models_ext::decoding::{check_subgroup_batch, deserialize_on_curve_only};
 
 /// Hooks for *BLS12-381* curve.
 pub trait CurveHooks: 'static + Sized {
+    /// Limits enforced on the inputs before dispatching any call.
+    ///
+    /// Oversized requests never reach the hooks. The `checked_*` entry points of the
+    /// configs fail with [`HookError::Limit`], while through the upstream traits
+    /// `msm` fails and projective multiplication yields the identity, while an
+    /// oversized multi Miller loop yields a zero output, which is then rejected by
+    /// final exponentiation (thus making `Pairing::multi_pairing` yield
+    /// `gt::rejected`).
+    const LIMITS: HookLimits = HookLimits::UNLIMITED;
+
     /// Pairing multi Miller loop.
+    ///
+    /// The inputs have the same length and no pair contains a zero point.
     fn multi_miller_loop(
-        g1: impl Iterator<Item = <Bls12_381<Self> as Pairing>::G1Prepared>,
-        g2: impl Iterator<Item = <Bls12_381<Self> as Pairing>::G2Prepared>,
+        g1: &[<Bls12_381<Self> as Pairing>::G1Prepared],
+        g2: &[<Bls12_381<Self> as Pairing>::G2Prepared],
     ) -> <Bls12_381<Self> as Pairing>::TargetField;
 
     /// Pairing final exponentiation.
@@ -32,6 +55,26 @@ pub trait CurveHooks: 'static + Sized {
         target: <Bls12_381<Self> as Pairing>::TargetField,
     ) -> <Bls12_381<Self> as Pairing>::TargetField;
 
+    /// Pairing final exponentiation of many independent Miller loop outputs.
+    ///
+    /// The targets are non-zero. Defaults to one `final_exponentiation` hook call
+    /// per target.
+    fn final_exponentiation_batch(
+        targets: &[<Bls12_381<Self> as Pairing>::TargetField],
+    ) -> Vec<<Bls12_381<Self> as Pairing>::TargetField> {
+        targets
+            .iter()
+            .map(|target| Self::final_exponentiation(*target))
+            .collect()
+    }
+
+    /// Pairing target group membership check.
+    ///
+    /// The target is unitary. Defaults to the check based on the group order.
+    fn gt_is_in_subgroup(target: &<Bls12_381<Self> as Pairing>::TargetField) -> bool {
+        gt::is_in_subgroup_via_order(&PairingOutput::<Bls12_381<Self>>(*target))
+    }
+
     /// Multi scalar multiplication on G1.
     fn msm_g1(
         bases: &[g1::G1Affine<Self>],
@@ -49,6 +92,237 @@ pub trait CurveHooks: 'static + Sized {
 
     /// Projective multiplication on G2.
     fn mul_projective_g2(base: &G2Projective<Self>, scalar: &[u64]) -> G2Projective<Self>;
+
+    /// Batch normalization on G1.
+    ///
+    /// Defaults to the software batch normalization.
+    fn normalize_batch_g1(points: &[G1Projective<Self>]) -> Vec<G1Affine<Self>> {
+        <G1Projective<Self> as CurveGroup>::normalize_batch(points)
+    }
+
+    /// Batch normalization on G2.
+    ///
+    /// Defaults to the software batch normalization.
+    fn normalize_batch_g2(points: &[G2Projective<Self>]) -> Vec<G2Affine<Self>> {
+        <G2Projective<Self> as CurveGroup>::normalize_batch(points)
+    }
+
+    /// Multi scalar multiplication on G1 with 128-bit scalars.
+    ///
+    /// Defaults to the `msm_g1` hook.
+    fn msm_small_g1(bases: &[G1Affine<Self>], scalars: &[u128]) -> G1Projective<Self> {
+        Self::msm_g1(bases, &scalars::from_small(scalars))
+    }
+
+    /// Multi scalar multiplication on G2 with 128-bit scalars.
+    ///
+    /// Defaults to the `msm_g2` hook.
+    fn msm_small_g2(bases: &[G2Affine<Self>], scalars: &[u128]) -> G2Projective<Self> {
+        Self::msm_g2(bases, &scalars::from_small(scalars))
+    }
+
+    /// Independent multi scalar multiplications on G1, each job being a pair of bases and scalars.
+    ///
+    /// Defaults to one `msm_g1` hook call per job.
+    fn multi_msm_g1(
+        jobs: &[MsmJob<G1Affine<Self>, <g1::Config<Self> as CurveConfig>::ScalarField>],
+    ) -> Vec<G1Projective<Self>> {
+        jobs.iter()
+            .map(|(bases, scalars)| Self::msm_g1(bases, scalars))
+            .collect()
+    }
+
+    /// Independent multi scalar multiplications on G2, each job being a pair of bases and scalars.
+    ///
+    /// Defaults to one `msm_g2` hook call per job.
+    fn multi_msm_g2(
+        jobs: &[MsmJob<G2Affine<Self>, <g2::Config<Self> as CurveConfig>::ScalarField>],
+    ) -> Vec<G2Projective<Self>> {
+        jobs.iter()
+            .map(|(bases, scalars)| Self::msm_g2(bases, scalars))
+            .collect()
+    }
+
+    /// Multiplication of one base by many scalars on G1.
+    ///
+    /// Defaults to one `mul_projective_g1` hook call per scalar, followed by the
+    /// `normalize_batch_g1` hook.
+    fn batch_mul_g1(
+        base: &G1Projective<Self>,
+        scalars: &[<g1::Config<Self> as CurveConfig>::ScalarField],
+    ) -> Vec<G1Affine<Self>> {
+        batch::batch_mul_each(
+            scalars,
+            |s| Self::mul_projective_g1(base, s),
+            Self::normalize_batch_g1,
+        )
+    }
+
+    /// Multiplication of one base by many scalars on G2.
+    ///
+    /// Defaults to one `mul_projective_g2` hook call per scalar, followed by the
+    /// `normalize_batch_g2` hook.
+    fn batch_mul_g2(
+        base: &G2Projective<Self>,
+        scalars: &[<g2::Config<Self> as CurveConfig>::ScalarField],
+    ) -> Vec<G2Affine<Self>> {
+        batch::batch_mul_each(
+            scalars,
+            |s| Self::mul_projective_g2(base, s),
+            Self::normalize_batch_g2,
+        )
+    }
+
+    /// Square root in the base field, used to decompress validated G1 points.
+    ///
+    /// Defaults to the software implementation.
+    fn sqrt_fq(x: &Fq) -> Option<Fq> {
+        x.sqrt()
+    }
+
+    /// Square root in the quadratic extension field, used to decompress validated
+    /// G2 points.
+    ///
+    /// Defaults to the software implementation.
+    fn sqrt_fq2(x: &Fq2) -> Option<Fq2> {
+        x.sqrt()
+    }
+}
+
+impl<H: CurveHooks, M: Meter> CurveHooks for MeteredHooks<H, M> {
+    const LIMITS: HookLimits = H::LIMITS;
+
+    fn multi_miller_loop(
+        g1: &[<Bls12_381<Self> as Pairing>::G1Prepared],
+        g2: &[<Bls12_381<Self> as Pairing>::G2Prepared],
+    ) -> <Bls12_381<Self> as Pairing>::TargetField {
+        let g1: Vec<_> = g1
+            .iter()
+            .map(|p| G1Prepared(cast::sw_affine(&p.0)))
+            .collect();
+        let g2: Vec<_> = g2
+            .iter()
+            .map(|p| G2Prepared(cast::sw_affine(&p.0)))
+            .collect();
+        M::record(HookOp::MultiMillerLoop, HookUsage::items(g1.len()));
+        H::multi_miller_loop(&g1, &g2)
+    }
+
+    fn final_exponentiation(
+        target: <Bls12_381<Self> as Pairing>::TargetField,
+    ) -> <Bls12_381<Self> as Pairing>::TargetField {
+        M::record(HookOp::FinalExponentiation, HookUsage::items(1));
+        H::final_exponentiation(target)
+    }
+
+    fn final_exponentiation_batch(
+        targets: &[<Bls12_381<Self> as Pairing>::TargetField],
+    ) -> Vec<<Bls12_381<Self> as Pairing>::TargetField> {
+        M::record(
+            HookOp::FinalExponentiationBatch,
+            HookUsage::items(targets.len()),
+        );
+        H::final_exponentiation_batch(targets)
+    }
+
+    fn gt_is_in_subgroup(target: &<Bls12_381<Self> as Pairing>::TargetField) -> bool {
+        M::record(HookOp::GtIsInSubgroup, HookUsage::items(1));
+        H::gt_is_in_subgroup(target)
+    }
+
+    fn msm_g1(
+        bases: &[g1::G1Affine<Self>],
+        scalars: &[<g1::Config<Self> as CurveConfig>::ScalarField],
+    ) -> G1Projective<Self> {
+        M::record(HookOp::MsmG1, HookUsage::msm(scalars));
+        let res = H::msm_g1(&cast::sw_affine_slice(bases), scalars);
+        cast::sw_projective(&res)
+    }
+
+    fn msm_g2(
+        bases: &[g2::G2Affine<Self>],
+        scalars: &[<g2::Config<Self> as CurveConfig>::ScalarField],
+    ) -> G2Projective<Self> {
+        M::record(HookOp::MsmG2, HookUsage::msm(scalars));
+        let res = H::msm_g2(&cast::sw_affine_slice(bases), scalars);
+        cast::sw_projective(&res)
+    }
+
+    fn mul_projective_g1(base: &G1Projective<Self>, scalar: &[u64]) -> G1Projective<Self> {
+        M::record(HookOp::MulProjectiveG1, HookUsage::mul(scalar));
+        let res = H::mul_projective_g1(&cast::sw_projective(base), scalar);
+        cast::sw_projective(&res)
+    }
+
+    fn mul_projective_g2(base: &G2Projective<Self>, scalar: &[u64]) -> G2Projective<Self> {
+        M::record(HookOp::MulProjectiveG2, HookUsage::mul(scalar));
+        let res = H::mul_projective_g2(&cast::sw_projective(base), scalar);
+        cast::sw_projective(&res)
+    }
+
+    fn normalize_batch_g1(points: &[G1Projective<Self>]) -> Vec<G1Affine<Self>> {
+        M::record(HookOp::NormalizeBatchG1, HookUsage::items(points.len()));
+        cast::sw_normalize_batch(points, H::normalize_batch_g1)
+    }
+
+    fn normalize_batch_g2(points: &[G2Projective<Self>]) -> Vec<G2Affine<Self>> {
+        M::record(HookOp::NormalizeBatchG2, HookUsage::items(points.len()));
+        cast::sw_normalize_batch(points, H::normalize_batch_g2)
+    }
+
+    fn msm_small_g1(bases: &[G1Affine<Self>], scalars: &[u128]) -> G1Projective<Self> {
+        M::record(HookOp::MsmSmallG1, HookUsage::msm_small(scalars));
+        let res = H::msm_small_g1(&cast::sw_affine_slice(bases), scalars);
+        cast::sw_projective(&res)
+    }
+
+    fn msm_small_g2(bases: &[G2Affine<Self>], scalars: &[u128]) -> G2Projective<Self> {
+        M::record(HookOp::MsmSmallG2, HookUsage::msm_small(scalars));
+        let res = H::msm_small_g2(&cast::sw_affine_slice(bases), scalars);
+        cast::sw_projective(&res)
+    }
+
+    fn multi_msm_g1(
+        jobs: &[MsmJob<G1Affine<Self>, <g1::Config<Self> as CurveConfig>::ScalarField>],
+    ) -> Vec<G1Projective<Self>> {
+        M::record(HookOp::MultiMsmG1, HookUsage::multi_msm(jobs));
+        cast::sw_multi_msm(jobs, H::multi_msm_g1)
+    }
+
+    fn multi_msm_g2(
+        jobs: &[MsmJob<G2Affine<Self>, <g2::Config<Self> as CurveConfig>::ScalarField>],
+    ) -> Vec<G2Projective<Self>> {
+        M::record(HookOp::MultiMsmG2, HookUsage::multi_msm(jobs));
+        cast::sw_multi_msm(jobs, H::multi_msm_g2)
+    }
+
+    fn batch_mul_g1(
+        base: &G1Projective<Self>,
+        scalars: &[<g1::Config<Self> as CurveConfig>::ScalarField],
+    ) -> Vec<G1Affine<Self>> {
+        M::record(HookOp::BatchMulG1, HookUsage::batch_mul(scalars));
+        let res = H::batch_mul_g1(&cast::sw_projective(base), scalars);
+        cast::sw_affine_slice(&res)
+    }
+
+    fn batch_mul_g2(
+        base: &G2Projective<Self>,
+        scalars: &[<g2::Config<Self> as CurveConfig>::ScalarField],
+    ) -> Vec<G2Affine<Self>> {
+        M::record(HookOp::BatchMulG2, HookUsage::batch_mul(scalars));
+        let res = H::batch_mul_g2(&cast::sw_projective(base), scalars);
+        cast::sw_affine_slice(&res)
+    }
+
+    fn sqrt_fq(x: &Fq) -> Option<Fq> {
+        M::record(HookOp::FieldSqrtFq, HookUsage::items(1));
+        H::sqrt_fq(x)
+    }
+
+    fn sqrt_fq2(x: &Fq2) -> Option<Fq2> {
+        M::record(HookOp::FieldSqrtFq2, HookUsage::items(1));
+        H::sqrt_fq2(x)
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -70,23 +344,113 @@ impl<H: CurveHooks> Bls12Config for Config<H> {
     type G2Config = g2::Config<H>;
 
     /// Multi Miller loop jumping into the user-defined `multi_miller_loop` hook.
+    ///
+    /// For the inputs rejected by `checked_multi_miller_loop` returns a zero output,
+    /// which is then rejected by `final_exponentiation`. `Pairing::multi_pairing`
+    /// yields `gt::rejected` on such output, see `checked_multi_pairing` for a
+    /// fallible version.
     #[inline(always)]
     fn multi_miller_loop(
         g1: impl IntoIterator<Item = impl Into<G1Prepared<Self>>>,
         g2: impl IntoIterator<Item = impl Into<G2Prepared<Self>>>,
     ) -> MillerLoopOutput<Bls12<Self>> {
-        let g1 = g1.into_iter().map(|item| item.into());
-        let g2 = g2.into_iter().map(|item| item.into());
-        let res = H::multi_miller_loop(g1, g2);
-        MillerLoopOutput(res)
+        Self::checked_multi_miller_loop(g1, g2).unwrap_or(MillerLoopOutput(Zero::zero()))
     }
 
     /// Final exponentiation jumping into the user-defined `final_exponentiation` hook.
+    ///
+    /// Returns `None` for a zero Miller loop output or a non-unitary hook output.
     #[inline(always)]
     fn final_exponentiation(
         target: MillerLoopOutput<Bls12<Self>>,
     ) -> Option<PairingOutput<Bls12<Self>>> {
+        if target.0.is_zero() {
+            return None;
+        }
         let res = H::final_exponentiation(target.0);
+        if !validation::is_unitary(&res) {
+            return None;
+        }
         Some(PairingOutput(res))
     }
+
+    /// Final exponentiation of many Miller loop outputs jumping into the
+    /// user-defined `final_exponentiation_batch` hook, with a single call.
+    ///
+    /// Same checks and errors as `final_exponentiation`, see
+    /// [`batch::final_exponentiation`].
+    fn final_exponentiation_batch(
+        targets: &[MillerLoopOutput<Bls12<Self>>],
+    ) -> Vec<Option<PairingOutput<Bls12<Self>>>> {
+        let targets: Vec<_> = targets.iter().map(|target| target.0).collect();
+        batch::final_exponentiation(
+            &targets,
+            H::final_exponentiation_batch,
+            validation::is_unitary,
+        )
+        .into_iter()
+        .map(|res| res.map(PairingOutput))
+        .collect()
+    }
+
+    /// Target group membership check jumping into the user-defined
+    /// `gt_is_in_subgroup` hook.
+    ///
+    /// Non-unitary elements are rejected without calling the hook.
+    fn gt_is_in_subgroup(out: &PairingOutput<Bls12<Self>>) -> bool {
+        validation::is_unitary(&out.0) && H::gt_is_in_subgroup(&out.0)
+    }
+}
+
+impl<H: CurveHooks> Config<H> {
+    /// Multi Miller loop jumping into the user-defined `multi_miller_loop` hook.
+    ///
+    /// Fails if the number of G1 and G2 elements differ or exceed the hooks limits.
+    /// The pairs with a zero point are dropped before the hook call, which is
+    /// skipped if no pair is left. Fails with [`HookError::InvalidOutput`] if the
+    /// hook returns one, as the loop over non-zero points never yields it.
+    pub fn checked_multi_miller_loop(
+        g1: impl IntoIterator<Item = impl Into<G1Prepared<Self>>>,
+        g2: impl IntoIterator<Item = impl Into<G2Prepared<Self>>>,
+    ) -> Result<MillerLoopOutput<Bls12<Self>>, HookError> {
+        let (g1, g2) = pairs::collect(
+            g1.into_iter().map(Into::into),
+            g2.into_iter().map(Into::into),
+            &H::LIMITS,
+            |a: &G1Prepared<Self>, b: &G2Prepared<Self>| a.is_zero() || b.is_zero(),
+        )?;
+        if g1.is_empty() {
+            return Ok(MillerLoopOutput(One::one()));
+        }
+        let res = H::multi_miller_loop(&g1, &g2);
+        if res.is_one() {
+            return Err(HookError::InvalidOutput);
+        }
+        Ok(MillerLoopOutput(res))
+    }
+
+    /// Pairing jumping into the user-defined `multi_miller_loop` and
+    /// `final_exponentiation` hooks.
+    ///
+    /// Fails as `checked_multi_miller_loop`, or with [`HookError::InvalidOutput`] if
+    /// `final_exponentiation` rejects the Miller loop output or the hook output.
+    pub fn checked_multi_pairing(
+        g1: impl IntoIterator<Item = impl Into<G1Prepared<Self>>>,
+        g2: impl IntoIterator<Item = impl Into<G2Prepared<Self>>>,
+    ) -> Result<PairingOutput<Bls12<Self>>, HookError> {
+        let f = Self::checked_multi_miller_loop(g1, g2)?;
+        <Self as Bls12Config>::final_exponentiation(f).ok_or(HookError::InvalidOutput)
+    }
+}
+
+impl<H: CurveHooks> FieldHooks<Fq> for Config<H> {
+    fn sqrt(x: &Fq) -> Option<Fq> {
+        H::sqrt_fq(x)
+    }
+}
+
+impl<H: CurveHooks> FieldHooks<Fq2> for Config<H> {
+    fn sqrt(x: &Fq2) -> Option<Fq2> {
+        H::sqrt_fq2(x)
+    }
 }
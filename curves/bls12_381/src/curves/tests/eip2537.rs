@@ -0,0 +1,157 @@
+//! EIP-2537 precompiles checked against the test vectors published with the EIP.
+//!
+//! The vectors of the former `G1MUL` and `G2MUL` precompiles are checked against
+//! the MSM precompiles, being single pair MSM inputs.
+
+use super::TestHooks;
+use crate::eip2537::{self, Error};
+use ark_std::{vec::Vec, UniformRand};
+use test_utils::{no_panic, vectors::parse};
+
+fn error(msg: &str) -> Error {
+    match msg {
+        "invalid input length" => Error::InputLength,
+        "invalid field element top bytes" => Error::FieldPadding,
+        "invalid fp.Element encoding" => Error::FieldEncoding,
+        "invalid point: not on curve" => Error::NotOnCurve,
+        "g1 point is not on correct subgroup" | "g2 point is not on correct subgroup" => {
+            Error::NotInSubgroup
+        }
+        _ => panic!("unknown error: {msg}"),
+    }
+}
+
+fn check<const N: usize>(json: &str, op: fn(&[u8]) -> Result<[u8; N], Error>) {
+    test_utils::vectors::check(json, error, op);
+}
+
+macro_rules! vectors {
+    ($file:literal) => {
+        include_str!(concat!("eip2537/", $file))
+    };
+}
+
+#[test]
+fn g1_add() {
+    let op = eip2537::g1_add::<TestHooks>;
+    check(vectors!("add_G1_bls.json"), op);
+    check(vectors!("fail-add_G1_bls.json"), op);
+}
+
+#[test]
+fn g2_add() {
+    let op = eip2537::g2_add::<TestHooks>;
+    check(vectors!("add_G2_bls.json"), op);
+    check(vectors!("fail-add_G2_bls.json"), op);
+}
+
+#[test]
+fn g1_msm() {
+    let op = eip2537::g1_msm::<TestHooks>;
+    check(vectors!("multiexp_G1_bls.json"), op);
+    check(vectors!("fail-multiexp_G1_bls.json"), op);
+    check(vectors!("mul_G1_bls.json"), op);
+    check(vectors!("fail-mul_G1_bls.json"), op);
+}
+
+#[test]
+fn g2_msm() {
+    let op = eip2537::g2_msm::<TestHooks>;
+    check(vectors!("multiexp_G2_bls.json"), op);
+    check(vectors!("fail-multiexp_G2_bls.json"), op);
+    check(vectors!("mul_G2_bls.json"), op);
+    check(vectors!("fail-mul_G2_bls.json"), op);
+}
+
+#[test]
+fn pairing_check() {
+    let op = eip2537::pairing_check::<TestHooks>;
+    check(vectors!("pairing_check_bls.json"), op);
+    check(vectors!("fail-pairing_check_bls.json"), op);
+}
+
+#[test]
+fn map_fp_to_g1() {
+    let op = eip2537::map_fp_to_g1::<TestHooks>;
+    check(vectors!("map_fp_to_G1_bls.json"), op);
+    check(vectors!("fail-map_fp_to_G1_bls.json"), op);
+}
+
+#[test]
+fn map_fp2_to_g2() {
+    let op = eip2537::map_fp2_to_g2::<TestHooks>;
+    check(vectors!("map_fp2_to_G2_bls.json"), op);
+    check(vectors!("fail-map_fp2_to_G2_bls.json"), op);
+}
+
+/// Non-empty inputs of the first vectors of `json`, the largest MSM inputs making
+/// the fuzzing too slow.
+fn inputs(json: &str) -> Vec<Vec<u8>> {
+    let vectors = parse(json, error).into_iter().take(8);
+    vectors.map(|v| v.input).filter(|i| !i.is_empty()).collect()
+}
+
+#[test]
+fn no_panic() {
+    let g1_add = [
+        vectors!("add_G1_bls.json"),
+        vectors!("fail-add_G1_bls.json"),
+    ]
+    .map(inputs);
+    no_panic::parse("g1_add", &g1_add.concat(), eip2537::g1_add::<TestHooks>);
+    let g2_add = [
+        vectors!("add_G2_bls.json"),
+        vectors!("fail-add_G2_bls.json"),
+    ]
+    .map(inputs);
+    no_panic::parse("g2_add", &g2_add.concat(), eip2537::g2_add::<TestHooks>);
+    let g1_msm = [
+        vectors!("multiexp_G1_bls.json"),
+        vectors!("fail-multiexp_G1_bls.json"),
+    ];
+    no_panic::parse(
+        "g1_msm",
+        &g1_msm.map(inputs).concat(),
+        eip2537::g1_msm::<TestHooks>,
+    );
+    let g2_msm = [
+        vectors!("multiexp_G2_bls.json"),
+        vectors!("fail-multiexp_G2_bls.json"),
+    ];
+    no_panic::parse(
+        "g2_msm",
+        &g2_msm.map(inputs).concat(),
+        eip2537::g2_msm::<TestHooks>,
+    );
+    let pairing = [
+        vectors!("pairing_check_bls.json"),
+        vectors!("fail-pairing_check_bls.json"),
+    ];
+    let op = eip2537::pairing_check::<TestHooks>;
+    no_panic::parse("pairing_check", &pairing.map(inputs).concat(), op);
+    let map_fp = [
+        vectors!("map_fp_to_G1_bls.json"),
+        vectors!("fail-map_fp_to_G1_bls.json"),
+    ];
+    let op = eip2537::map_fp_to_g1::<TestHooks>;
+    no_panic::parse("map_fp_to_g1", &map_fp.map(inputs).concat(), op);
+    let map_fp2 = [
+        vectors!("map_fp2_to_G2_bls.json"),
+        vectors!("fail-map_fp2_to_G2_bls.json"),
+    ];
+    let op = eip2537::map_fp2_to_g2::<TestHooks>;
+    no_panic::parse("map_fp2_to_g2", &map_fp2.map(inputs).concat(), op);
+}
+
+#[test]
+fn encoding_roundtrip() {
+    let mut rng = ark_std::test_rng();
+    for _ in 0..10 {
+        let p = super::G1Affine::rand(&mut rng);
+        assert_eq!(eip2537::decode_g1(&eip2537::encode_g1(&p), true), Ok(p));
+        let q = super::G2Affine::rand(&mut rng);
+        assert_eq!(eip2537::decode_g2(&eip2537::encode_g2(&q), true), Ok(q));
+    }
+    let zero = super::G1Affine::default();
+    assert_eq!(eip2537::encode_g1(&zero), [0; eip2537::G1_LEN]);
+}
@@ -0,0 +1,169 @@
+//! Non-canonical point encodings, rejected with distinct reasons in strict mode and
+//! accepted by the lax legacy decoding.
+
+use super::{G1Affine, G1Config, G2Affine, G2Config};
+use crate::{check_encoding, Encoding, EncodingError};
+use ark_ff::{BigInteger, PrimeField};
+use ark_models_ext::AffineRepr;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Validate,
+};
+use ark_std::{io, string::ToString, vec, vec::Vec};
+
+type Decode<T> = fn(&[u8], Compress, Validate, Encoding) -> Result<T, SerializationError>;
+
+const COMPRESSED: u8 = 1 << 7;
+const INFINITY: u8 = 1 << 6;
+const LARGEST: u8 = 1 << 5;
+
+fn encode<T: CanonicalSerialize>(p: &T, compress: Compress) -> Vec<u8> {
+    let mut bytes = vec![];
+    p.serialize_with_mode(&mut bytes, compress).unwrap();
+    bytes
+}
+
+/// Invalid encodings, tagged with the expected rejection reason, derived from the
+/// valid encodings of the generator and of the point at infinity.
+fn invalid_vectors<T: AffineRepr + CanonicalSerialize>() -> Vec<(Vec<u8>, bool, EncodingError)> {
+    let mut vectors = vec![];
+    for compressed in [true, false] {
+        let compress = if compressed {
+            Compress::Yes
+        } else {
+            Compress::No
+        };
+        let generator = encode(&T::generator(), compress);
+        let zero = encode(&T::zero(), compress);
+        let fq_len = 48;
+
+        let mut v = generator.clone();
+        v[0] ^= COMPRESSED;
+        vectors.push((v, compressed, EncodingError::Compression));
+
+        let mut v = zero.clone();
+        v[0] ^= COMPRESSED;
+        vectors.push((v, compressed, EncodingError::Compression));
+
+        let mut v = zero.clone();
+        v[0] |= LARGEST;
+        vectors.push((v, compressed, EncodingError::InfinitySort));
+
+        let mut v = zero.clone();
+        v[0] |= 1;
+        vectors.push((v, compressed, EncodingError::InfinityData));
+
+        let mut v = zero.clone();
+        *v.last_mut().unwrap() = 1;
+        vectors.push((v, compressed, EncodingError::InfinityData));
+
+        let mut v = generator.clone();
+        v[0] |= INFINITY;
+        vectors.push((v, compressed, EncodingError::InfinityData));
+
+        if !compressed {
+            let mut v = generator.clone();
+            v[0] |= LARGEST;
+            vectors.push((v, compressed, EncodingError::UncompressedSort));
+        }
+
+        // The modulus in place of each coordinate.
+        for chunk in 0..generator.len() / fq_len {
+            let mut v = generator.clone();
+            let start = chunk * fq_len;
+            let flags = v[start] & !0b0001_1111;
+            v[start..start + fq_len].copy_from_slice(&crate::Fq::MODULUS.to_bytes_be());
+            v[start] |= flags;
+            vectors.push((v, compressed, EncodingError::Field));
+        }
+    }
+    vectors
+}
+
+fn check<T>(decode: Decode<T>)
+where
+    T: AffineRepr + CanonicalSerialize + CanonicalDeserialize,
+{
+    for (bytes, compressed, reason) in invalid_vectors::<T>() {
+        assert_eq!(check_encoding(&bytes, compressed), Err(reason));
+        let compress = if compressed {
+            Compress::Yes
+        } else {
+            Compress::No
+        };
+        let err = T::deserialize_with_mode(&bytes[..], compress, Validate::No)
+            .expect_err("non-canonical encoding accepted");
+        assert_eq!(EncodingError::of(&err), Some(reason), "{reason}");
+        let err = decode(&bytes, compress, Validate::No, Encoding::Strict).unwrap_err();
+        assert_eq!(EncodingError::of(&err), Some(reason), "{reason}");
+
+        // Legacy decoding accepts the malleable flags.
+        let lax = decode(&bytes, compress, Validate::No, Encoding::Lax);
+        match reason {
+            EncodingError::InfinitySort | EncodingError::InfinityData => {
+                assert!(lax.unwrap().is_zero())
+            }
+            EncodingError::UncompressedSort => assert!(lax.is_ok()),
+            EncodingError::Length | EncodingError::Compression | EncodingError::Field => {
+                assert!(lax.is_err())
+            }
+        }
+    }
+
+    for compress in [Compress::Yes, Compress::No] {
+        for p in [T::generator(), T::zero()] {
+            let bytes = encode(&p, compress);
+            assert_eq!(check_encoding(&bytes, compress == Compress::Yes), Ok(()));
+            for encoding in [Encoding::Strict, Encoding::Lax] {
+                assert_eq!(
+                    decode(&bytes, compress, Validate::Yes, encoding).unwrap(),
+                    p
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn g1_invalid_vectors() {
+    check::<G1Affine>(|bytes, compress, validate, encoding| {
+        G1Config::deserialize_with_encoding(bytes, compress, validate, encoding)
+    });
+}
+
+#[test]
+fn g2_invalid_vectors() {
+    check::<G2Affine>(|bytes, compress, validate, encoding| {
+        G2Config::deserialize_with_encoding(bytes, compress, validate, encoding)
+    });
+}
+
+#[test]
+fn invalid_lengths() {
+    for len in [0, 1, 47, 49, 95, 97] {
+        let bytes = vec![0xc0; len];
+        assert_eq!(check_encoding(&bytes, true), Err(EncodingError::Length));
+    }
+}
+
+#[test]
+fn distinct_reasons() {
+    let errors = [
+        EncodingError::Length,
+        EncodingError::Compression,
+        EncodingError::InfinityData,
+        EncodingError::InfinitySort,
+        EncodingError::UncompressedSort,
+        EncodingError::Field,
+    ];
+    for reason in errors {
+        assert_eq!(EncodingError::of(&reason.into()), Some(reason));
+        // Recovered by type, not by message.
+        let msg = io::Error::new(io::ErrorKind::InvalidData, reason.to_string());
+        assert_eq!(EncodingError::of(&SerializationError::IoError(msg)), None);
+    }
+    assert_eq!(EncodingError::of(&SerializationError::InvalidData), None);
+    assert_eq!(
+        EncodingError::of(&SerializationError::UnexpectedFlags),
+        None
+    );
+}
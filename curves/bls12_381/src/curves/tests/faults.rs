@@ -0,0 +1,264 @@
+use super::TestHooks;
+use crate::{fr::Fr, CurveHooks};
+
+use ark_ff::Zero;
+use ark_models_ext::{
+    bls12::{G1Prepared, G2Prepared},
+    cast,
+    checked::HookError,
+    metering::HookOp,
+    pairing::Pairing,
+    short_weierstrass::{Affine, SWCurveConfig},
+    AffineRepr, CurveConfig, CurveGroup, VariableBaseMSM,
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{test_rng, vec::Vec, UniformRand};
+use test_utils::faults::{self, Fault, FaultyHooks};
+
+type Bls12_381 = crate::Bls12_381<FaultyHooks<TestHooks>>;
+type G1Affine = crate::G1Affine<FaultyHooks<TestHooks>>;
+type G2Affine = crate::G2Affine<FaultyHooks<TestHooks>>;
+type G1Projective = crate::G1Projective<FaultyHooks<TestHooks>>;
+type G2Projective = crate::G2Projective<FaultyHooks<TestHooks>>;
+
+const FAULTS: [Fault; 5] = [
+    Fault::Identity,
+    Fault::Random,
+    Fault::OffCurve,
+    Fault::NonSubgroup,
+    Fault::Fail,
+];
+
+impl<H: CurveHooks> CurveHooks for FaultyHooks<H> {
+    fn multi_miller_loop(
+        g1: &[<crate::Bls12_381<Self> as Pairing>::G1Prepared],
+        g2: &[<crate::Bls12_381<Self> as Pairing>::G2Prepared],
+    ) -> <crate::Bls12_381<Self> as Pairing>::TargetField {
+        let g1: Vec<_> = g1
+            .iter()
+            .map(|p| G1Prepared(cast::sw_affine(&p.0)))
+            .collect();
+        let g2: Vec<_> = g2
+            .iter()
+            .map(|p| G2Prepared(cast::sw_affine(&p.0)))
+            .collect();
+        let res = H::multi_miller_loop(&g1, &g2);
+        faults::faulty_target(HookOp::MultiMillerLoop, res)
+    }
+
+    fn final_exponentiation(
+        target: <crate::Bls12_381<Self> as Pairing>::TargetField,
+    ) -> <crate::Bls12_381<Self> as Pairing>::TargetField {
+        let res = H::final_exponentiation(target);
+        faults::faulty_target(HookOp::FinalExponentiation, res)
+    }
+
+    fn msm_g1(
+        bases: &[crate::G1Affine<Self>],
+        scalars: &[<crate::g1::Config<Self> as CurveConfig>::ScalarField],
+    ) -> crate::G1Projective<Self> {
+        let res = H::msm_g1(&cast::sw_affine_slice(bases), scalars);
+        faults::faulty_sw(HookOp::MsmG1, cast::sw_projective(&res))
+    }
+
+    fn msm_g2(
+        bases: &[crate::G2Affine<Self>],
+        scalars: &[<crate::g2::Config<Self> as CurveConfig>::ScalarField],
+    ) -> crate::G2Projective<Self> {
+        let res = H::msm_g2(&cast::sw_affine_slice(bases), scalars);
+        faults::faulty_sw(HookOp::MsmG2, cast::sw_projective(&res))
+    }
+
+    fn mul_projective_g1(
+        base: &crate::G1Projective<Self>,
+        scalar: &[u64],
+    ) -> crate::G1Projective<Self> {
+        let res = H::mul_projective_g1(&cast::sw_projective(base), scalar);
+        faults::faulty_sw(HookOp::MulProjectiveG1, cast::sw_projective(&res))
+    }
+
+    fn mul_projective_g2(
+        base: &crate::G2Projective<Self>,
+        scalar: &[u64],
+    ) -> crate::G2Projective<Self> {
+        let res = H::mul_projective_g2(&cast::sw_projective(base), scalar);
+        faults::faulty_sw(HookOp::MulProjectiveG2, cast::sw_projective(&res))
+    }
+}
+
+/// Checks `e(a G1, G2) * e(-G1, b G2) == 1`, which holds iff `a == b`.
+fn verify(a: u64, b: u64) -> Option<bool> {
+    let g1 = G1Affine::generator();
+    let g2 = G2Affine::generator();
+    let a_g1 = (g1 * Fr::from(a)).into_affine();
+    let b_g2 = (g2 * Fr::from(b)).into_affine();
+    let f = Bls12_381::multi_miller_loop([a_g1, -g1], [g2, b_g2]);
+    Bls12_381::final_exponentiation(f).map(|out| out.is_zero())
+}
+
+fn non_subgroup_point<P: SWCurveConfig>() -> Affine<P> {
+    let mut rng = test_rng();
+    loop {
+        let x = P::BaseField::rand(&mut rng);
+        if let Some(p) = Affine::<P>::get_point_from_x_unchecked(x, false) {
+            if !p.is_in_correct_subgroup_assuming_on_curve() {
+                return p;
+            }
+        }
+    }
+}
+
+#[test]
+fn faulty_pairing_never_accepts_forged_equation() {
+    faults::clear();
+    assert_eq!(verify(3, 3), Some(true));
+    assert_eq!(verify(3, 5), Some(false));
+
+    for op in [HookOp::MultiMillerLoop, HookOp::FinalExponentiation] {
+        for fault in FAULTS {
+            // A final exponentiation returning one is indistinguishable from a
+            // legit output, the hooks are trusted not to do so.
+            if op == HookOp::FinalExponentiation && fault == Fault::Identity {
+                continue;
+            }
+            faults::inject(op, fault, 0);
+            let res = verify(3, 5);
+            faults::clear();
+            assert_ne!(res, Some(true), "{op:?} {fault:?}");
+            // Malformed outputs are surfaced as errors
+            let surfaced = match op {
+                HookOp::MultiMillerLoop => matches!(fault, Fault::Identity | Fault::Fail),
+                _ => matches!(fault, Fault::Random | Fault::OffCurve | Fault::Fail),
+            };
+            assert_eq!(res.is_none(), surfaced, "{op:?} {fault:?}");
+        }
+    }
+}
+
+#[test]
+fn faulty_miller_loop_on_nth_call() {
+    faults::inject(HookOp::MultiMillerLoop, Fault::Fail, 2);
+    assert_eq!(verify(3, 3), Some(true));
+    assert_eq!(verify(3, 3), None);
+    assert_eq!(verify(3, 3), Some(true));
+    faults::clear();
+}
+
+#[test]
+fn faulty_msm_is_surfaced() {
+    let mut rng = test_rng();
+    let scalars: Vec<_> = (0..4).map(|_| Fr::rand(&mut rng)).collect();
+    let g1 = [G1Affine::generator(); 4];
+    let g2 = [G2Affine::generator(); 4];
+
+    faults::clear();
+    let expected_g1 = G1Projective::msm(&g1, &scalars).unwrap();
+    let expected_g2 = G2Projective::msm(&g2, &scalars).unwrap();
+
+    for fault in FAULTS {
+        faults::inject(HookOp::MsmG1, fault, 0);
+        let res = G1Projective::msm(&g1, &scalars);
+        faults::inject(HookOp::MsmG2, fault, 0);
+        let res2 = G2Projective::msm(&g2, &scalars);
+        faults::clear();
+        if fault == Fault::OffCurve {
+            // Detected, thus rejected
+            assert_eq!(res, Err(g1.len()));
+            assert_eq!(res2, Err(g2.len()));
+        } else {
+            assert_ne!(res, Ok(expected_g1), "{fault:?}");
+            assert_ne!(res2, Ok(expected_g2), "{fault:?}");
+        }
+    }
+}
+
+#[test]
+fn faulty_msm_precompile_fails() {
+    use crate::eip2537::{self, Error, SCALAR_LEN};
+
+    let mut scalar = [0; SCALAR_LEN];
+    scalar[SCALAR_LEN - 1] = 7;
+    let g1 = [&eip2537::encode_g1(&G1Affine::generator())[..], &scalar].concat();
+    let g2 = [&eip2537::encode_g2(&G2Affine::generator())[..], &scalar].concat();
+
+    faults::clear();
+    assert!(eip2537::g1_msm::<FaultyHooks<TestHooks>>(&g1).is_ok());
+    assert!(eip2537::g2_msm::<FaultyHooks<TestHooks>>(&g2).is_ok());
+    faults::inject(HookOp::MsmG1, Fault::OffCurve, 0);
+    assert_eq!(
+        eip2537::g1_msm::<FaultyHooks<TestHooks>>(&g1),
+        Err(Error::Hook)
+    );
+    faults::inject(HookOp::MsmG2, Fault::OffCurve, 0);
+    assert_eq!(
+        eip2537::g2_msm::<FaultyHooks<TestHooks>>(&g2),
+        Err(Error::Hook)
+    );
+    faults::clear();
+}
+
+#[test]
+fn faulty_mul_never_validates_non_subgroup_points() {
+    faults::clear();
+    let p1: G1Affine = non_subgroup_point();
+    let p2: G2Affine = non_subgroup_point();
+    let mut buf1 = Vec::new();
+    p1.serialize_compressed(&mut buf1).unwrap();
+    let mut buf2 = Vec::new();
+    p2.serialize_compressed(&mut buf2).unwrap();
+
+    let check1 = || p1.is_in_correct_subgroup_assuming_on_curve();
+    let decode1 = || G1Affine::deserialize_compressed(&buf1[..]).is_ok();
+    let check2 = || p2.is_in_correct_subgroup_assuming_on_curve();
+    let decode2 = || G2Affine::deserialize_compressed(&buf2[..]).is_ok();
+
+    for fault in FAULTS {
+        for nth in [0, 1, 2] {
+            faults::inject(HookOp::MulProjectiveG1, fault, nth);
+            assert!(!check1() && !decode1());
+            faults::inject(HookOp::MulProjectiveG2, fault, nth);
+            assert!(!check2() && !decode2());
+        }
+    }
+    faults::clear();
+}
+
+#[test]
+fn faulty_mul_off_curve_falls_back() {
+    let g1 = G1Affine::generator().into_group();
+    let g2 = G2Affine::generator().into_group();
+    faults::clear();
+    let expected_g1 = g1 * Fr::from(7);
+    let expected_g2 = g2 * Fr::from(7);
+
+    faults::inject(HookOp::MulProjectiveG1, Fault::OffCurve, 0);
+    assert_eq!(
+        crate::g1::Config::checked_mul(&g1, &[7]),
+        Err(HookError::InvalidOutput)
+    );
+    assert_eq!(g1 * Fr::from(7), expected_g1);
+    faults::inject(HookOp::MulProjectiveG2, Fault::OffCurve, 0);
+    assert_eq!(
+        crate::g2::Config::checked_mul(&g2, &[7]),
+        Err(HookError::InvalidOutput)
+    );
+    assert_eq!(g2 * Fr::from(7), expected_g2);
+    faults::clear();
+}
+
+#[test]
+fn faulty_miller_loop_one_is_surfaced() {
+    let g1 = G1Affine::generator();
+    let g2 = G2Affine::generator();
+    faults::inject(HookOp::MultiMillerLoop, Fault::Identity, 0);
+    assert_eq!(
+        crate::Config::checked_multi_miller_loop([g1], [g2]),
+        Err(HookError::InvalidOutput)
+    );
+    assert_eq!(
+        crate::Config::checked_multi_pairing([g1], [g2]),
+        Err(HookError::InvalidOutput)
+    );
+    faults::clear();
+    assert!(crate::Config::checked_multi_pairing([g1], [g2]).is_ok());
+}
@@ -1,17 +1,22 @@
 use crate::{fq::Fq, fq2::Fq2, fr::Fr, CurveHooks};
+use ark_models_ext::limits::HookLimits;
 
 use ark_algebra_test_templates::*;
 use ark_bls12_381::{
     g1::Config as ArkG1Config, g2::Config as ArkG2Config, Bls12_381 as ArkBls12_381,
 };
-use ark_ff::{fields::Field, One, Zero};
+use ark_ff::{fields::Field, BigInteger, One, PrimeField, Zero};
 use ark_models_ext::{
     pairing::{Pairing, PairingOutput},
     short_weierstrass::SWCurveConfig,
-    AffineRepr, CurveConfig, CurveGroup, PrimeGroup,
+    AffineRepr, CurveConfig, CurveGroup, PrimeGroup, VariableBaseMSM,
 };
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
-use ark_std::{rand::Rng, test_rng, vec, UniformRand};
+use ark_std::{rand::Rng, test_rng, vec, vec::Vec, UniformRand};
+
+mod eip2537;
+mod encoding;
+mod faults;
 
 struct TestHooks;
 
@@ -25,8 +30,8 @@ type G2Config = crate::g2::Config<TestHooks>;
 
 impl CurveHooks for TestHooks {
     fn multi_miller_loop(
-        g1: impl Iterator<Item = <Bls12_381 as Pairing>::G1Prepared>,
-        g2: impl Iterator<Item = <Bls12_381 as Pairing>::G2Prepared>,
+        g1: &[<Bls12_381 as Pairing>::G1Prepared],
+        g2: &[<Bls12_381 as Pairing>::G2Prepared],
     ) -> <Bls12_381 as Pairing>::TargetField {
         test_utils::multi_miller_loop_generic::<Bls12_381, ArkBls12_381>(g1, g2)
     }
@@ -58,6 +63,14 @@ impl CurveHooks for TestHooks {
     fn mul_projective_g2(base: &G2Projective, scalar: &[u64]) -> G2Projective {
         test_utils::mul_projective_sw_generic::<G2Config, ArkG2Config>(base, scalar)
     }
+
+    fn normalize_batch_g1(points: &[G1Projective]) -> Vec<G1Affine> {
+        test_utils::normalize_batch_sw_generic::<G1Config, ArkG1Config>(points)
+    }
+
+    fn normalize_batch_g2(points: &[G2Projective]) -> Vec<G2Affine> {
+        test_utils::normalize_batch_sw_generic::<G2Config, ArkG2Config>(points)
+    }
 }
 
 test_group!(g1; G1Projective; sw);
@@ -65,6 +78,196 @@ test_group!(g2; G2Projective; sw);
 test_group!(pairing_output; PairingOutput<Bls12_381>; msm);
 test_pairing!(ark_pairing; crate::Bls12_381<super::TestHooks>);
 
+#[test]
+fn differential() {
+    use test_utils::differential as diff;
+
+    diff::group::<G1Projective, ark_bls12_381::G1Projective>(&diff::sw_samples::<ArkG1Config>());
+    diff::group::<G2Projective, ark_bls12_381::G2Projective>(&diff::sw_samples::<ArkG2Config>());
+    diff::pairing::<Bls12_381, ArkBls12_381>();
+    diff::miller_loop_accumulator::<Bls12_381, ArkBls12_381>();
+    diff::torus::<Bls12_381, ArkBls12_381, _>();
+    diff::gt_subgroup::<Bls12_381, ArkBls12_381, _>();
+    diff::raw_field::<<Bls12_381 as Pairing>::TargetField, <ArkBls12_381 as Pairing>::TargetField>(
+    );
+    diff::final_exponentiation_batch::<Bls12_381, ArkBls12_381>(
+        Bls12_381::final_exponentiation_batch,
+    );
+    diff::normalize_batch::<G1Projective, ark_bls12_381::G1Projective>(
+        &diff::sw_samples::<ArkG1Config>(),
+        G1Config::normalize_batch,
+    );
+    diff::normalize_batch::<G2Projective, ark_bls12_381::G2Projective>(
+        &diff::sw_samples::<ArkG2Config>(),
+        G2Config::normalize_batch,
+    );
+    diff::msm_small::<G1Projective, ark_bls12_381::G1Projective>(
+        &diff::sw_samples::<ArkG1Config>(),
+        G1Config::msm_small,
+        G1Config::msm_auto,
+    );
+    diff::multi_msm::<G1Projective, ark_bls12_381::G1Projective>(
+        &diff::sw_samples::<ArkG1Config>(),
+        G1Config::multi_msm,
+    );
+    diff::batch_mul::<G1Projective, ark_bls12_381::G1Projective>(
+        &diff::sw_samples::<ArkG1Config>(),
+        G1Config::batch_mul,
+    );
+    diff::msm_small::<G2Projective, ark_bls12_381::G2Projective>(
+        &diff::sw_samples::<ArkG2Config>(),
+        G2Config::msm_small,
+        G2Config::msm_auto,
+    );
+    diff::multi_msm::<G2Projective, ark_bls12_381::G2Projective>(
+        &diff::sw_samples::<ArkG2Config>(),
+        G2Config::multi_msm,
+    );
+    diff::batch_mul::<G2Projective, ark_bls12_381::G2Projective>(
+        &diff::sw_samples::<ArkG2Config>(),
+        G2Config::batch_mul,
+    );
+}
+
+#[test]
+fn hooks_conformance() {
+    use ark_models_ext::metering::{HookOp, LocalMeter, MeteredHooks};
+
+    type Hooks = MeteredHooks<TestHooks, LocalMeter>;
+
+    test_utils::conformance::miller_loop_accumulator::<crate::Bls12_381<Hooks>>();
+    test_utils::conformance::final_exponentiation_batch(
+        crate::Bls12_381::<Hooks>::final_exponentiation_batch,
+    );
+    test_utils::conformance::gt_is_in_subgroup::<crate::Bls12_381<Hooks>>();
+    test_utils::conformance::decompression::<crate::G1Affine<Hooks>>(HookOp::FieldSqrtFq);
+    test_utils::conformance::decompression::<crate::G2Affine<Hooks>>(HookOp::FieldSqrtFq2);
+    test_utils::conformance::sw::<crate::g1::Config<Hooks>>(HookOp::MsmG1, HookOp::MulProjectiveG1);
+    test_utils::conformance::sw::<crate::g2::Config<Hooks>>(HookOp::MsmG2, HookOp::MulProjectiveG2);
+    test_utils::conformance::normalize_batch(
+        HookOp::NormalizeBatchG1,
+        crate::g1::Config::<Hooks>::normalize_batch,
+    );
+    test_utils::conformance::normalize_batch(
+        HookOp::NormalizeBatchG2,
+        crate::g2::Config::<Hooks>::normalize_batch,
+    );
+    test_utils::conformance::msm_small(
+        HookOp::MsmSmallG1,
+        HookOp::MsmG1,
+        crate::g1::Config::<Hooks>::msm_small,
+        crate::g1::Config::<Hooks>::msm_auto,
+    );
+    test_utils::conformance::multi_msm(HookOp::MultiMsmG1, crate::g1::Config::<Hooks>::multi_msm);
+    test_utils::conformance::batch_mul(HookOp::BatchMulG1, crate::g1::Config::<Hooks>::batch_mul);
+    test_utils::conformance::msm_small(
+        HookOp::MsmSmallG2,
+        HookOp::MsmG2,
+        crate::g2::Config::<Hooks>::msm_small,
+        crate::g2::Config::<Hooks>::msm_auto,
+    );
+    test_utils::conformance::multi_msm(HookOp::MultiMsmG2, crate::g2::Config::<Hooks>::multi_msm);
+    test_utils::conformance::batch_mul(HookOp::BatchMulG2, crate::g2::Config::<Hooks>::batch_mul);
+}
+
+#[test]
+fn hook_free_decoding() {
+    use ark_models_ext::metering::{LocalMeter, MeteredHooks};
+    use test_utils::differential as diff;
+
+    type Hooks = MeteredHooks<TestHooks, LocalMeter>;
+
+    test_utils::conformance::decoding::<crate::G1Projective<Hooks>, ark_bls12_381::G1Projective>(
+        &diff::sw_samples::<ArkG1Config>(),
+    );
+    test_utils::conformance::decoding::<crate::G2Projective<Hooks>, ark_bls12_381::G2Projective>(
+        &diff::sw_samples::<ArkG2Config>(),
+    );
+}
+
+#[test]
+fn no_panic_decoding() {
+    use ark_models_ext::{gt::GtOutput, pairing::PairingOutput, torus::TorusOutput, AffineRepr};
+
+    let p = G1Affine::generator();
+    test_utils::no_panic::deserialize(&[p, G1Affine::zero()]);
+    test_utils::no_panic::raw(&[p, G1Affine::zero()]);
+    test_utils::no_panic::deserialize(&[p.into_group(), G1Projective::default()]);
+    test_utils::no_panic::raw(&[p.into_group(), G1Projective::default()]);
+    let q = G2Affine::generator();
+    test_utils::no_panic::deserialize(&[q, G2Affine::zero()]);
+    test_utils::no_panic::raw(&[q, G2Affine::zero()]);
+    test_utils::no_panic::deserialize(&[q.into_group(), G2Projective::default()]);
+    test_utils::no_panic::raw(&[q.into_group(), G2Projective::default()]);
+    let out = Bls12_381::pairing(p, q);
+    test_utils::no_panic::deserialize(&[out, PairingOutput::default()]);
+    test_utils::no_panic::raw(&[out.0, Default::default()]);
+    test_utils::no_panic::deserialize(&[TorusOutput(out), TorusOutput(PairingOutput::default())]);
+    test_utils::no_panic::deserialize(&[GtOutput(out), GtOutput(PairingOutput::default())]);
+}
+
+#[test]
+fn field_hooks_dispatch() {
+    use ark_models_ext::metering::{HookOp, LocalMeter, MeteredHooks};
+
+    type Hooks = MeteredHooks<TestHooks, LocalMeter>;
+
+    let mut rng = test_rng();
+    let calls = |op| LocalMeter::snapshot().get(op).calls;
+
+    let mut buf = Vec::new();
+    G1Affine::rand(&mut rng)
+        .serialize_compressed(&mut buf)
+        .unwrap();
+    LocalMeter::reset();
+    let _ = crate::G1Affine::<Hooks>::deserialize_compressed(&buf[..]).unwrap();
+    assert_eq!(calls(HookOp::FieldSqrtFq), 1);
+    LocalMeter::reset();
+    let _ = crate::G1Affine::<Hooks>::deserialize_compressed_unchecked(&buf[..]).unwrap();
+    assert_eq!(calls(HookOp::FieldSqrtFq), 0);
+
+    let mut buf = Vec::new();
+    G2Affine::rand(&mut rng)
+        .serialize_compressed(&mut buf)
+        .unwrap();
+    LocalMeter::reset();
+    let _ = crate::G2Affine::<Hooks>::deserialize_compressed(&buf[..]).unwrap();
+    assert_eq!(calls(HookOp::FieldSqrtFq2), 1);
+    LocalMeter::reset();
+    let _ = crate::G2Affine::<Hooks>::deserialize_compressed_unchecked(&buf[..]).unwrap();
+    assert_eq!(calls(HookOp::FieldSqrtFq2), 0);
+}
+
+#[test]
+fn field_hooks_fallback() {
+    use ark_models_ext::field_hooks::{self, FieldHooks};
+
+    // Wrong root for every square root request but zero and one.
+    struct LyingHooks;
+
+    impl<F: Field> FieldHooks<F> for LyingHooks {
+        fn sqrt(x: &F) -> Option<F> {
+            Some(*x)
+        }
+    }
+
+    fn check<F: Field>(x: F) {
+        let root = field_hooks::sqrt::<_, LyingHooks>(&x);
+        assert_eq!(root.is_some(), x.sqrt().is_some());
+        if let Some(root) = root {
+            assert_eq!(root.square(), x);
+        }
+    }
+
+    let mut rng = test_rng();
+    check(Fq::zero());
+    check(Fq2::zero());
+    for _ in 0..8 {
+        check(Fq::rand(&mut rng));
+        check(Fq2::rand(&mut rng));
+    }
+}
+
 #[test]
 fn test_g1_endomorphism_beta() {
     assert!(crate::g1::BETA.pow([3u64]).is_one());
@@ -233,3 +436,270 @@ fn test_cofactor_clearing_g2() {
         assert_eq!(optimised, naive);
     }
 }
+
+#[test]
+fn metered_hooks_usage() {
+    use ark_models_ext::metering::{CostTable, HookOp, LocalMeter, MeteredHooks, OpCost};
+
+    type Hooks = MeteredHooks<TestHooks, LocalMeter>;
+
+    let mut rng = test_rng();
+    let bases: Vec<_> = (0..8)
+        .map(|_| crate::G1Projective::<Hooks>::rand(&mut rng).into_affine())
+        .collect();
+    let scalars: Vec<_> = (0..8).map(|_| Fr::rand(&mut rng)).collect();
+    let g1 = crate::G1Affine::<Hooks>::generator();
+    let g2 = crate::G2Affine::<Hooks>::generator();
+
+    LocalMeter::reset();
+
+    let msm = crate::G1Projective::<Hooks>::msm(&bases, &scalars).unwrap();
+    let mul = g1 * Fr::from(5);
+    let pairing = crate::Bls12_381::<Hooks>::pairing(g1, g2);
+
+    let snapshot = LocalMeter::snapshot();
+    let stats = snapshot.get(HookOp::MsmG1);
+    assert_eq!(stats.calls, 1);
+    assert_eq!(stats.items, 8);
+    let scalar_bits: u64 = scalars
+        .iter()
+        .map(|s| s.into_bigint().num_bits() as u64)
+        .sum();
+    assert_eq!(stats.scalar_bits, scalar_bits);
+    let stats = snapshot.get(HookOp::MulProjectiveG1);
+    assert_eq!((stats.calls, stats.items, stats.scalar_bits), (1, 1, 3));
+    let stats = snapshot.get(HookOp::MultiMillerLoop);
+    assert_eq!((stats.calls, stats.items), (1, 1));
+    assert_eq!(snapshot.get(HookOp::FinalExponentiation).calls, 1);
+    assert_eq!(snapshot.get(HookOp::MsmG2).calls, 0);
+    assert_eq!(snapshot.calls(), 4);
+
+    let table = CostTable::new()
+        .with(HookOp::MsmG1, OpCost::new(100, 10, 1))
+        .with(HookOp::MultiMillerLoop, OpCost::new(1000, 500, 0));
+    assert_eq!(snapshot.cost(&table), 100 + 80 + scalar_bits + 1500);
+
+    // Wrapping doesn't alter the results.
+    let bases: Vec<G1Affine> = ark_models_ext::cast::sw_affine_slice(&bases);
+    let expected = G1Projective::msm(&bases, &scalars).unwrap();
+    assert_eq!(msm.into_affine().xy(), expected.into_affine().xy());
+    assert_eq!(
+        mul.into_affine().xy(),
+        (G1Affine::generator() * Fr::from(5)).into_affine().xy()
+    );
+    let expected = Bls12_381::pairing(G1Affine::generator(), G2Affine::generator());
+    assert_eq!(pairing.0, expected.0);
+}
+
+struct LimitedHooks;
+
+impl CurveHooks for LimitedHooks {
+    const LIMITS: HookLimits = HookLimits::new(2, 1, 2);
+
+    fn multi_miller_loop(
+        g1: &[<crate::Bls12_381<Self> as Pairing>::G1Prepared],
+        g2: &[<crate::Bls12_381<Self> as Pairing>::G2Prepared],
+    ) -> <crate::Bls12_381<Self> as Pairing>::TargetField {
+        test_utils::multi_miller_loop_generic::<crate::Bls12_381<Self>, ArkBls12_381>(g1, g2)
+    }
+
+    fn final_exponentiation(
+        target: <crate::Bls12_381<Self> as Pairing>::TargetField,
+    ) -> <crate::Bls12_381<Self> as Pairing>::TargetField {
+        test_utils::final_exponentiation_generic::<crate::Bls12_381<Self>, ArkBls12_381>(target)
+    }
+
+    fn msm_g1(
+        bases: &[crate::G1Affine<Self>],
+        scalars: &[<crate::g1::Config<Self> as CurveConfig>::ScalarField],
+    ) -> crate::G1Projective<Self> {
+        test_utils::msm_sw_generic::<crate::g1::Config<Self>, ArkG1Config>(bases, scalars)
+    }
+
+    fn msm_g2(
+        bases: &[crate::G2Affine<Self>],
+        scalars: &[<crate::g2::Config<Self> as CurveConfig>::ScalarField],
+    ) -> crate::G2Projective<Self> {
+        test_utils::msm_sw_generic::<crate::g2::Config<Self>, ArkG2Config>(bases, scalars)
+    }
+
+    fn mul_projective_g1(
+        base: &crate::G1Projective<Self>,
+        scalar: &[u64],
+    ) -> crate::G1Projective<Self> {
+        test_utils::mul_projective_sw_generic::<crate::g1::Config<Self>, ArkG1Config>(base, scalar)
+    }
+
+    fn mul_projective_g2(
+        base: &crate::G2Projective<Self>,
+        scalar: &[u64],
+    ) -> crate::G2Projective<Self> {
+        test_utils::mul_projective_sw_generic::<crate::g2::Config<Self>, ArkG2Config>(base, scalar)
+    }
+}
+
+#[test]
+fn hook_limits_enforced() {
+    use ark_models_ext::{checked::HookError, limits::LimitError};
+
+    type G1Affine = crate::G1Affine<LimitedHooks>;
+    type G1Projective = crate::G1Projective<LimitedHooks>;
+    type G1Config = crate::g1::Config<LimitedHooks>;
+    type G2Affine = crate::G2Affine<LimitedHooks>;
+    type Bls12_381 = crate::Bls12_381<LimitedHooks>;
+
+    let g1 = G1Affine::generator();
+    let g2 = G2Affine::generator();
+
+    // MSM length
+    let scalars = [Fr::one(), Fr::one(), Fr::one()];
+    assert_eq!(G1Projective::msm(&[g1, g1], &scalars[..2]), Ok(g1 + g1));
+    assert_eq!(G1Projective::msm(&[g1, g1, g1], &scalars), Err(3));
+    assert_eq!(G1Projective::msm(&[g1, g1], &scalars), Err(2));
+    assert_eq!(
+        G1Config::checked_msm(&[g1, g1, g1], &scalars),
+        Err(HookError::Limit(LimitError::MsmLength { len: 3, max: 2 }))
+    );
+    assert_eq!(
+        G1Config::checked_msm(&[g1, g1], &scalars),
+        Err(HookError::LengthMismatch { left: 2, right: 3 })
+    );
+    assert_eq!(
+        G1Config::msm_small(&[g1, g1, g1], &[1, 1, 1]),
+        Err(HookError::Limit(LimitError::MsmLength { len: 3, max: 2 }))
+    );
+
+    // Multi MSM, bound by the total number of bases
+    assert_eq!(
+        G1Config::multi_msm(&[
+            (&[g1, g1], &scalars[..2]),
+            (&[g1], &scalars[..1]),
+            (&[g1], &scalars[..2]),
+        ]),
+        vec![
+            Ok(g1 + g1),
+            Err(HookError::Limit(LimitError::MsmLength { len: 3, max: 2 })),
+            Err(HookError::LengthMismatch { left: 1, right: 2 }),
+        ]
+    );
+
+    // Scalar limbs, most significant zero limbs are not accounted
+    let g = g1.into_group();
+    assert!(!g1.mul_bigint([1, 1, 0, 0]).is_zero());
+    assert_eq!(
+        G1Config::checked_mul(&g, &[1, 1, 1, 0]),
+        Err(HookError::Limit(LimitError::ScalarLimbs { len: 3, max: 2 }))
+    );
+
+    // Batch multiplication, bound by both the MSM length and the scalar limbs
+    assert_eq!(G1Config::batch_mul(&g, &scalars[..2]), Ok(vec![g1, g1]));
+    assert_eq!(
+        G1Config::batch_mul(&g, &scalars),
+        Err(HookError::Limit(LimitError::MsmLength { len: 3, max: 2 }))
+    );
+    assert_eq!(
+        G1Config::batch_mul(&g, &[Fr::from(u128::MAX)]),
+        Ok(vec![(g1 * Fr::from(u128::MAX)).into_affine()])
+    );
+    assert_eq!(
+        G1Config::batch_mul(&g, &[-Fr::one()]),
+        Err(HookError::Limit(LimitError::ScalarLimbs { len: 4, max: 2 }))
+    );
+
+    // Miller loop pairs
+    let res = Bls12_381::multi_pairing([g1], [g2]);
+    let expected = ArkBls12_381::pairing(
+        ark_bls12_381::G1Affine::generator(),
+        ark_bls12_381::G2Affine::generator(),
+    );
+    assert_eq!(res.0, expected.0);
+    let f = Bls12_381::multi_miller_loop([g1, g1], [g2, g2]);
+    assert!(f.0.is_zero());
+    assert!(Bls12_381::final_exponentiation(f).is_none());
+    assert_eq!(
+        crate::Config::<LimitedHooks>::checked_multi_pairing([g1, g1], [g2, g2]),
+        Err(HookError::Limit(LimitError::MillerLoopPairs {
+            len: 2,
+            max: 1
+        }))
+    );
+}
+
+#[test]
+fn hook_limits_mul_rejected() {
+    let g1 = crate::G1Affine::<LimitedHooks>::generator();
+    assert!(g1.mul_bigint([1, 1, 1, 0]).is_zero());
+    let expected = test_utils::mul_plain(g1.into_group(), [1, 1, 0, 0]);
+    assert_eq!(g1.mul_bigint([1, 1, 0, 0]), expected);
+}
+
+#[test]
+fn hook_limits_multi_pairing_rejected() {
+    use ark_models_ext::gt::{self, TargetGroup};
+
+    type Bls12_381 = crate::Bls12_381<LimitedHooks>;
+
+    // One pair over `max_pairs`
+    let g1 = crate::G1Affine::<LimitedHooks>::generator();
+    let g2 = crate::G2Affine::<LimitedHooks>::generator();
+    let res = Bls12_381::multi_pairing([g1, -g1], [g2, g2]);
+    assert_eq!(res, gt::rejected());
+    assert!(!res.is_zero());
+    assert!(!Bls12_381::is_in_subgroup(&res));
+}
+
+#[test]
+fn multi_miller_loop_inputs() {
+    use ark_models_ext::{
+        checked::HookError,
+        limits::LimitError,
+        metering::{HookOp, LocalMeter, MeteredHooks},
+    };
+
+    type Hooks = MeteredHooks<TestHooks, LocalMeter>;
+    type Config = crate::Config<Hooks>;
+
+    let g1 = crate::G1Affine::<Hooks>::generator();
+    let g2 = crate::G2Affine::<Hooks>::generator();
+    let z1 = crate::G1Affine::<Hooks>::zero();
+    let z2 = crate::G2Affine::<Hooks>::zero();
+    let expected = ArkBls12_381::multi_miller_loop(
+        [ark_bls12_381::G1Affine::generator()],
+        [ark_bls12_381::G2Affine::generator()],
+    );
+
+    // Length mismatch, never truncated
+    LocalMeter::reset();
+    assert_eq!(
+        Config::checked_multi_miller_loop([g1, g1], [g2]),
+        Err(HookError::LengthMismatch { left: 2, right: 1 })
+    );
+    assert!(crate::Bls12_381::<Hooks>::multi_miller_loop([g1, g1], [g2])
+        .0
+        .is_zero());
+    assert_eq!(LocalMeter::snapshot().calls(), 0);
+
+    // Pairs with a zero point are dropped
+    LocalMeter::reset();
+    let res = Config::checked_multi_miller_loop([z1, g1, g1], [g2, g2, z2]).unwrap();
+    assert_eq!(res.0, expected.0);
+    let stats = LocalMeter::snapshot().get(HookOp::MultiMillerLoop);
+    assert_eq!((stats.calls, stats.items), (1, 1));
+
+    // No hook call if no pair is left
+    LocalMeter::reset();
+    let res = Config::checked_multi_miller_loop([z1, g1], [g2, z2]).unwrap();
+    assert!(res.0.is_one());
+    assert_eq!(LocalMeter::snapshot().calls(), 0);
+
+    // Limits
+    let g1 = crate::G1Affine::<LimitedHooks>::generator();
+    let g2 = crate::G2Affine::<LimitedHooks>::generator();
+    assert_eq!(
+        crate::Config::<LimitedHooks>::checked_multi_miller_loop([g1, g1], [g2, g2]),
+        Err(HookError::Limit(LimitError::MillerLoopPairs {
+            len: 2,
+            max: 1
+        }))
+    );
+}
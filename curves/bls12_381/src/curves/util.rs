@@ -1,7 +1,14 @@
-use ark_ff::{BigInteger384, PrimeField};
-use ark_models_ext::{short_weierstrass::Affine, AffineRepr};
-use ark_serialize::SerializationError;
-use ark_std::{io::Read, vec::Vec};
+use ark_ff::{BigInteger384, Field, PrimeField};
+use ark_models_ext::{
+    field_hooks::{self, FieldHooks},
+    short_weierstrass::{Affine, SWCurveConfig},
+    AffineRepr,
+};
+use ark_serialize::{SerializationError, Validate};
+use ark_std::{
+    io::{self, ErrorKind, Read},
+    Zero,
+};
 
 use crate::CurveHooks;
 use crate::{g1::Config as G1Config, g2::Config as G2Config};
@@ -10,6 +17,9 @@ use ark_bls12_381::{fq::Fq, fq2::Fq2};
 pub const G1_SERIALIZED_SIZE: usize = 48;
 pub const G2_SERIALIZED_SIZE: usize = 96;
 
+/// Mask of the first byte bits which are not flags.
+const FLAGS_MASK: u8 = 0b0001_1111;
+
 pub struct EncodingFlags {
     pub is_compressed: bool,
     pub is_infinity: bool,
@@ -17,15 +27,12 @@ pub struct EncodingFlags {
 }
 
 impl EncodingFlags {
-    pub fn get_flags(bytes: Vec<u8>) -> Self {
-        let compression_flag_set = (bytes[0] >> 7) & 1;
-        let infinity_flag_set = (bytes[0] >> 6) & 1;
-        let sort_flag_set = (bytes[0] >> 5) & 1;
-
+    /// Flags of the encoding starting with `first`.
+    pub fn get_flags(first: u8) -> Self {
         Self {
-            is_compressed: compression_flag_set == 1,
-            is_infinity: infinity_flag_set == 1,
-            is_lexographically_largest: sort_flag_set == 1,
+            is_compressed: (first >> 7) & 1 == 1,
+            is_infinity: (first >> 6) & 1 == 1,
+            is_lexographically_largest: (first >> 5) & 1 == 1,
         }
     }
     pub fn encode_flags(&self, bytes: &mut [u8]) {
@@ -43,19 +50,114 @@ impl EncodingFlags {
     }
 }
 
-pub(crate) fn deserialize_fq(bytes: [u8; 48]) -> Option<Fq> {
-    let mut tmp = BigInteger384::new([0, 0, 0, 0, 0, 0]);
+/// Points encoding strictness.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    /// Reject every non-canonical encoding.
+    Strict,
+    /// As upstream *Arkworks*, a point at infinity with other bits set decodes to
+    /// zero and the sort flag is ignored on the point at infinity and on uncompressed
+    /// points.
+    Lax,
+}
+
+/// Non-canonical point encoding, rejected by the [`Encoding::Strict`] decoding.
+///
+/// Reported as a [`SerializationError::IoError`] of kind `InvalidData` carrying the
+/// typed error, which `EncodingError::of` recovers with `std`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EncodingError {
+    /// Encoding length not a non-zero multiple of the `Fq` encoding length.
+    Length,
+    /// Compression flag not matching the expected encoding.
+    Compression,
+    /// Point at infinity with any bit other than the flags set.
+    InfinityData,
+    /// Sort flag set on the point at infinity.
+    InfinitySort,
+    /// Sort flag set on an uncompressed point.
+    UncompressedSort,
+    /// Coordinate not less than the modulus.
+    Field,
+}
+
+impl ark_std::fmt::Display for EncodingError {
+    fn fmt(&self, f: &mut ark_std::fmt::Formatter<'_>) -> ark_std::fmt::Result {
+        let msg = match self {
+            EncodingError::Length => "invalid encoding length",
+            EncodingError::Compression => "unexpected compression flag",
+            EncodingError::InfinityData => "non-zero point at infinity data",
+            EncodingError::InfinitySort => "sort flag on point at infinity",
+            EncodingError::UncompressedSort => "sort flag on uncompressed point",
+            EncodingError::Field => "non-canonical field element",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl ark_std::error::Error for EncodingError {}
+
+impl EncodingError {
+    /// Reason of a deserialization failure caused by a non-canonical encoding.
+    ///
+    /// Recovered by downcasting the I/O error, which requires `std`. Without it the
+    /// reason is only reported by [`check_encoding`].
+    #[cfg(feature = "std")]
+    pub fn of(err: &SerializationError) -> Option<Self> {
+        let SerializationError::IoError(err) = err else {
+            return None;
+        };
+        if err.kind() != ErrorKind::InvalidData {
+            return None;
+        }
+        err.get_ref()?.downcast_ref::<Self>().copied()
+    }
+}
+
+impl From<EncodingError> for SerializationError {
+    fn from(err: EncodingError) -> Self {
+        SerializationError::IoError(io::Error::new(ErrorKind::InvalidData, err))
+    }
+}
 
-    // Note: The following unwraps are if the compiler cannot convert
-    // the byte slice into [u8;8], we know this is infallible since we
-    // are providing the indices at compile time and bytes has a fixed size
-    tmp.0[5] = u64::from_be_bytes(<[u8; 8]>::try_from(bytes[0..8].to_vec()).unwrap());
-    tmp.0[4] = u64::from_be_bytes(<[u8; 8]>::try_from(bytes[8..16].to_vec()).unwrap());
-    tmp.0[3] = u64::from_be_bytes(<[u8; 8]>::try_from(bytes[16..24].to_vec()).unwrap());
-    tmp.0[2] = u64::from_be_bytes(<[u8; 8]>::try_from(bytes[24..32].to_vec()).unwrap());
-    tmp.0[1] = u64::from_be_bytes(<[u8; 8]>::try_from(bytes[32..40].to_vec()).unwrap());
-    tmp.0[0] = u64::from_be_bytes(<[u8; 8]>::try_from(bytes[40..48].to_vec()).unwrap());
+/// Check that `bytes` is the canonical encoding of a point, compressed or not as
+/// requested by `compressed`.
+///
+/// `bytes` is made of 48 bytes chunks, one per `Fq` coordinate. The curve equation
+/// is not checked.
+pub fn check_encoding(bytes: &[u8], compressed: bool) -> Result<(), EncodingError> {
+    let chunks = bytes.chunks_exact(G1_SERIALIZED_SIZE);
+    if bytes.is_empty() || !chunks.remainder().is_empty() {
+        return Err(EncodingError::Length);
+    }
+    let first = bytes[0];
+    let flags = EncodingFlags::get_flags(first);
+    if flags.is_compressed != compressed {
+        return Err(EncodingError::Compression);
+    }
+    if flags.is_infinity {
+        if flags.is_lexographically_largest {
+            return Err(EncodingError::InfinitySort);
+        }
+        if first & FLAGS_MASK != 0 || bytes[1..].iter().any(|b| *b != 0) {
+            return Err(EncodingError::InfinityData);
+        }
+        return Ok(());
+    }
+    if !compressed && flags.is_lexographically_largest {
+        return Err(EncodingError::UncompressedSort);
+    }
+    for i in 0..chunks.len() {
+        read_fq_with_offset(bytes, i, i == 0).map_err(|_| EncodingError::Field)?;
+    }
+    Ok(())
+}
 
+pub(crate) fn deserialize_fq(bytes: &[u8; G1_SERIALIZED_SIZE]) -> Option<Fq> {
+    let mut tmp = BigInteger384::new([0; 6]);
+    for (limb, chunk) in tmp.0.iter_mut().zip(bytes.rchunks_exact(8)) {
+        *limb = u64::from_be_bytes(chunk.try_into().expect("chunks of 8 bytes; qed"));
+    }
     Fq::from_bigint(tmp)
 }
 
@@ -74,24 +176,56 @@ pub(crate) fn serialize_fq(field: Fq) -> [u8; 48] {
     result
 }
 
+/// Read the `offset`-th `Fq` element of `bytes`, masking away the flag bits if `mask`.
 pub(crate) fn read_fq_with_offset(
-    bytes: Vec<u8>,
+    bytes: &[u8],
     offset: usize,
     mask: bool,
 ) -> Result<Fq, SerializationError> {
-    let mut tmp = [0; G1_SERIALIZED_SIZE];
-    // read `G1_SERIALIZED_SIZE` bytes
-    tmp.copy_from_slice(&bytes[offset * G1_SERIALIZED_SIZE..G1_SERIALIZED_SIZE * (offset + 1)]);
-
+    let mut tmp: [u8; G1_SERIALIZED_SIZE] = bytes
+        .chunks_exact(G1_SERIALIZED_SIZE)
+        .nth(offset)
+        .and_then(|chunk| chunk.try_into().ok())
+        .ok_or(SerializationError::InvalidData)?;
     if mask {
         // Mask away the flag bits
-        tmp[0] &= 0b0001_1111;
+        tmp[0] &= FLAGS_MASK;
+    }
+    deserialize_fq(&tmp).ok_or(SerializationError::InvalidData)
+}
+
+// Upstream `Affine::get_point_from_x_unchecked`, with the square root jumping into
+// the field hooks when validating. Unchecked deserialization must never call the
+// hooks (see `ark_models_ext::decoding`), thus takes the square root in software.
+fn get_point_from_x_unchecked<P, F>(
+    x: P::BaseField,
+    greatest: bool,
+    validate: Validate,
+) -> Option<Affine<P>>
+where
+    P: SWCurveConfig,
+    F: FieldHooks<P::BaseField>,
+{
+    let mut x3_plus_ax_plus_b = P::add_b(x.square() * x);
+    if !P::COEFF_A.is_zero() {
+        x3_plus_ax_plus_b += P::mul_by_a(x);
     }
-    deserialize_fq(tmp).ok_or(SerializationError::InvalidData)
+    let y = match validate {
+        Validate::Yes => field_hooks::sqrt::<_, F>(&x3_plus_ax_plus_b),
+        Validate::No => x3_plus_ax_plus_b.sqrt(),
+    }?;
+    let neg_y = -y;
+    let (smaller, larger) = if y < neg_y { (y, neg_y) } else { (neg_y, y) };
+    Some(Affine::new_unchecked(
+        x,
+        if greatest { larger } else { smaller },
+    ))
 }
 
 pub(crate) fn read_g1_compressed<R: Read, H: CurveHooks>(
     mut reader: R,
+    validate: Validate,
+    encoding: Encoding,
 ) -> Result<Affine<G1Config<H>>, SerializationError> {
     let mut bytes = [0u8; G1_SERIALIZED_SIZE];
     reader
@@ -99,8 +233,12 @@ pub(crate) fn read_g1_compressed<R: Read, H: CurveHooks>(
         .ok()
         .ok_or(SerializationError::InvalidData)?;
 
+    if encoding == Encoding::Strict {
+        check_encoding(&bytes, true)?;
+    }
+
     // Obtain the three flags from the start of the byte sequence
-    let flags = EncodingFlags::get_flags(bytes[..].to_vec());
+    let flags = EncodingFlags::get_flags(bytes[0]);
 
     // we expect to be deserializing a compressed point
     if !flags.is_compressed {
@@ -112,24 +250,33 @@ pub(crate) fn read_g1_compressed<R: Read, H: CurveHooks>(
     }
 
     // Attempt to obtain the x-coordinate
-    let x = read_fq_with_offset(bytes.to_vec(), 0, true)?;
+    let x = read_fq_with_offset(&bytes, 0, true)?;
 
-    let p = Affine::<G1Config<H>>::get_point_from_x_unchecked(x, flags.is_lexographically_largest)
-        .ok_or(SerializationError::InvalidData)?;
+    let p = get_point_from_x_unchecked::<G1Config<H>, crate::Config<H>>(
+        x,
+        flags.is_lexographically_largest,
+        validate,
+    )
+    .ok_or(SerializationError::InvalidData)?;
 
     Ok(p)
 }
 
 pub(crate) fn read_g1_uncompressed<R: Read, H: CurveHooks>(
     mut reader: R,
+    encoding: Encoding,
 ) -> Result<Affine<G1Config<H>>, SerializationError> {
     let mut bytes = [0u8; 2 * G1_SERIALIZED_SIZE];
     reader
         .read_exact(&mut bytes)
         .map_err(|_| SerializationError::InvalidData)?;
 
+    if encoding == Encoding::Strict {
+        check_encoding(&bytes, false)?;
+    }
+
     // Obtain the three flags from the start of the byte sequence
-    let flags = EncodingFlags::get_flags(bytes[..].to_vec());
+    let flags = EncodingFlags::get_flags(bytes[0]);
 
     // we expect to be deserializing an uncompressed point
     if flags.is_compressed {
@@ -141,9 +288,9 @@ pub(crate) fn read_g1_uncompressed<R: Read, H: CurveHooks>(
     }
 
     // Attempt to obtain the x-coordinate
-    let x = read_fq_with_offset(bytes.to_vec(), 0, true)?;
+    let x = read_fq_with_offset(&bytes, 0, true)?;
     // Attempt to obtain the y-coordinate
-    let y = read_fq_with_offset(bytes.to_vec(), 1, false)?;
+    let y = read_fq_with_offset(&bytes, 1, false)?;
 
     let p = Affine::<G1Config<H>>::new_unchecked(x, y);
 
@@ -152,14 +299,20 @@ pub(crate) fn read_g1_uncompressed<R: Read, H: CurveHooks>(
 
 pub(crate) fn read_g2_compressed<R: Read, H: CurveHooks>(
     mut reader: R,
+    validate: Validate,
+    encoding: Encoding,
 ) -> Result<Affine<G2Config<H>>, SerializationError> {
     let mut bytes = [0u8; G2_SERIALIZED_SIZE];
     reader
         .read_exact(&mut bytes)
         .map_err(|_| SerializationError::InvalidData)?;
 
+    if encoding == Encoding::Strict {
+        check_encoding(&bytes, true)?;
+    }
+
     // Obtain the three flags from the start of the byte sequence
-    let flags = EncodingFlags::get_flags(bytes.to_vec());
+    let flags = EncodingFlags::get_flags(bytes[0]);
 
     // we expect to be deserializing a compressed point
     if !flags.is_compressed {
@@ -171,27 +324,36 @@ pub(crate) fn read_g2_compressed<R: Read, H: CurveHooks>(
     }
 
     // Attempt to obtain the x-coordinate
-    let xc1 = read_fq_with_offset(bytes.to_vec(), 0, true)?;
-    let xc0 = read_fq_with_offset(bytes.to_vec(), 1, false)?;
+    let xc1 = read_fq_with_offset(&bytes, 0, true)?;
+    let xc0 = read_fq_with_offset(&bytes, 1, false)?;
 
     let x = Fq2::new(xc0, xc1);
 
-    let p = Affine::<G2Config<H>>::get_point_from_x_unchecked(x, flags.is_lexographically_largest)
-        .ok_or(SerializationError::InvalidData)?;
+    let p = get_point_from_x_unchecked::<G2Config<H>, crate::Config<H>>(
+        x,
+        flags.is_lexographically_largest,
+        validate,
+    )
+    .ok_or(SerializationError::InvalidData)?;
 
     Ok(p)
 }
 
 pub(crate) fn read_g2_uncompressed<R: Read, H: CurveHooks>(
     mut reader: R,
+    encoding: Encoding,
 ) -> Result<Affine<G2Config<H>>, SerializationError> {
     let mut bytes = [0u8; 2 * G2_SERIALIZED_SIZE];
     reader
         .read_exact(&mut bytes)
         .map_err(|_| SerializationError::InvalidData)?;
 
+    if encoding == Encoding::Strict {
+        check_encoding(&bytes, false)?;
+    }
+
     // Obtain the three flags from the start of the byte sequence
-    let flags = EncodingFlags::get_flags(bytes.to_vec());
+    let flags = EncodingFlags::get_flags(bytes[0]);
 
     // we expect to be deserializing an uncompressed point
     if flags.is_compressed {
@@ -203,13 +365,13 @@ pub(crate) fn read_g2_uncompressed<R: Read, H: CurveHooks>(
     }
 
     // Attempt to obtain the x-coordinate
-    let xc1 = read_fq_with_offset(bytes.to_vec(), 0, true)?;
-    let xc0 = read_fq_with_offset(bytes.to_vec(), 1, false)?;
+    let xc1 = read_fq_with_offset(&bytes, 0, true)?;
+    let xc0 = read_fq_with_offset(&bytes, 1, false)?;
     let x = Fq2::new(xc0, xc1);
 
     // Attempt to obtain the y-coordinate
-    let yc1 = read_fq_with_offset(bytes.to_vec(), 2, false)?;
-    let yc0 = read_fq_with_offset(bytes.to_vec(), 3, false)?;
+    let yc1 = read_fq_with_offset(&bytes, 2, false)?;
+    let yc0 = read_fq_with_offset(&bytes, 3, false)?;
     let y = Fq2::new(yc0, yc1);
 
     let p = Affine::<G2Config<H>>::new_unchecked(x, y);
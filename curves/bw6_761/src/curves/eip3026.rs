@@ -0,0 +1,129 @@
+//! [EIP-3026](https://eips.ethereum.org/EIPS/eip-3026) precompiles.
+//!
+//! Byte exact input parsing and output encoding of the *BW6-761* add, MSM and
+//! pairing precompiles, with the operations executed via the curve hooks.
+//!
+//! A base field element is encoded as 96 big endian bytes and must be canonical.
+//! G1 and G2 are both defined over the base field, and a point is encoded as
+//! `x || y`, the point at infinity being all zeros. A scalar is encoded as 64 big
+//! endian bytes and is not required to be less than the group order.
+//!
+//! The MSM and pairing inputs are checked to belong to the prime order subgroup,
+//! while the addition inputs are only checked to be on the curve.
+
+use crate::{g1, g2, CurveHooks, Fq, Fr, BW6_761};
+use ark_models_ext::{pairing::Pairing, precompile, AffineRepr, CurveGroup};
+use ark_std::Zero;
+
+pub use ark_models_ext::precompile::Error;
+
+/// Encoded base field element length.
+pub const FP_LEN: usize = 96;
+/// Encoded G1 point length.
+pub const G1_LEN: usize = 2 * FP_LEN;
+/// Encoded G2 point length.
+pub const G2_LEN: usize = 2 * FP_LEN;
+/// Encoded scalar length.
+pub const SCALAR_LEN: usize = 64;
+/// Pairing output length.
+pub const PAIRING_LEN: usize = 32;
+
+/// Decode a base field element.
+pub fn decode_fp(bytes: &[u8; FP_LEN]) -> Result<Fq, Error> {
+    precompile::decode_fp(bytes)
+}
+
+/// Encode a base field element.
+pub fn encode_fp(fp: &Fq) -> [u8; FP_LEN] {
+    precompile::encode_fp(fp)
+}
+
+/// Decode a G1 point, checking the subgroup membership if `subgroup_check`.
+pub fn decode_g1<H: CurveHooks>(
+    bytes: &[u8; G1_LEN],
+    subgroup_check: bool,
+) -> Result<g1::G1Affine<H>, Error> {
+    let (x, y) = precompile::split::<FP_LEN, FP_LEN>(bytes);
+    precompile::point(decode_fp(x)?, decode_fp(y)?, subgroup_check)
+}
+
+/// Encode a G1 point.
+pub fn encode_g1<H: CurveHooks>(p: &g1::G1Affine<H>) -> [u8; G1_LEN] {
+    let mut res = [0; G1_LEN];
+    if let Some((x, y)) = p.xy() {
+        res[..FP_LEN].copy_from_slice(&encode_fp(&x));
+        res[FP_LEN..].copy_from_slice(&encode_fp(&y));
+    }
+    res
+}
+
+/// Decode a G2 point, checking the subgroup membership if `subgroup_check`.
+pub fn decode_g2<H: CurveHooks>(
+    bytes: &[u8; G2_LEN],
+    subgroup_check: bool,
+) -> Result<g2::G2Affine<H>, Error> {
+    let (x, y) = precompile::split::<FP_LEN, FP_LEN>(bytes);
+    precompile::point(decode_fp(x)?, decode_fp(y)?, subgroup_check)
+}
+
+/// Encode a G2 point.
+pub fn encode_g2<H: CurveHooks>(p: &g2::G2Affine<H>) -> [u8; G2_LEN] {
+    let mut res = [0; G2_LEN];
+    if let Some((x, y)) = p.xy() {
+        res[..FP_LEN].copy_from_slice(&encode_fp(&x));
+        res[FP_LEN..].copy_from_slice(&encode_fp(&y));
+    }
+    res
+}
+
+/// `BW6_G1_ADD`: sum of two G1 points.
+pub fn g1_add<H: CurveHooks>(input: &[u8]) -> Result<[u8; G1_LEN], Error> {
+    let input = precompile::exact::<{ 2 * G1_LEN }>(input)?;
+    let (a, b) = precompile::split::<G1_LEN, G1_LEN>(input);
+    let res = decode_g1::<H>(a, false)? + decode_g1::<H>(b, false)?;
+    Ok(encode_g1(&res.into_affine()))
+}
+
+/// `BW6_G2_ADD`: sum of two G2 points.
+pub fn g2_add<H: CurveHooks>(input: &[u8]) -> Result<[u8; G2_LEN], Error> {
+    let input = precompile::exact::<{ 2 * G2_LEN }>(input)?;
+    let (a, b) = precompile::split::<G2_LEN, G2_LEN>(input);
+    let res = decode_g2::<H>(a, false)? + decode_g2::<H>(b, false)?;
+    Ok(encode_g2(&res.into_affine()))
+}
+
+/// `BW6_G1_MULTIEXP`: multi scalar multiplication of G1 points, jumping into
+/// the `msm_g1` hook.
+pub fn g1_msm<H: CurveHooks>(input: &[u8]) -> Result<[u8; G1_LEN], Error> {
+    let (bases, scalars) =
+        precompile::msm_input::<G1_LEN, SCALAR_LEN, _, Fr>(input, |p| decode_g1::<H>(p, true))?;
+    let res = g1::Config::<H>::checked_msm(&bases, &scalars).map_err(|_| Error::Hook)?;
+    Ok(encode_g1(&res.into_affine()))
+}
+
+/// `BW6_G2_MULTIEXP`: multi scalar multiplication of G2 points, jumping into
+/// the `msm_g2` hook.
+pub fn g2_msm<H: CurveHooks>(input: &[u8]) -> Result<[u8; G2_LEN], Error> {
+    let (bases, scalars) =
+        precompile::msm_input::<G2_LEN, SCALAR_LEN, _, Fr>(input, |p| decode_g2::<H>(p, true))?;
+    let res = g2::Config::<H>::checked_msm(&bases, &scalars).map_err(|_| Error::Hook)?;
+    Ok(encode_g2(&res.into_affine()))
+}
+
+/// `BW6_PAIRING`: whether the product of the pairings of the given pairs is
+/// the identity, jumping into the `multi_miller_loop` and `final_exponentiation`
+/// hooks.
+///
+/// The output is 32 bytes, the last one being one on success and zero otherwise.
+pub fn pairing<H: CurveHooks>(input: &[u8]) -> Result<[u8; PAIRING_LEN], Error> {
+    let (g1, g2) = precompile::pairs::<G1_LEN, G2_LEN, _, _>(
+        input,
+        |a| decode_g1::<H>(a, true),
+        |b| decode_g2::<H>(b, true),
+    )?;
+    let f = crate::Config::<H>::checked_multi_miller_loop(g1, g2).map_err(|_| Error::Hook)?;
+    let out = BW6_761::<H>::final_exponentiation(f).ok_or(Error::Hook)?;
+    let mut res = [0; PAIRING_LEN];
+    res[PAIRING_LEN - 1] = out.is_zero() as u8;
+    Ok(res)
+}
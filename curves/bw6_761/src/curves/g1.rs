@@ -1,11 +1,17 @@
 use crate::CurveHooks;
 
 use ark_bw6_761::g1::Config as ArkConfig;
+use ark_ec::scalar_mul::glv::GLVConfig;
 use ark_models_ext::{
+    batch,
+    batch::MsmJob,
     bw6,
-    {short_weierstrass::SWCurveConfig, CurveConfig},
+    bw6::BW6Config,
+    checked::{self, HookError},
+    validation,
+    {short_weierstrass::SWCurveConfig, CurveConfig, PrimeGroup},
 };
-use ark_std::marker::PhantomData;
+use ark_std::{marker::PhantomData, vec::Vec, Zero};
 
 pub use ark_bw6_761::g1::{G1_GENERATOR_X, G1_GENERATOR_Y};
 
@@ -30,18 +36,20 @@ impl<H: CurveHooks> SWCurveConfig for Config<H> {
     const GENERATOR: G1Affine<H> = G1Affine::<H>::new_unchecked(G1_GENERATOR_X, G1_GENERATOR_Y);
 
     /// Multi scalar multiplication jumping into the user-defined `msm_g1` hook.
+    ///
+    /// Fails on any `checked_msm` failure, see [`checked::upstream_msm`].
     #[inline(always)]
     fn msm(bases: &[G1Affine<H>], scalars: &[Self::ScalarField]) -> Result<G1Projective<H>, usize> {
-        if bases.len() != scalars.len() {
-            return Err(bases.len().min(scalars.len()));
-        }
-        Ok(H::msm_g1(bases, scalars))
+        checked::upstream_msm(bases.len(), Self::checked_msm(bases, scalars))
     }
 
     /// Projective multiplication jumping into the user-defined `mul_projective_g1` hook.
+    ///
+    /// The identity if the scalar exceeds the hooks limits, see
+    /// [`checked::upstream_mul`].
     #[inline(always)]
     fn mul_projective(base: &G1Projective<H>, scalar: &[u64]) -> G1Projective<H> {
-        H::mul_projective_g1(base, scalar)
+        checked::upstream_mul(base, scalar, Self::checked_mul(base, scalar))
     }
 
     /// Affine multiplication jumping into the user-defined `mul_projective_g1` hook.
@@ -54,4 +62,141 @@ impl<H: CurveHooks> SWCurveConfig for Config<H> {
     fn mul_by_a(elem: Self::BaseField) -> Self::BaseField {
         <ArkConfig as SWCurveConfig>::mul_by_a(elem)
     }
+
+    /// Subgroup check via the endomorphism, i.e. `[x^3 - x^2 + 1]P + endomorphism([x + 1]P) = 0`.
+    ///
+    /// The coefficients are a short vector of the lattice of the `(a, b)` such that
+    /// `a + b * lambda = 0 mod r`, with `lambda` the endomorphism eigenvalue.
+    /// Only multiplications by `x` are performed, thus the scalars are never reduced
+    /// modulo `r` and a hook returning the identity can't make any point pass.
+    ///
+    /// See https://eprint.iacr.org/2022/352.pdf
+    #[inline(always)]
+    fn is_in_correct_subgroup_assuming_on_curve(item: &G1Affine<H>) -> bool {
+        let x = crate::Config::<H>::X;
+        let p = G1Projective::<H>::from(*item);
+        let x_p = p.mul_bigint(x);
+        let x2_p = x_p.mul_bigint(x);
+        let x3_p = x2_p.mul_bigint(x);
+        (x3_p - x2_p + p + endomorphism(&(x_p + p))).is_zero()
+    }
+}
+
+impl<H: CurveHooks> Config<H> {
+    /// Multi scalar multiplication jumping into the user-defined `msm_g1` hook.
+    ///
+    /// Fails if the lengths differ or exceed the hooks limits, or if the hook
+    /// output is not on the curve.
+    pub fn checked_msm(
+        bases: &[G1Affine<H>],
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<G1Projective<H>, HookError> {
+        checked::msm(
+            bases,
+            scalars,
+            &H::LIMITS,
+            H::msm_g1,
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Projective multiplication jumping into the user-defined `mul_projective_g1` hook.
+    ///
+    /// Fails if the scalar exceeds the hooks limits or if the hook output is not
+    /// on the curve.
+    pub fn checked_mul(
+        base: &G1Projective<H>,
+        scalar: &[u64],
+    ) -> Result<G1Projective<H>, HookError> {
+        checked::mul(
+            scalar,
+            &H::LIMITS,
+            |scalar| H::mul_projective_g1(base, scalar),
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Batch normalization jumping into the user-defined `normalize_batch_g1` hook.
+    ///
+    /// See [`checked::normalize_batch`].
+    pub fn normalize_batch(points: &[G1Projective<H>]) -> Vec<G1Affine<H>> {
+        checked::normalize_batch(
+            points,
+            H::normalize_batch_g1,
+            validation::sw_is_normalization_of,
+        )
+    }
+
+    /// Multi scalar multiplication with 128-bit scalars jumping into the
+    /// user-defined `msm_small_g1` hook.
+    ///
+    /// Same checks and errors as `checked_msm`.
+    pub fn msm_small(
+        bases: &[G1Affine<H>],
+        scalars: &[u128],
+    ) -> Result<G1Projective<H>, HookError> {
+        checked::msm(
+            bases,
+            scalars,
+            &H::LIMITS,
+            H::msm_small_g1,
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Multi scalar multiplication jumping into the user-defined `msm_small_g1` hook
+    /// if all the scalars fit 128 bits, into the `msm_g1` hook otherwise.
+    pub fn msm_auto(
+        bases: &[G1Affine<H>],
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<G1Projective<H>, HookError> {
+        checked::msm_auto(
+            scalars,
+            |scalars| Self::msm_small(bases, scalars),
+            |scalars| Self::checked_msm(bases, scalars),
+        )
+    }
+
+    /// Independent multi scalar multiplications jumping into the user-defined
+    /// `multi_msm_g1` hook, with a single call for all the jobs.
+    ///
+    /// Each job is checked as by `checked_msm`, with the limits applied to the
+    /// total number of bases. See [`batch::multi_msm`] for the errors.
+    pub fn multi_msm(
+        jobs: &[MsmJob<G1Affine<H>, <Self as CurveConfig>::ScalarField>],
+    ) -> Vec<Result<G1Projective<H>, HookError>> {
+        batch::multi_msm(
+            jobs,
+            &H::LIMITS,
+            H::multi_msm_g1,
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Multiplication of one base by many scalars jumping into the user-defined
+    /// `batch_mul_g1` hook, with a single call for all the scalars.
+    ///
+    /// The products are returned in affine form. See [`batch::batch_mul`] for the
+    /// errors.
+    pub fn batch_mul(
+        base: &G1Projective<H>,
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<Vec<G1Affine<H>>, HookError> {
+        batch::batch_mul(
+            scalars,
+            &H::LIMITS,
+            |scalars| H::batch_mul_g1(base, scalars),
+            |p| p.is_on_curve(),
+        )
+    }
+}
+
+/// Endomorphism of the points on the curve.
+///
+/// endomorphism_p(x,y) = (OMEGA * x, y)
+/// where OMEGA is a non-trivial cubic root of unity in fq.
+pub fn endomorphism<H: CurveHooks>(p: &G1Projective<H>) -> G1Projective<H> {
+    let mut res = *p;
+    res.x *= <ArkConfig as GLVConfig>::ENDO_COEFFS[0];
+    res
 }
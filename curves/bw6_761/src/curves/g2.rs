@@ -1,11 +1,17 @@
 use crate::CurveHooks;
 
 use ark_bw6_761::g2::Config as ArkConfig;
+use ark_ec::scalar_mul::glv::GLVConfig;
 use ark_models_ext::{
+    batch,
+    batch::MsmJob,
     bw6,
-    {short_weierstrass::SWCurveConfig, CurveConfig},
+    bw6::BW6Config,
+    checked::{self, HookError},
+    validation,
+    {short_weierstrass::SWCurveConfig, CurveConfig, PrimeGroup},
 };
-use ark_std::marker::PhantomData;
+use ark_std::{marker::PhantomData, vec::Vec, Zero};
 
 pub use ark_bw6_761::g2::{G2_GENERATOR_X, G2_GENERATOR_Y};
 
@@ -30,18 +36,20 @@ impl<H: CurveHooks> SWCurveConfig for Config<H> {
     const GENERATOR: G2Affine<H> = G2Affine::<H>::new_unchecked(G2_GENERATOR_X, G2_GENERATOR_Y);
 
     /// Multi scalar multiplication jumping into the user-defined `msm_g2` hook.
+    ///
+    /// Fails on any `checked_msm` failure, see [`checked::upstream_msm`].
     #[inline(always)]
     fn msm(bases: &[G2Affine<H>], scalars: &[Self::ScalarField]) -> Result<G2Projective<H>, usize> {
-        if bases.len() != scalars.len() {
-            return Err(bases.len().min(scalars.len()));
-        }
-        Ok(H::msm_g2(bases, scalars))
+        checked::upstream_msm(bases.len(), Self::checked_msm(bases, scalars))
     }
 
     /// Projective multiplication jumping into the user-defined `mul_projective_g2` hook.
+    ///
+    /// The identity if the scalar exceeds the hooks limits, see
+    /// [`checked::upstream_mul`].
     #[inline(always)]
     fn mul_projective(base: &G2Projective<H>, scalar: &[u64]) -> G2Projective<H> {
-        H::mul_projective_g2(base, scalar)
+        checked::upstream_mul(base, scalar, Self::checked_mul(base, scalar))
     }
 
     /// Affine multiplication jumping into the user-defined `mul_projective_g2` hook.
@@ -54,4 +62,146 @@ impl<H: CurveHooks> SWCurveConfig for Config<H> {
     fn mul_by_a(elem: Self::BaseField) -> Self::BaseField {
         <ArkConfig as SWCurveConfig>::mul_by_a(elem)
     }
+
+    /// Subgroup check via the endomorphism, i.e. `endomorphism([c + x + 1]P) = [c]P`
+    /// with `c = (x^3 - x^2 - 2x - 1) / 3`.
+    ///
+    /// The coefficients `(-c, c + x + 1)` are a vector of the lattice of the `(a, b)`
+    /// such that `a + b * lambda = 0 mod r`, with `lambda` the endomorphism eigenvalue,
+    /// of norm `a^2 - ab + b^2 = r`. The kernel of `a + b * endomorphism` is then the
+    /// prime order subgroup alone. The shorter `(x + 1, x^3 - x^2 + 1)` has norm `3r`,
+    /// and the G2 cofactor being a multiple of 3 it would accept the 3-torsion.
+    /// The scalars are never reduced modulo `r`, thus a hook returning the identity
+    /// can't make any point pass.
+    ///
+    /// See https://eprint.iacr.org/2022/352.pdf
+    #[inline(always)]
+    fn is_in_correct_subgroup_assuming_on_curve(item: &G2Affine<H>) -> bool {
+        let x = crate::Config::<H>::X;
+        let p = G2Projective::<H>::from(*item);
+        let c_p = p.mul_bigint(SUBGROUP_CHECK_C);
+        (endomorphism(&(c_p + p.mul_bigint(x) + p)) - c_p).is_zero()
+    }
+}
+
+/// `(x^3 - x^2 - 2x - 1) / 3`, see `is_in_correct_subgroup_assuming_on_curve`.
+const SUBGROUP_CHECK_C: [u64; 3] = [0xd3a7bfffffffffff, 0x2e16ba885fffffff, 0x0bf9b117dd04a400];
+
+impl<H: CurveHooks> Config<H> {
+    /// Multi scalar multiplication jumping into the user-defined `msm_g2` hook.
+    ///
+    /// Fails if the lengths differ or exceed the hooks limits, or if the hook
+    /// output is not on the curve.
+    pub fn checked_msm(
+        bases: &[G2Affine<H>],
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<G2Projective<H>, HookError> {
+        checked::msm(
+            bases,
+            scalars,
+            &H::LIMITS,
+            H::msm_g2,
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Projective multiplication jumping into the user-defined `mul_projective_g2` hook.
+    ///
+    /// Fails if the scalar exceeds the hooks limits or if the hook output is not
+    /// on the curve.
+    pub fn checked_mul(
+        base: &G2Projective<H>,
+        scalar: &[u64],
+    ) -> Result<G2Projective<H>, HookError> {
+        checked::mul(
+            scalar,
+            &H::LIMITS,
+            |scalar| H::mul_projective_g2(base, scalar),
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Batch normalization jumping into the user-defined `normalize_batch_g2` hook.
+    ///
+    /// See [`checked::normalize_batch`].
+    pub fn normalize_batch(points: &[G2Projective<H>]) -> Vec<G2Affine<H>> {
+        checked::normalize_batch(
+            points,
+            H::normalize_batch_g2,
+            validation::sw_is_normalization_of,
+        )
+    }
+
+    /// Multi scalar multiplication with 128-bit scalars jumping into the
+    /// user-defined `msm_small_g2` hook.
+    ///
+    /// Same checks and errors as `checked_msm`.
+    pub fn msm_small(
+        bases: &[G2Affine<H>],
+        scalars: &[u128],
+    ) -> Result<G2Projective<H>, HookError> {
+        checked::msm(
+            bases,
+            scalars,
+            &H::LIMITS,
+            H::msm_small_g2,
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Multi scalar multiplication jumping into the user-defined `msm_small_g2` hook
+    /// if all the scalars fit 128 bits, into the `msm_g2` hook otherwise.
+    pub fn msm_auto(
+        bases: &[G2Affine<H>],
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<G2Projective<H>, HookError> {
+        checked::msm_auto(
+            scalars,
+            |scalars| Self::msm_small(bases, scalars),
+            |scalars| Self::checked_msm(bases, scalars),
+        )
+    }
+
+    /// Independent multi scalar multiplications jumping into the user-defined
+    /// `multi_msm_g2` hook, with a single call for all the jobs.
+    ///
+    /// Each job is checked as by `checked_msm`, with the limits applied to the
+    /// total number of bases. See [`batch::multi_msm`] for the errors.
+    pub fn multi_msm(
+        jobs: &[MsmJob<G2Affine<H>, <Self as CurveConfig>::ScalarField>],
+    ) -> Vec<Result<G2Projective<H>, HookError>> {
+        batch::multi_msm(
+            jobs,
+            &H::LIMITS,
+            H::multi_msm_g2,
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Multiplication of one base by many scalars jumping into the user-defined
+    /// `batch_mul_g2` hook, with a single call for all the scalars.
+    ///
+    /// The products are returned in affine form. See [`batch::batch_mul`] for the
+    /// errors.
+    pub fn batch_mul(
+        base: &G2Projective<H>,
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<Vec<G2Affine<H>>, HookError> {
+        batch::batch_mul(
+            scalars,
+            &H::LIMITS,
+            |scalars| H::batch_mul_g2(base, scalars),
+            |p| p.is_on_curve(),
+        )
+    }
+}
+
+/// Endomorphism of the points on the curve.
+///
+/// endomorphism_p(x,y) = (OMEGA * x, y)
+/// where OMEGA is a non-trivial cubic root of unity in fq.
+pub fn endomorphism<H: CurveHooks>(p: &G2Projective<H>) -> G2Projective<H> {
+    let mut res = *p;
+    res.x *= <ArkConfig as GLVConfig>::ENDO_COEFFS[0];
+    res
 }
@@ -0,0 +1,83 @@
+//! EIP-3026 precompiles checked against regression snapshots.
+//!
+//! These are not conformance vectors: the official EIP-3026 vectors are not
+//! vendored. The snapshots follow the layout of the EIP-2537 reference vectors.
+//! Their expected outputs were computed with the upstream *Arkworks* BW6-761
+//! implementation, and their failures are the ones of the EIP-3026 specification,
+//! so they only catch regressions against upstream.
+
+use super::TestHooks;
+use crate::eip3026;
+use ark_std::vec::Vec;
+use test_utils::{
+    no_panic,
+    vectors::{check, error, parse},
+};
+
+macro_rules! vectors {
+    ($file:literal) => {
+        include_str!(concat!("eip3026_snapshots/", $file))
+    };
+}
+
+#[test]
+fn g1_add() {
+    let op = eip3026::g1_add::<TestHooks>;
+    check(vectors!("add_G1.json"), error, op);
+    check(vectors!("fail-add_G1.json"), error, op);
+}
+
+#[test]
+fn g2_add() {
+    let op = eip3026::g2_add::<TestHooks>;
+    check(vectors!("add_G2.json"), error, op);
+    check(vectors!("fail-add_G2.json"), error, op);
+}
+
+#[test]
+fn g1_msm() {
+    let op = eip3026::g1_msm::<TestHooks>;
+    check(vectors!("multiexp_G1.json"), error, op);
+    check(vectors!("fail-multiexp_G1.json"), error, op);
+}
+
+#[test]
+fn g2_msm() {
+    let op = eip3026::g2_msm::<TestHooks>;
+    check(vectors!("multiexp_G2.json"), error, op);
+    check(vectors!("fail-multiexp_G2.json"), error, op);
+}
+
+#[test]
+fn pairing() {
+    let op = eip3026::pairing::<TestHooks>;
+    check(vectors!("pairing.json"), error, op);
+    check(vectors!("fail-pairing.json"), error, op);
+}
+
+fn inputs(json: &str) -> Vec<Vec<u8>> {
+    let vectors = parse(json, error).into_iter();
+    vectors.map(|v| v.input).filter(|i| !i.is_empty()).collect()
+}
+
+#[test]
+fn no_panic() {
+    let g1_add = [vectors!("add_G1.json"), vectors!("fail-add_G1.json")].map(inputs);
+    no_panic::parse("g1_add", &g1_add.concat(), eip3026::g1_add::<TestHooks>);
+    let g2_add = [vectors!("add_G2.json"), vectors!("fail-add_G2.json")].map(inputs);
+    no_panic::parse("g2_add", &g2_add.concat(), eip3026::g2_add::<TestHooks>);
+    let g1_msm = [
+        vectors!("multiexp_G1.json"),
+        vectors!("fail-multiexp_G1.json"),
+    ]
+    .map(inputs);
+    no_panic::parse("g1_msm", &g1_msm.concat(), eip3026::g1_msm::<TestHooks>);
+    let g2_msm = [
+        vectors!("multiexp_G2.json"),
+        vectors!("fail-multiexp_G2.json"),
+    ]
+    .map(inputs);
+    no_panic::parse("g2_msm", &g2_msm.concat(), eip3026::g2_msm::<TestHooks>);
+    let pairing = [vectors!("pairing.json"), vectors!("fail-pairing.json")].map(inputs);
+    no_panic::parse("pairing", &pairing.concat(), eip3026::pairing::<TestHooks>);
+}
@@ -0,0 +1,534 @@
+use crate::CurveHooks;
+
+use ark_algebra_test_templates::*;
+use ark_bw6_761::{g1::Config as ArkG1Config, g2::Config as ArkG2Config, BW6_761 as ArkBW6_761};
+use ark_ec::scalar_mul::glv::GLVConfig;
+use ark_ff::{PrimeField, Zero};
+use ark_models_ext::{
+    pairing::{Pairing, PairingOutput},
+    short_weierstrass::{Affine, SWCurveConfig},
+    AffineRepr, CurveConfig, CurveGroup,
+};
+use ark_std::{rand::Rng, test_rng, vec::Vec, UniformRand};
+use test_utils::mul_plain;
+
+mod eip3026_snapshots;
+
+struct TestHooks;
+
+type BW6_761 = crate::BW6_761<TestHooks>;
+type G1Projective = crate::G1Projective<TestHooks>;
+type G2Projective = crate::G2Projective<TestHooks>;
+type G1Affine = crate::G1Affine<TestHooks>;
+type G2Affine = crate::G2Affine<TestHooks>;
+type G1Config = crate::g1::Config<TestHooks>;
+type G2Config = crate::g2::Config<TestHooks>;
+
+impl CurveHooks for TestHooks {
+    fn multi_miller_loop(
+        g1: &[<BW6_761 as Pairing>::G1Prepared],
+        g2: &[<BW6_761 as Pairing>::G2Prepared],
+    ) -> <BW6_761 as Pairing>::TargetField {
+        test_utils::multi_miller_loop_generic::<BW6_761, ArkBW6_761>(g1, g2)
+    }
+
+    fn final_exponentiation(
+        target: <BW6_761 as Pairing>::TargetField,
+    ) -> <BW6_761 as Pairing>::TargetField {
+        test_utils::final_exponentiation_generic::<BW6_761, ArkBW6_761>(target)
+    }
+
+    fn msm_g1(
+        bases: &[G1Affine],
+        scalars: &[<G1Config as CurveConfig>::ScalarField],
+    ) -> G1Projective {
+        test_utils::msm_sw_generic::<G1Config, ArkG1Config>(bases, scalars)
+    }
+
+    fn msm_g2(
+        bases: &[G2Affine],
+        scalars: &[<G2Config as CurveConfig>::ScalarField],
+    ) -> G2Projective {
+        test_utils::msm_sw_generic::<G2Config, ArkG2Config>(bases, scalars)
+    }
+
+    fn mul_projective_g1(base: &G1Projective, scalar: &[u64]) -> G1Projective {
+        test_utils::mul_projective_sw_generic::<G1Config, ArkG1Config>(base, scalar)
+    }
+
+    fn mul_projective_g2(base: &G2Projective, scalar: &[u64]) -> G2Projective {
+        test_utils::mul_projective_sw_generic::<G2Config, ArkG2Config>(base, scalar)
+    }
+
+    fn normalize_batch_g1(points: &[G1Projective]) -> Vec<G1Affine> {
+        test_utils::normalize_batch_sw_generic::<G1Config, ArkG1Config>(points)
+    }
+
+    fn normalize_batch_g2(points: &[G2Projective]) -> Vec<G2Affine> {
+        test_utils::normalize_batch_sw_generic::<G2Config, ArkG2Config>(points)
+    }
+}
+
+test_group!(g1; G1Projective; sw);
+test_group!(g2; G2Projective; sw);
+test_group!(pairing_output; PairingOutput<BW6_761>; msm);
+test_pairing!(pairing; crate::BW6_761<super::TestHooks>);
+
+#[test]
+fn differential() {
+    use test_utils::differential as diff;
+
+    diff::group::<G1Projective, ark_bw6_761::G1Projective>(&diff::sw_samples::<ArkG1Config>());
+    diff::group::<G2Projective, ark_bw6_761::G2Projective>(&diff::sw_samples::<ArkG2Config>());
+    diff::pairing::<BW6_761, ArkBW6_761>();
+    diff::miller_loop_accumulator::<BW6_761, ArkBW6_761>();
+    diff::torus::<BW6_761, ArkBW6_761, _>();
+    diff::gt_subgroup::<BW6_761, ArkBW6_761, _>();
+    diff::raw_field::<<BW6_761 as Pairing>::TargetField, <ArkBW6_761 as Pairing>::TargetField>();
+    diff::final_exponentiation_batch::<BW6_761, ArkBW6_761>(BW6_761::final_exponentiation_batch);
+    diff::normalize_batch::<G1Projective, ark_bw6_761::G1Projective>(
+        &diff::sw_samples::<ArkG1Config>(),
+        G1Config::normalize_batch,
+    );
+    diff::normalize_batch::<G2Projective, ark_bw6_761::G2Projective>(
+        &diff::sw_samples::<ArkG2Config>(),
+        G2Config::normalize_batch,
+    );
+    diff::msm_small::<G1Projective, ark_bw6_761::G1Projective>(
+        &diff::sw_samples::<ArkG1Config>(),
+        G1Config::msm_small,
+        G1Config::msm_auto,
+    );
+    diff::multi_msm::<G1Projective, ark_bw6_761::G1Projective>(
+        &diff::sw_samples::<ArkG1Config>(),
+        G1Config::multi_msm,
+    );
+    diff::batch_mul::<G1Projective, ark_bw6_761::G1Projective>(
+        &diff::sw_samples::<ArkG1Config>(),
+        G1Config::batch_mul,
+    );
+    diff::msm_small::<G2Projective, ark_bw6_761::G2Projective>(
+        &diff::sw_samples::<ArkG2Config>(),
+        G2Config::msm_small,
+        G2Config::msm_auto,
+    );
+    diff::multi_msm::<G2Projective, ark_bw6_761::G2Projective>(
+        &diff::sw_samples::<ArkG2Config>(),
+        G2Config::multi_msm,
+    );
+    diff::batch_mul::<G2Projective, ark_bw6_761::G2Projective>(
+        &diff::sw_samples::<ArkG2Config>(),
+        G2Config::batch_mul,
+    );
+}
+
+#[test]
+fn hooks_conformance() {
+    use ark_models_ext::metering::{HookOp, LocalMeter, MeteredHooks};
+
+    type Hooks = MeteredHooks<TestHooks, LocalMeter>;
+
+    test_utils::conformance::miller_loop_accumulator::<crate::BW6_761<Hooks>>();
+    test_utils::conformance::final_exponentiation_batch(
+        crate::BW6_761::<Hooks>::final_exponentiation_batch,
+    );
+    test_utils::conformance::gt_is_in_subgroup::<crate::BW6_761<Hooks>>();
+    test_utils::conformance::sw::<crate::g1::Config<Hooks>>(HookOp::MsmG1, HookOp::MulProjectiveG1);
+    test_utils::conformance::sw::<crate::g2::Config<Hooks>>(HookOp::MsmG2, HookOp::MulProjectiveG2);
+    test_utils::conformance::normalize_batch(
+        HookOp::NormalizeBatchG1,
+        crate::g1::Config::<Hooks>::normalize_batch,
+    );
+    test_utils::conformance::normalize_batch(
+        HookOp::NormalizeBatchG2,
+        crate::g2::Config::<Hooks>::normalize_batch,
+    );
+    test_utils::conformance::msm_small(
+        HookOp::MsmSmallG1,
+        HookOp::MsmG1,
+        crate::g1::Config::<Hooks>::msm_small,
+        crate::g1::Config::<Hooks>::msm_auto,
+    );
+    test_utils::conformance::multi_msm(HookOp::MultiMsmG1, crate::g1::Config::<Hooks>::multi_msm);
+    test_utils::conformance::batch_mul(HookOp::BatchMulG1, crate::g1::Config::<Hooks>::batch_mul);
+    test_utils::conformance::msm_small(
+        HookOp::MsmSmallG2,
+        HookOp::MsmG2,
+        crate::g2::Config::<Hooks>::msm_small,
+        crate::g2::Config::<Hooks>::msm_auto,
+    );
+    test_utils::conformance::multi_msm(HookOp::MultiMsmG2, crate::g2::Config::<Hooks>::multi_msm);
+    test_utils::conformance::batch_mul(HookOp::BatchMulG2, crate::g2::Config::<Hooks>::batch_mul);
+}
+
+#[test]
+fn hook_free_decoding() {
+    use ark_models_ext::metering::{LocalMeter, MeteredHooks};
+    use test_utils::differential as diff;
+
+    type Hooks = MeteredHooks<TestHooks, LocalMeter>;
+
+    test_utils::conformance::decoding::<crate::G1Projective<Hooks>, ark_bw6_761::G1Projective>(
+        &diff::sw_samples::<ArkG1Config>(),
+    );
+    test_utils::conformance::decoding::<crate::G2Projective<Hooks>, ark_bw6_761::G2Projective>(
+        &diff::sw_samples::<ArkG2Config>(),
+    );
+}
+
+#[test]
+fn no_panic_decoding() {
+    use ark_models_ext::{pairing::PairingOutput, torus::TorusOutput, AffineRepr};
+
+    let p = G1Affine::generator();
+    test_utils::no_panic::deserialize(&[p, G1Affine::zero()]);
+    test_utils::no_panic::raw(&[p, G1Affine::zero()]);
+    test_utils::no_panic::deserialize(&[p.into_group(), G1Projective::default()]);
+    test_utils::no_panic::raw(&[p.into_group(), G1Projective::default()]);
+    let q = G2Affine::generator();
+    test_utils::no_panic::deserialize(&[q, G2Affine::zero()]);
+    test_utils::no_panic::raw(&[q, G2Affine::zero()]);
+    test_utils::no_panic::deserialize(&[q.into_group(), G2Projective::default()]);
+    test_utils::no_panic::raw(&[q.into_group(), G2Projective::default()]);
+    let out = BW6_761::pairing(p, q);
+    test_utils::no_panic::deserialize(&[out, PairingOutput::default()]);
+    test_utils::no_panic::raw(&[out.0, Default::default()]);
+    test_utils::no_panic::deserialize(&[TorusOutput(out), TorusOutput(PairingOutput::default())]);
+}
+
+fn in_subgroup_naive<P: SWCurveConfig>(p: &Affine<P>) -> bool {
+    mul_plain(p.into_group(), P::ScalarField::MODULUS).is_zero()
+}
+
+/// Random curve points followed by their components outside the prime order subgroup.
+fn non_subgroup_samples<P: SWCurveConfig>() -> impl Iterator<Item = Affine<P>> {
+    let mut rng = test_rng();
+    let mut samples = Vec::new();
+    while samples.len() < 10 {
+        let x = P::BaseField::rand(&mut rng);
+        samples.extend(Affine::<P>::get_point_from_x_unchecked(x, rng.gen()));
+    }
+    let low_order: Vec<_> = samples
+        .iter()
+        .map(|p| mul_plain(p.into_group(), P::ScalarField::MODULUS).into_affine())
+        .collect();
+    samples.into_iter().chain(low_order)
+}
+
+#[test]
+fn eip3026() {
+    use crate::eip3026::{self, Error};
+    use ark_ff::BigInteger;
+    type Fr = <G1Config as CurveConfig>::ScalarField;
+
+    let mut rng = test_rng();
+    let scalar = |s: &Fr| {
+        let be = s.into_bigint().to_bytes_be();
+        let mut res = [0; eip3026::SCALAR_LEN];
+        res[eip3026::SCALAR_LEN - be.len()..].copy_from_slice(&be);
+        res
+    };
+    let p: Vec<G1Affine> = (0..3).map(|_| G1Affine::rand(&mut rng)).collect();
+    let q: Vec<G2Affine> = (0..3).map(|_| G2Affine::rand(&mut rng)).collect();
+    let s: Vec<Fr> = (0..3).map(|_| Fr::rand(&mut rng)).collect();
+    let g1 = |p: &G1Affine| eip3026::encode_g1(p);
+    let g2 = |q: &G2Affine| eip3026::encode_g2(q);
+
+    // Addition, including the identity.
+    let zero = G1Affine::zero();
+    let input = [g1(&p[0]), g1(&p[1])].concat();
+    let expected = g1(&(p[0] + p[1]).into_affine());
+    assert_eq!(eip3026::g1_add::<TestHooks>(&input), Ok(expected));
+    let input = [g1(&p[0]), g1(&zero)].concat();
+    assert_eq!(eip3026::g1_add::<TestHooks>(&input), Ok(g1(&p[0])));
+    let input = [g2(&q[0]), g2(&q[1])].concat();
+    let expected = g2(&(q[0] + q[1]).into_affine());
+    assert_eq!(eip3026::g2_add::<TestHooks>(&input), Ok(expected));
+
+    // MSM, with a scalar not less than the group order.
+    let mut input: Vec<u8> = (0..3)
+        .flat_map(|i| [&g1(&p[i])[..], &scalar(&s[i])].concat())
+        .collect();
+    input.extend(g1(&p[0]));
+    input.extend([0xff; eip3026::SCALAR_LEN]);
+    let big = Fr::from_be_bytes_mod_order(&[0xff; eip3026::SCALAR_LEN]);
+    let expected = (0..3).fold(mul_plain(p[0].into_group(), big.into_bigint()), |acc, i| {
+        acc + mul_plain(p[i].into_group(), s[i].into_bigint())
+    });
+    assert_eq!(
+        eip3026::g1_msm::<TestHooks>(&input),
+        Ok(g1(&expected.into_affine()))
+    );
+    let input: Vec<u8> = (0..3)
+        .flat_map(|i| [&g2(&q[i])[..], &scalar(&s[i])].concat())
+        .collect();
+    let expected = (0..3).fold(G2Projective::zero(), |acc, i| {
+        acc + mul_plain(q[i].into_group(), s[i].into_bigint())
+    });
+    assert_eq!(
+        eip3026::g2_msm::<TestHooks>(&input),
+        Ok(g2(&expected.into_affine()))
+    );
+
+    // Pairing check.
+    let mut success = [0; eip3026::PAIRING_LEN];
+    success[eip3026::PAIRING_LEN - 1] = 1;
+    let input = [&g1(&p[0])[..], &g2(&q[0]), &g1(&-p[0]), &g2(&q[0])].concat();
+    assert_eq!(eip3026::pairing::<TestHooks>(&input), Ok(success));
+    let input = [
+        &g1(&p[0])[..],
+        &g2(&q[0]),
+        &g1(&p[1]),
+        &g2(&G2Affine::zero()),
+    ]
+    .concat();
+    assert_eq!(
+        eip3026::pairing::<TestHooks>(&input),
+        Ok([0; eip3026::PAIRING_LEN])
+    );
+
+    // Failures.
+    assert_eq!(eip3026::g1_add::<TestHooks>(&[]), Err(Error::InputLength));
+    assert_eq!(eip3026::g1_msm::<TestHooks>(&[]), Err(Error::InputLength));
+    assert_eq!(eip3026::pairing::<TestHooks>(&[]), Err(Error::InputLength));
+    assert_eq!(
+        eip3026::g1_add::<TestHooks>(&[0; 2 * eip3026::G1_LEN + 1]),
+        Err(Error::InputLength)
+    );
+    let modulus = <crate::Fq as PrimeField>::MODULUS.to_bytes_be();
+    let mut input = [0; 2 * eip3026::G1_LEN];
+    input[eip3026::FP_LEN - modulus.len()..eip3026::FP_LEN].copy_from_slice(&modulus);
+    assert_eq!(
+        eip3026::g1_add::<TestHooks>(&input),
+        Err(Error::FieldEncoding)
+    );
+    let mut input = [0; 2 * eip3026::G1_LEN];
+    input[eip3026::FP_LEN - 1] = 1;
+    input[eip3026::G1_LEN - 1] = 1;
+    assert_eq!(eip3026::g1_add::<TestHooks>(&input), Err(Error::NotOnCurve));
+
+    // Points outside of the subgroup are only accepted by the addition.
+    let outside = (1u64..)
+        .filter_map(|x| Affine::<G1Config>::get_point_from_x_unchecked(x.into(), false))
+        .find(|p| !p.is_in_correct_subgroup_assuming_on_curve())
+        .unwrap();
+    let input = [g1(&outside), g1(&zero)].concat();
+    assert_eq!(eip3026::g1_add::<TestHooks>(&input), Ok(g1(&outside)));
+    let input = [&g1(&outside)[..], &scalar(&s[0])].concat();
+    assert_eq!(
+        eip3026::g1_msm::<TestHooks>(&input),
+        Err(Error::NotInSubgroup)
+    );
+    let input = [&g1(&outside)[..], &g2(&q[0])].concat();
+    assert_eq!(
+        eip3026::pairing::<TestHooks>(&input),
+        Err(Error::NotInSubgroup)
+    );
+}
+
+#[test]
+fn test_g1_endomorphism_eigenvalue() {
+    let p = G1Projective::rand(&mut test_rng());
+    let lambda = <ArkG1Config as GLVConfig>::LAMBDA.into_bigint();
+    assert_eq!(crate::g1::endomorphism(&p), mul_plain(p, lambda));
+}
+
+#[test]
+fn test_g2_endomorphism_eigenvalue() {
+    let p = G2Projective::rand(&mut test_rng());
+    let lambda = <ArkG2Config as GLVConfig>::LAMBDA.into_bigint();
+    assert_eq!(crate::g2::endomorphism(&p), mul_plain(p, lambda));
+}
+
+#[test]
+fn test_g1_subgroup_membership_via_endomorphism() {
+    let mut rng = test_rng();
+    let p = G1Projective::rand(&mut rng).into_affine();
+    assert!(p.is_in_correct_subgroup_assuming_on_curve());
+    assert!(G1Affine::zero().is_in_correct_subgroup_assuming_on_curve());
+}
+
+#[test]
+fn test_g1_subgroup_non_membership_via_endomorphism() {
+    for p in non_subgroup_samples::<G1Config>() {
+        assert!(!in_subgroup_naive(&p));
+        assert!(!p.is_in_correct_subgroup_assuming_on_curve());
+    }
+}
+
+#[test]
+fn test_g2_subgroup_membership_via_endomorphism() {
+    let mut rng = test_rng();
+    let p = G2Projective::rand(&mut rng).into_affine();
+    assert!(p.is_in_correct_subgroup_assuming_on_curve());
+    assert!(G2Affine::zero().is_in_correct_subgroup_assuming_on_curve());
+}
+
+#[test]
+fn test_g2_subgroup_non_membership_via_endomorphism() {
+    // The 3-torsion point `(0, 2)`, alone and added to a subgroup point.
+    let t = G2Affine::new_unchecked(Zero::zero(), 2u64.into());
+    assert!(t.is_on_curve());
+    let g = G2Affine::generator();
+    let torsion = [t, (g + t).into_affine()];
+    for p in non_subgroup_samples::<G2Config>().chain(torsion) {
+        assert!(!in_subgroup_naive(&p));
+        assert!(!p.is_in_correct_subgroup_assuming_on_curve());
+    }
+}
+
+mod faults {
+    use super::TestHooks;
+    use crate::CurveHooks;
+
+    use ark_ff::{PrimeField, Zero};
+    use ark_models_ext::{
+        bw6::{G1Prepared, G2Prepared},
+        cast,
+        metering::HookOp,
+        pairing::Pairing,
+        short_weierstrass::{Affine, SWCurveConfig},
+        AffineRepr, CurveConfig, CurveGroup,
+    };
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use ark_std::{test_rng, vec::Vec, UniformRand};
+    use test_utils::faults::{self, Fault, FaultyHooks};
+
+    type BW6_761 = crate::BW6_761<FaultyHooks<TestHooks>>;
+    type G1Affine = crate::G1Affine<FaultyHooks<TestHooks>>;
+    type G2Affine = crate::G2Affine<FaultyHooks<TestHooks>>;
+
+    const FAULTS: [Fault; 5] = [
+        Fault::Identity,
+        Fault::Random,
+        Fault::OffCurve,
+        Fault::NonSubgroup,
+        Fault::Fail,
+    ];
+
+    impl<H: CurveHooks> CurveHooks for FaultyHooks<H> {
+        fn multi_miller_loop(
+            g1: &[<crate::BW6_761<Self> as Pairing>::G1Prepared],
+            g2: &[<crate::BW6_761<Self> as Pairing>::G2Prepared],
+        ) -> <crate::BW6_761<Self> as Pairing>::TargetField {
+            let g1: Vec<_> = g1
+                .iter()
+                .map(|p| G1Prepared(cast::sw_affine(&p.0)))
+                .collect();
+            let g2: Vec<_> = g2
+                .iter()
+                .map(|p| G2Prepared(cast::sw_affine(&p.0)))
+                .collect();
+            let res = H::multi_miller_loop(&g1, &g2);
+            faults::faulty_target(HookOp::MultiMillerLoop, res)
+        }
+
+        fn final_exponentiation(
+            target: <crate::BW6_761<Self> as Pairing>::TargetField,
+        ) -> <crate::BW6_761<Self> as Pairing>::TargetField {
+            let res = H::final_exponentiation(target);
+            faults::faulty_target(HookOp::FinalExponentiation, res)
+        }
+
+        fn msm_g1(
+            bases: &[crate::G1Affine<Self>],
+            scalars: &[<crate::g1::Config<Self> as CurveConfig>::ScalarField],
+        ) -> crate::G1Projective<Self> {
+            let res = H::msm_g1(&cast::sw_affine_slice(bases), scalars);
+            faults::faulty_sw(HookOp::MsmG1, cast::sw_projective(&res))
+        }
+
+        fn msm_g2(
+            bases: &[crate::G2Affine<Self>],
+            scalars: &[<crate::g2::Config<Self> as CurveConfig>::ScalarField],
+        ) -> crate::G2Projective<Self> {
+            let res = H::msm_g2(&cast::sw_affine_slice(bases), scalars);
+            faults::faulty_sw(HookOp::MsmG2, cast::sw_projective(&res))
+        }
+
+        fn mul_projective_g1(
+            base: &crate::G1Projective<Self>,
+            scalar: &[u64],
+        ) -> crate::G1Projective<Self> {
+            let res = H::mul_projective_g1(&cast::sw_projective(base), scalar);
+            faults::faulty_sw(HookOp::MulProjectiveG1, cast::sw_projective(&res))
+        }
+
+        fn mul_projective_g2(
+            base: &crate::G2Projective<Self>,
+            scalar: &[u64],
+        ) -> crate::G2Projective<Self> {
+            let res = H::mul_projective_g2(&cast::sw_projective(base), scalar);
+            faults::faulty_sw(HookOp::MulProjectiveG2, cast::sw_projective(&res))
+        }
+    }
+
+    /// Checks `e(a G1, G2) * e(-G1, b G2) == 1`, which holds iff `a == b`.
+    fn verify(a: u64, b: u64) -> Option<bool> {
+        let g1 = G1Affine::generator();
+        let g2 = G2Affine::generator();
+        let a_g1 = (g1 * crate::Fr::from(a)).into_affine();
+        let b_g2 = (g2 * crate::Fr::from(b)).into_affine();
+        let f = BW6_761::multi_miller_loop([a_g1, -g1], [g2, b_g2]);
+        BW6_761::final_exponentiation(f).map(|out| out.is_zero())
+    }
+
+    fn non_subgroup_point<P: SWCurveConfig>() -> Affine<P> {
+        let mut rng = test_rng();
+        loop {
+            let x = P::BaseField::rand(&mut rng);
+            if let Some(p) = Affine::<P>::get_point_from_x_unchecked(x, false) {
+                if !p.mul_bigint(P::ScalarField::MODULUS).is_zero() {
+                    return p;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn faulty_pairing_never_accepts_forged_equation() {
+        faults::clear();
+        assert_eq!(verify(3, 3), Some(true));
+        assert_eq!(verify(3, 5), Some(false));
+
+        for op in [HookOp::MultiMillerLoop, HookOp::FinalExponentiation] {
+            for fault in FAULTS {
+                // A final exponentiation returning one is indistinguishable from a
+                // legit output, the hooks are trusted not to do so.
+                if op == HookOp::FinalExponentiation && fault == Fault::Identity {
+                    continue;
+                }
+                faults::inject(op, fault, 0);
+                let res = verify(3, 5);
+                faults::clear();
+                assert_ne!(res, Some(true), "{op:?} {fault:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn faulty_mul_never_validates_non_subgroup_points() {
+        faults::clear();
+        let p1: G1Affine = non_subgroup_point();
+        let p2: G2Affine = non_subgroup_point();
+        let mut buf1 = Vec::new();
+        p1.serialize_compressed(&mut buf1).unwrap();
+        let mut buf2 = Vec::new();
+        p2.serialize_compressed(&mut buf2).unwrap();
+
+        let check1 = || p1.is_in_correct_subgroup_assuming_on_curve();
+        let decode1 = || G1Affine::deserialize_compressed(&buf1[..]).is_ok();
+        let check2 = || p2.is_in_correct_subgroup_assuming_on_curve();
+        let decode2 = || G2Affine::deserialize_compressed(&buf2[..]).is_ok();
+
+        for fault in FAULTS {
+            for nth in [0, 1, 2] {
+                faults::inject(HookOp::MulProjectiveG1, fault, nth);
+                assert!(!check1() && !decode1());
+                faults::inject(HookOp::MulProjectiveG2, fault, nth);
+                assert!(!check2() && !decode2());
+            }
+        }
+        faults::clear();
+    }
+}
@@ -1,13 +1,22 @@
 use ark_ed_on_bls12_377::{EdwardsConfig as ArkConfig, GENERATOR_X, GENERATOR_Y};
 use ark_models_ext::{
+    batch,
+    batch::MsmJob,
+    cast,
+    checked::{self, HookError},
+    limits::HookLimits,
+    metering::{HookOp, HookUsage, Meter, MeteredHooks},
+    scalars,
     twisted_edwards::{self, MontCurveConfig, TECurveConfig},
-    CurveConfig,
+    validation, CurveConfig, CurveGroup,
 };
-use ark_std::marker::PhantomData;
+use ark_std::{marker::PhantomData, vec::Vec};
 
 #[cfg(test)]
 mod tests;
 
+pub use ark_models_ext::decoding::{check_subgroup_batch, deserialize_on_curve_only};
+
 pub type EdwardsAffine<H> = twisted_edwards::Affine<EdwardsConfig<H>>;
 pub type EdwardsProjective<H> = twisted_edwards::Projective<EdwardsConfig<H>>;
 
@@ -16,6 +25,13 @@ pub struct EdwardsConfig<H: CurveHooks>(PhantomData<fn() -> H>);
 
 /// Hooks for *Ed-on-BLS12-377*.
 pub trait CurveHooks: 'static + Sized {
+    /// Limits enforced on the inputs before dispatching any call.
+    ///
+    /// Oversized requests never reach the hooks. The `checked_*` entry points of the
+    /// config fail with [`HookError::Limit`], while through the upstream traits
+    /// `msm` fails and projective multiplication yields the identity.
+    const LIMITS: HookLimits = HookLimits::UNLIMITED;
+
     /// Twisted Edwards multi scalar multiplication.
     fn msm(
         bases: &[EdwardsAffine<Self>],
@@ -24,6 +40,92 @@ pub trait CurveHooks: 'static + Sized {
 
     /// Twisted Edwards projective multiplication.
     fn mul_projective(base: &EdwardsProjective<Self>, scalar: &[u64]) -> EdwardsProjective<Self>;
+
+    /// Twisted Edwards batch normalization.
+    ///
+    /// Defaults to the software batch normalization.
+    fn normalize_batch(points: &[EdwardsProjective<Self>]) -> Vec<EdwardsAffine<Self>> {
+        <EdwardsProjective<Self> as CurveGroup>::normalize_batch(points)
+    }
+
+    /// Twisted Edwards multi scalar multiplication with 128-bit scalars.
+    ///
+    /// Defaults to the `msm` hook.
+    fn msm_small(bases: &[EdwardsAffine<Self>], scalars: &[u128]) -> EdwardsProjective<Self> {
+        Self::msm(bases, &scalars::from_small(scalars))
+    }
+
+    /// Twisted Edwards independent multi scalar multiplications.
+    ///
+    /// Defaults to one `msm` hook call per job.
+    fn multi_msm(
+        jobs: &[MsmJob<EdwardsAffine<Self>, <EdwardsConfig<Self> as CurveConfig>::ScalarField>],
+    ) -> Vec<EdwardsProjective<Self>> {
+        jobs.iter()
+            .map(|(bases, scalars)| Self::msm(bases, scalars))
+            .collect()
+    }
+
+    /// Twisted Edwards multiplication of one base by many scalars.
+    ///
+    /// Defaults to one `mul_projective` hook call per scalar, followed by the
+    /// `normalize_batch` hook.
+    fn batch_mul(
+        base: &EdwardsProjective<Self>,
+        scalars: &[<EdwardsConfig<Self> as CurveConfig>::ScalarField],
+    ) -> Vec<EdwardsAffine<Self>> {
+        batch::batch_mul_each(
+            scalars,
+            |s| Self::mul_projective(base, s),
+            Self::normalize_batch,
+        )
+    }
+}
+
+impl<H: CurveHooks, M: Meter> CurveHooks for MeteredHooks<H, M> {
+    const LIMITS: HookLimits = H::LIMITS;
+
+    fn msm(
+        bases: &[EdwardsAffine<Self>],
+        scalars: &[<EdwardsConfig<Self> as CurveConfig>::ScalarField],
+    ) -> EdwardsProjective<Self> {
+        M::record(HookOp::MsmTe, HookUsage::msm(scalars));
+        let res = H::msm(&cast::te_affine_slice(bases), scalars);
+        cast::te_projective(&res)
+    }
+
+    fn mul_projective(base: &EdwardsProjective<Self>, scalar: &[u64]) -> EdwardsProjective<Self> {
+        M::record(HookOp::MulProjectiveTe, HookUsage::mul(scalar));
+        let res = H::mul_projective(&cast::te_projective(base), scalar);
+        cast::te_projective(&res)
+    }
+
+    fn normalize_batch(points: &[EdwardsProjective<Self>]) -> Vec<EdwardsAffine<Self>> {
+        M::record(HookOp::NormalizeBatchTe, HookUsage::items(points.len()));
+        cast::te_normalize_batch(points, H::normalize_batch)
+    }
+
+    fn msm_small(bases: &[EdwardsAffine<Self>], scalars: &[u128]) -> EdwardsProjective<Self> {
+        M::record(HookOp::MsmSmallTe, HookUsage::msm_small(scalars));
+        let res = H::msm_small(&cast::te_affine_slice(bases), scalars);
+        cast::te_projective(&res)
+    }
+
+    fn multi_msm(
+        jobs: &[MsmJob<EdwardsAffine<Self>, <EdwardsConfig<Self> as CurveConfig>::ScalarField>],
+    ) -> Vec<EdwardsProjective<Self>> {
+        M::record(HookOp::MultiMsmTe, HookUsage::multi_msm(jobs));
+        cast::te_multi_msm(jobs, H::multi_msm)
+    }
+
+    fn batch_mul(
+        base: &EdwardsProjective<Self>,
+        scalars: &[<EdwardsConfig<Self> as CurveConfig>::ScalarField],
+    ) -> Vec<EdwardsAffine<Self>> {
+        M::record(HookOp::BatchMulTe, HookUsage::batch_mul(scalars));
+        let res = H::batch_mul(&cast::te_projective(base), scalars);
+        cast::te_affine_slice(&res)
+    }
 }
 
 impl<H: CurveHooks> CurveConfig for EdwardsConfig<H> {
@@ -43,21 +145,23 @@ impl<H: CurveHooks> TECurveConfig for EdwardsConfig<H> {
     type MontCurveConfig = Self;
 
     /// Multi scalar multiplication jumping into the user-defined `msm` hook.
+    ///
+    /// Fails on any `checked_msm` failure, see [`checked::upstream_msm`].
     #[inline(always)]
     fn msm(
         bases: &[EdwardsAffine<H>],
         scalars: &[Self::ScalarField],
     ) -> Result<EdwardsProjective<H>, usize> {
-        if bases.len() != scalars.len() {
-            return Err(bases.len().min(scalars.len()));
-        }
-        Ok(H::msm(bases, scalars))
+        checked::upstream_msm(bases.len(), Self::checked_msm(bases, scalars))
     }
 
     /// Projective multiplication jumping into the user-defined `mul_projective` hook.
+    ///
+    /// The identity if the scalar exceeds the hooks limits, see
+    /// [`checked::upstream_mul`].
     #[inline(always)]
     fn mul_projective(base: &EdwardsProjective<H>, scalar: &[u64]) -> EdwardsProjective<H> {
-        H::mul_projective(base, scalar)
+        checked::upstream_mul(base, scalar, Self::checked_mul(base, scalar))
     }
 
     /// Affine multiplication jumping into the user-defined `mul_projective_g2` hook.
@@ -70,6 +174,12 @@ impl<H: CurveHooks> TECurveConfig for EdwardsConfig<H> {
     fn mul_by_a(elem: Self::BaseField) -> Self::BaseField {
         <ArkConfig as TECurveConfig>::mul_by_a(elem)
     }
+
+    /// Subgroup check robust to hooks returning the identity.
+    #[inline(always)]
+    fn is_in_correct_subgroup_assuming_on_curve(item: &EdwardsAffine<H>) -> bool {
+        validation::te_is_in_subgroup_via_order(item)
+    }
 }
 
 impl<H: CurveHooks> MontCurveConfig for EdwardsConfig<H> {
@@ -78,3 +188,112 @@ impl<H: CurveHooks> MontCurveConfig for EdwardsConfig<H> {
 
     type TECurveConfig = Self;
 }
+
+impl<H: CurveHooks> EdwardsConfig<H> {
+    /// Multi scalar multiplication jumping into the user-defined `msm` hook.
+    ///
+    /// Fails if the lengths differ or exceed the hooks limits, or if the hook
+    /// output is not on the curve.
+    pub fn checked_msm(
+        bases: &[EdwardsAffine<H>],
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<EdwardsProjective<H>, HookError> {
+        checked::msm(
+            bases,
+            scalars,
+            &H::LIMITS,
+            H::msm,
+            validation::te_projective_is_on_curve,
+        )
+    }
+
+    /// Projective multiplication jumping into the user-defined `mul_projective` hook.
+    ///
+    /// Fails if the scalar exceeds the hooks limits or if the hook output is not
+    /// on the curve.
+    pub fn checked_mul(
+        base: &EdwardsProjective<H>,
+        scalar: &[u64],
+    ) -> Result<EdwardsProjective<H>, HookError> {
+        checked::mul(
+            scalar,
+            &H::LIMITS,
+            |scalar| H::mul_projective(base, scalar),
+            validation::te_projective_is_on_curve,
+        )
+    }
+
+    /// Batch normalization jumping into the user-defined `normalize_batch` hook.
+    ///
+    /// See [`checked::normalize_batch`].
+    pub fn normalize_batch(points: &[EdwardsProjective<H>]) -> Vec<EdwardsAffine<H>> {
+        checked::normalize_batch(
+            points,
+            H::normalize_batch,
+            validation::te_is_normalization_of,
+        )
+    }
+
+    /// Multi scalar multiplication with 128-bit scalars jumping into the
+    /// user-defined `msm_small` hook.
+    ///
+    /// Same checks and errors as `checked_msm`.
+    pub fn msm_small(
+        bases: &[EdwardsAffine<H>],
+        scalars: &[u128],
+    ) -> Result<EdwardsProjective<H>, HookError> {
+        checked::msm(
+            bases,
+            scalars,
+            &H::LIMITS,
+            H::msm_small,
+            validation::te_projective_is_on_curve,
+        )
+    }
+
+    /// Multi scalar multiplication jumping into the user-defined `msm_small` hook
+    /// if all the scalars fit 128 bits, into the `msm` hook otherwise.
+    pub fn msm_auto(
+        bases: &[EdwardsAffine<H>],
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<EdwardsProjective<H>, HookError> {
+        checked::msm_auto(
+            scalars,
+            |scalars| Self::msm_small(bases, scalars),
+            |scalars| Self::checked_msm(bases, scalars),
+        )
+    }
+
+    /// Independent multi scalar multiplications jumping into the user-defined
+    /// `multi_msm` hook, with a single call for all the jobs.
+    ///
+    /// Each job is checked as by `checked_msm`, with the limits applied to the
+    /// total number of bases. See [`batch::multi_msm`] for the errors.
+    pub fn multi_msm(
+        jobs: &[MsmJob<EdwardsAffine<H>, <Self as CurveConfig>::ScalarField>],
+    ) -> Vec<Result<EdwardsProjective<H>, HookError>> {
+        batch::multi_msm(
+            jobs,
+            &H::LIMITS,
+            H::multi_msm,
+            validation::te_projective_is_on_curve,
+        )
+    }
+
+    /// Multiplication of one base by many scalars jumping into the user-defined
+    /// `batch_mul` hook, with a single call for all the scalars.
+    ///
+    /// The products are returned in affine form. See [`batch::batch_mul`] for the
+    /// errors.
+    pub fn batch_mul(
+        base: &EdwardsProjective<H>,
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<Vec<EdwardsAffine<H>>, HookError> {
+        batch::batch_mul(
+            scalars,
+            &H::LIMITS,
+            |scalars| H::batch_mul(base, scalars),
+            |p| p.is_on_curve(),
+        )
+    }
+}
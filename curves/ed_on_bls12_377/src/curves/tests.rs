@@ -3,6 +3,7 @@ use crate::CurveHooks;
 use ark_algebra_test_templates::*;
 use ark_ed_on_bls12_377::EdwardsConfig as ArkConfig;
 use ark_models_ext::CurveConfig;
+use ark_std::vec::Vec;
 
 struct TestHooks;
 
@@ -18,6 +19,120 @@ impl CurveHooks for TestHooks {
     fn mul_projective(base: &Projective, scalar: &[u64]) -> Projective {
         test_utils::mul_projective_te_generic::<Config, ArkConfig>(base, scalar)
     }
+
+    fn normalize_batch(points: &[Projective]) -> Vec<Affine> {
+        test_utils::normalize_batch_te_generic::<Config, ArkConfig>(points)
+    }
 }
 
 test_group!(te; Projective; te);
+
+#[test]
+fn differential() {
+    use test_utils::differential as diff;
+
+    diff::group::<Projective, ark_ed_on_bls12_377::EdwardsProjective>(
+        &diff::te_samples::<ArkConfig>(),
+    );
+    diff::normalize_batch::<Projective, ark_ed_on_bls12_377::EdwardsProjective>(
+        &diff::te_samples::<ArkConfig>(),
+        Config::normalize_batch,
+    );
+    diff::msm_small::<Projective, ark_ed_on_bls12_377::EdwardsProjective>(
+        &diff::te_samples::<ArkConfig>(),
+        Config::msm_small,
+        Config::msm_auto,
+    );
+    diff::multi_msm::<Projective, ark_ed_on_bls12_377::EdwardsProjective>(
+        &diff::te_samples::<ArkConfig>(),
+        Config::multi_msm,
+    );
+    diff::batch_mul::<Projective, ark_ed_on_bls12_377::EdwardsProjective>(
+        &diff::te_samples::<ArkConfig>(),
+        Config::batch_mul,
+    );
+}
+
+#[test]
+fn hooks_conformance() {
+    use ark_models_ext::metering::{HookOp, LocalMeter, MeteredHooks};
+
+    type Hooks = MeteredHooks<TestHooks, LocalMeter>;
+
+    test_utils::conformance::te::<crate::EdwardsConfig<Hooks>>(
+        HookOp::MsmTe,
+        HookOp::MulProjectiveTe,
+    );
+    test_utils::conformance::normalize_batch(
+        HookOp::NormalizeBatchTe,
+        crate::EdwardsConfig::<Hooks>::normalize_batch,
+    );
+    test_utils::conformance::msm_small(
+        HookOp::MsmSmallTe,
+        HookOp::MsmTe,
+        crate::EdwardsConfig::<Hooks>::msm_small,
+        crate::EdwardsConfig::<Hooks>::msm_auto,
+    );
+    test_utils::conformance::multi_msm(
+        HookOp::MultiMsmTe,
+        crate::EdwardsConfig::<Hooks>::multi_msm,
+    );
+    test_utils::conformance::batch_mul(
+        HookOp::BatchMulTe,
+        crate::EdwardsConfig::<Hooks>::batch_mul,
+    );
+}
+
+#[test]
+fn hook_free_decoding() {
+    use ark_models_ext::metering::{LocalMeter, MeteredHooks};
+    use test_utils::differential as diff;
+
+    type Hooks = MeteredHooks<TestHooks, LocalMeter>;
+
+    test_utils::conformance::decoding::<
+        crate::EdwardsProjective<Hooks>,
+        ark_ed_on_bls12_377::EdwardsProjective,
+    >(&diff::te_samples::<ArkConfig>());
+}
+
+#[test]
+fn no_panic_decoding() {
+    use ark_models_ext::AffineRepr;
+
+    let p = Affine::generator();
+    test_utils::no_panic::deserialize(&[p, Affine::zero()]);
+    test_utils::no_panic::raw(&[p, Affine::zero()]);
+    test_utils::no_panic::deserialize(&[p.into_group(), Projective::default()]);
+    test_utils::no_panic::raw(&[p.into_group(), Projective::default()]);
+}
+
+#[test]
+fn metered_hooks_usage() {
+    use ark_models_ext::{
+        metering::{HookOp, LocalMeter, MeteredHooks},
+        AffineRepr, VariableBaseMSM,
+    };
+
+    type Hooks = MeteredHooks<TestHooks, LocalMeter>;
+    type Fr = <Config as CurveConfig>::ScalarField;
+
+    let g = crate::EdwardsAffine::<Hooks>::generator();
+    let bases = [g, g, g];
+    let scalars = [Fr::from(1), Fr::from(2), Fr::from(1 << 10)];
+
+    LocalMeter::reset();
+
+    let msm = crate::EdwardsProjective::<Hooks>::msm(&bases, &scalars).unwrap();
+    let mul = g * Fr::from(1027);
+    assert_eq!(msm, mul);
+
+    let snapshot = LocalMeter::snapshot();
+    let stats = snapshot.get(HookOp::MsmTe);
+    assert_eq!(
+        (stats.calls, stats.items, stats.scalar_bits),
+        (1, 3, 1 + 2 + 11)
+    );
+    let stats = snapshot.get(HookOp::MulProjectiveTe);
+    assert_eq!((stats.calls, stats.items, stats.scalar_bits), (1, 1, 11));
+}
@@ -1,16 +1,28 @@
 use ark_ed_on_bls12_381_bandersnatch::{
-    BandersnatchConfig as ArkConfig, SW_GENERATOR_X, SW_GENERATOR_Y, TE_GENERATOR_X, TE_GENERATOR_Y,
+    BandersnatchConfig as ArkConfig, Fq, SW_GENERATOR_X, SW_GENERATOR_Y, TE_GENERATOR_X,
+    TE_GENERATOR_Y,
 };
+use ark_ff::{AdditiveGroup, Field, MontFp};
 use ark_models_ext::{
+    batch,
+    batch::MsmJob,
+    cast,
+    checked::{self, HookError},
+    limits::HookLimits,
+    metering::{HookOp, HookUsage, Meter, MeteredHooks},
     models::CurveConfig,
+    scalars,
     short_weierstrass::{self, SWCurveConfig},
     twisted_edwards::{self, MontCurveConfig, TECurveConfig},
+    validation, AffineRepr, CurveGroup, PrimeGroup,
 };
-use ark_std::marker::PhantomData;
+use ark_std::{marker::PhantomData, vec::Vec, One, Zero};
 
 #[cfg(test)]
 mod tests;
 
+pub use ark_models_ext::decoding::{check_subgroup_batch, deserialize_on_curve_only};
+
 pub type EdwardsAffine<H> = twisted_edwards::Affine<BandersnatchConfig<H>>;
 pub type EdwardsProjective<H> = twisted_edwards::Projective<BandersnatchConfig<H>>;
 
@@ -25,6 +37,13 @@ pub type SWConfig<H> = BandersnatchConfig<H>;
 
 /// Hooks for *Ed-on-BLS12-377-Bandernatch*.
 pub trait CurveHooks: 'static + Sized {
+    /// Limits enforced on the inputs before dispatching any call.
+    ///
+    /// Oversized requests never reach the hooks. The `checked_*` entry points of the
+    /// config fail with [`HookError::Limit`], while through the upstream traits
+    /// `msm` fails and projective multiplication yields the identity.
+    const LIMITS: HookLimits = HookLimits::UNLIMITED;
+
     /// Twisted Edwards multi scalar multiplication.
     fn msm_te(
         bases: &[EdwardsAffine<Self>],
@@ -43,6 +62,177 @@ pub trait CurveHooks: 'static + Sized {
 
     /// Short Weierstrass projective multiplication.
     fn mul_projective_sw(base: &SWProjective<Self>, scalar: &[u64]) -> SWProjective<Self>;
+
+    /// Twisted Edwards batch normalization.
+    ///
+    /// Defaults to the software batch normalization.
+    fn normalize_batch_te(points: &[EdwardsProjective<Self>]) -> Vec<EdwardsAffine<Self>> {
+        <EdwardsProjective<Self> as CurveGroup>::normalize_batch(points)
+    }
+
+    /// Short Weierstrass batch normalization.
+    ///
+    /// Defaults to the software batch normalization.
+    fn normalize_batch_sw(points: &[SWProjective<Self>]) -> Vec<SWAffine<Self>> {
+        <SWProjective<Self> as CurveGroup>::normalize_batch(points)
+    }
+
+    /// Twisted Edwards multi scalar multiplication with 128-bit scalars.
+    ///
+    /// Defaults to the `msm_te` hook.
+    fn msm_small_te(bases: &[EdwardsAffine<Self>], scalars: &[u128]) -> EdwardsProjective<Self> {
+        Self::msm_te(bases, &scalars::from_small(scalars))
+    }
+
+    /// Short Weierstrass multi scalar multiplication with 128-bit scalars.
+    ///
+    /// Defaults to the `msm_sw` hook.
+    fn msm_small_sw(bases: &[SWAffine<Self>], scalars: &[u128]) -> SWProjective<Self> {
+        Self::msm_sw(bases, &scalars::from_small(scalars))
+    }
+
+    /// Twisted Edwards independent multi scalar multiplications.
+    ///
+    /// Defaults to one `msm_te` hook call per job.
+    fn multi_msm_te(
+        jobs: &[MsmJob<EdwardsAffine<Self>, <EdwardsConfig<Self> as CurveConfig>::ScalarField>],
+    ) -> Vec<EdwardsProjective<Self>> {
+        jobs.iter()
+            .map(|(bases, scalars)| Self::msm_te(bases, scalars))
+            .collect()
+    }
+
+    /// Short Weierstrass independent multi scalar multiplications.
+    ///
+    /// Defaults to one `msm_sw` hook call per job.
+    fn multi_msm_sw(
+        jobs: &[MsmJob<SWAffine<Self>, <SWConfig<Self> as CurveConfig>::ScalarField>],
+    ) -> Vec<SWProjective<Self>> {
+        jobs.iter()
+            .map(|(bases, scalars)| Self::msm_sw(bases, scalars))
+            .collect()
+    }
+
+    /// Twisted Edwards multiplication of one base by many scalars.
+    ///
+    /// Defaults to one `mul_projective_te` hook call per scalar, followed by the
+    /// `normalize_batch_te` hook.
+    fn batch_mul_te(
+        base: &EdwardsProjective<Self>,
+        scalars: &[<EdwardsConfig<Self> as CurveConfig>::ScalarField],
+    ) -> Vec<EdwardsAffine<Self>> {
+        batch::batch_mul_each(
+            scalars,
+            |s| Self::mul_projective_te(base, s),
+            Self::normalize_batch_te,
+        )
+    }
+
+    /// Short Weierstrass multiplication of one base by many scalars.
+    ///
+    /// Defaults to one `mul_projective_sw` hook call per scalar, followed by the
+    /// `normalize_batch_sw` hook.
+    fn batch_mul_sw(
+        base: &SWProjective<Self>,
+        scalars: &[<SWConfig<Self> as CurveConfig>::ScalarField],
+    ) -> Vec<SWAffine<Self>> {
+        batch::batch_mul_each(
+            scalars,
+            |s| Self::mul_projective_sw(base, s),
+            Self::normalize_batch_sw,
+        )
+    }
+}
+
+impl<H: CurveHooks, M: Meter> CurveHooks for MeteredHooks<H, M> {
+    const LIMITS: HookLimits = H::LIMITS;
+
+    fn msm_te(
+        bases: &[EdwardsAffine<Self>],
+        scalars: &[<EdwardsConfig<Self> as CurveConfig>::ScalarField],
+    ) -> EdwardsProjective<Self> {
+        M::record(HookOp::MsmTe, HookUsage::msm(scalars));
+        let res = H::msm_te(&cast::te_affine_slice(bases), scalars);
+        cast::te_projective(&res)
+    }
+
+    fn mul_projective_te(
+        base: &EdwardsProjective<Self>,
+        scalar: &[u64],
+    ) -> EdwardsProjective<Self> {
+        M::record(HookOp::MulProjectiveTe, HookUsage::mul(scalar));
+        let res = H::mul_projective_te(&cast::te_projective(base), scalar);
+        cast::te_projective(&res)
+    }
+
+    fn msm_sw(
+        bases: &[SWAffine<Self>],
+        scalars: &[<SWConfig<Self> as CurveConfig>::ScalarField],
+    ) -> SWProjective<Self> {
+        M::record(HookOp::MsmSw, HookUsage::msm(scalars));
+        let res = H::msm_sw(&cast::sw_affine_slice(bases), scalars);
+        cast::sw_projective(&res)
+    }
+
+    fn mul_projective_sw(base: &SWProjective<Self>, scalar: &[u64]) -> SWProjective<Self> {
+        M::record(HookOp::MulProjectiveSw, HookUsage::mul(scalar));
+        let res = H::mul_projective_sw(&cast::sw_projective(base), scalar);
+        cast::sw_projective(&res)
+    }
+
+    fn normalize_batch_te(points: &[EdwardsProjective<Self>]) -> Vec<EdwardsAffine<Self>> {
+        M::record(HookOp::NormalizeBatchTe, HookUsage::items(points.len()));
+        cast::te_normalize_batch(points, H::normalize_batch_te)
+    }
+
+    fn normalize_batch_sw(points: &[SWProjective<Self>]) -> Vec<SWAffine<Self>> {
+        M::record(HookOp::NormalizeBatchSw, HookUsage::items(points.len()));
+        cast::sw_normalize_batch(points, H::normalize_batch_sw)
+    }
+
+    fn msm_small_te(bases: &[EdwardsAffine<Self>], scalars: &[u128]) -> EdwardsProjective<Self> {
+        M::record(HookOp::MsmSmallTe, HookUsage::msm_small(scalars));
+        let res = H::msm_small_te(&cast::te_affine_slice(bases), scalars);
+        cast::te_projective(&res)
+    }
+
+    fn msm_small_sw(bases: &[SWAffine<Self>], scalars: &[u128]) -> SWProjective<Self> {
+        M::record(HookOp::MsmSmallSw, HookUsage::msm_small(scalars));
+        let res = H::msm_small_sw(&cast::sw_affine_slice(bases), scalars);
+        cast::sw_projective(&res)
+    }
+
+    fn multi_msm_te(
+        jobs: &[MsmJob<EdwardsAffine<Self>, <EdwardsConfig<Self> as CurveConfig>::ScalarField>],
+    ) -> Vec<EdwardsProjective<Self>> {
+        M::record(HookOp::MultiMsmTe, HookUsage::multi_msm(jobs));
+        cast::te_multi_msm(jobs, H::multi_msm_te)
+    }
+
+    fn multi_msm_sw(
+        jobs: &[MsmJob<SWAffine<Self>, <SWConfig<Self> as CurveConfig>::ScalarField>],
+    ) -> Vec<SWProjective<Self>> {
+        M::record(HookOp::MultiMsmSw, HookUsage::multi_msm(jobs));
+        cast::sw_multi_msm(jobs, H::multi_msm_sw)
+    }
+
+    fn batch_mul_te(
+        base: &EdwardsProjective<Self>,
+        scalars: &[<EdwardsConfig<Self> as CurveConfig>::ScalarField],
+    ) -> Vec<EdwardsAffine<Self>> {
+        M::record(HookOp::BatchMulTe, HookUsage::batch_mul(scalars));
+        let res = H::batch_mul_te(&cast::te_projective(base), scalars);
+        cast::te_affine_slice(&res)
+    }
+
+    fn batch_mul_sw(
+        base: &SWProjective<Self>,
+        scalars: &[<SWConfig<Self> as CurveConfig>::ScalarField],
+    ) -> Vec<SWAffine<Self>> {
+        M::record(HookOp::BatchMulSw, HookUsage::batch_mul(scalars));
+        let res = H::batch_mul_sw(&cast::sw_projective(base), scalars);
+        cast::sw_affine_slice(&res)
+    }
 }
 
 impl<H: CurveHooks> CurveConfig for BandersnatchConfig<H> {
@@ -63,21 +253,23 @@ impl<H: CurveHooks> TECurveConfig for BandersnatchConfig<H> {
     type MontCurveConfig = Self;
 
     /// Multi scalar multiplication jumping into the user-defined `te_msm` hook.
+    ///
+    /// Fails on any `checked_msm_te` failure, see [`checked::upstream_msm`].
     #[inline(always)]
     fn msm(
         bases: &[EdwardsAffine<H>],
         scalars: &[Self::ScalarField],
     ) -> Result<EdwardsProjective<H>, usize> {
-        if bases.len() != scalars.len() {
-            return Err(bases.len().min(scalars.len()));
-        }
-        Ok(H::msm_te(bases, scalars))
+        checked::upstream_msm(bases.len(), Self::checked_msm_te(bases, scalars))
     }
 
     /// Projective multiplication jumping into the user-defined `te_mul_projective` hook.
+    ///
+    /// The identity if the scalar exceeds the hooks limits, see
+    /// [`checked::upstream_mul`].
     #[inline(always)]
     fn mul_projective(base: &EdwardsProjective<H>, scalar: &[u64]) -> EdwardsProjective<H> {
-        H::mul_projective_te(base, scalar)
+        checked::upstream_mul(base, scalar, Self::checked_mul_te(base, scalar))
     }
 
     /// Affine multiplication jumping into the user-defined `te_mul_projective` hook.
@@ -90,6 +282,26 @@ impl<H: CurveHooks> TECurveConfig for BandersnatchConfig<H> {
     fn mul_by_a(elem: Self::BaseField) -> Self::BaseField {
         <ArkConfig as TECurveConfig>::mul_by_a(elem)
     }
+
+    /// Subgroup check via the endomorphism of the short Weierstrass form.
+    ///
+    /// See [`endomorphism`] and the short Weierstrass subgroup check.
+    #[inline(always)]
+    fn is_in_correct_subgroup_assuming_on_curve(item: &EdwardsAffine<H>) -> bool {
+        let p = EdwardsProjective::<H>::from(*item);
+        let a_minus_one_p = p.mul_bigint(SUBGROUP_CHECK_A_MINUS_ONE).into_affine();
+        let b_p = p.mul_bigint(SUBGROUP_CHECK_B).into_affine();
+        let a_p = te_to_sw(&a_minus_one_p) + te_to_sw(item);
+        (a_p + endomorphism(&te_to_sw(&b_p))).is_zero()
+    }
+
+    /// Multiplication by the cofactor 4 via two doublings.
+    ///
+    /// Cheaper than both a hook call and an endomorphism evaluation.
+    #[inline(always)]
+    fn clear_cofactor(item: &EdwardsAffine<H>) -> EdwardsAffine<H> {
+        item.into_group().double().double().into_affine()
+    }
 }
 
 impl<H: CurveHooks> SWCurveConfig for BandersnatchConfig<H> {
@@ -100,21 +312,19 @@ impl<H: CurveHooks> SWCurveConfig for BandersnatchConfig<H> {
 
     /// Multi scalar multiplication jumping into the user-defined `sw_msm` hook.
     ///
-    /// On any internal error returns `Err(0)`.
+    /// Fails on any `checked_msm_sw` failure, see [`checked::upstream_msm`].
     #[inline(always)]
     fn msm(bases: &[SWAffine<H>], scalars: &[Self::ScalarField]) -> Result<SWProjective<H>, usize> {
-        if bases.len() != scalars.len() {
-            return Err(bases.len().min(scalars.len()));
-        }
-        Ok(H::msm_sw(bases, scalars))
+        checked::upstream_msm(bases.len(), Self::checked_msm_sw(bases, scalars))
     }
 
     /// Projective multiplication jumping into the user-defined `sw_mul_projective` hook.
     ///
-    /// On any internal error returns `Projective::zero()`.
+    /// The identity if the scalar exceeds the hooks limits, see
+    /// [`checked::upstream_mul`].
     #[inline(always)]
     fn mul_projective(base: &SWProjective<H>, scalar: &[u64]) -> SWProjective<H> {
-        H::mul_projective_sw(base, scalar)
+        checked::upstream_mul(base, scalar, Self::checked_mul_sw(base, scalar))
     }
 
     /// Affine multiplication jumping into the user-defined `sw_mul_projective` hook.
@@ -122,6 +332,29 @@ impl<H: CurveHooks> SWCurveConfig for BandersnatchConfig<H> {
     fn mul_affine(base: &SWAffine<H>, scalar: &[u64]) -> SWProjective<H> {
         <Self as SWCurveConfig>::mul_projective(&(*base).into(), scalar)
     }
+
+    /// Subgroup check via the endomorphism, i.e. `[a]P + endomorphism([b]P) = 0`.
+    ///
+    /// `(a, b)` is a short vector of the lattice of the `(a, b)` such that
+    /// `a + b * LAMBDA = 0 mod r`. As `a` is odd and `b` is even, `a + b * endomorphism`
+    /// acts as the identity on the 2-torsion points, which make up the whole cofactor
+    /// group. `[a]P` is computed as `[a - 1]P + P`, so that a hook returning the
+    /// identity can't make any point pass.
+    #[inline(always)]
+    fn is_in_correct_subgroup_assuming_on_curve(item: &SWAffine<H>) -> bool {
+        let p = SWProjective::<H>::from(*item);
+        let a_p = p.mul_bigint(SUBGROUP_CHECK_A_MINUS_ONE) + p;
+        let b_p = p.mul_bigint(SUBGROUP_CHECK_B);
+        (a_p + endomorphism(&b_p)).is_zero()
+    }
+
+    /// Multiplication by the cofactor 4 via two doublings.
+    ///
+    /// Cheaper than both a hook call and an endomorphism evaluation.
+    #[inline(always)]
+    fn clear_cofactor(item: &SWAffine<H>) -> SWAffine<H> {
+        item.into_group().double().double().into_affine()
+    }
 }
 
 impl<H: CurveHooks> MontCurveConfig for BandersnatchConfig<H> {
@@ -130,3 +363,274 @@ impl<H: CurveHooks> MontCurveConfig for BandersnatchConfig<H> {
 
     type TECurveConfig = Self;
 }
+
+impl<H: CurveHooks> BandersnatchConfig<H> {
+    /// Multi scalar multiplication jumping into the user-defined `msm_te` hook.
+    ///
+    /// Fails if the lengths differ or exceed the hooks limits, or if the hook
+    /// output is not on the curve.
+    pub fn checked_msm_te(
+        bases: &[EdwardsAffine<H>],
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<EdwardsProjective<H>, HookError> {
+        checked::msm(
+            bases,
+            scalars,
+            &H::LIMITS,
+            H::msm_te,
+            validation::te_projective_is_on_curve,
+        )
+    }
+
+    /// Projective multiplication jumping into the user-defined `mul_projective_te` hook.
+    ///
+    /// Fails if the scalar exceeds the hooks limits or if the hook output is not
+    /// on the curve.
+    pub fn checked_mul_te(
+        base: &EdwardsProjective<H>,
+        scalar: &[u64],
+    ) -> Result<EdwardsProjective<H>, HookError> {
+        checked::mul(
+            scalar,
+            &H::LIMITS,
+            |scalar| H::mul_projective_te(base, scalar),
+            validation::te_projective_is_on_curve,
+        )
+    }
+
+    /// Multi scalar multiplication jumping into the user-defined `msm_sw` hook.
+    ///
+    /// Fails if the lengths differ or exceed the hooks limits, or if the hook
+    /// output is not on the curve.
+    pub fn checked_msm_sw(
+        bases: &[SWAffine<H>],
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<SWProjective<H>, HookError> {
+        checked::msm(
+            bases,
+            scalars,
+            &H::LIMITS,
+            H::msm_sw,
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Projective multiplication jumping into the user-defined `mul_projective_sw` hook.
+    ///
+    /// Fails if the scalar exceeds the hooks limits or if the hook output is not
+    /// on the curve.
+    pub fn checked_mul_sw(
+        base: &SWProjective<H>,
+        scalar: &[u64],
+    ) -> Result<SWProjective<H>, HookError> {
+        checked::mul(
+            scalar,
+            &H::LIMITS,
+            |scalar| H::mul_projective_sw(base, scalar),
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Batch normalization jumping into the user-defined `normalize_batch_te` hook.
+    ///
+    /// See [`checked::normalize_batch`].
+    pub fn normalize_batch_te(points: &[EdwardsProjective<H>]) -> Vec<EdwardsAffine<H>> {
+        checked::normalize_batch(
+            points,
+            H::normalize_batch_te,
+            validation::te_is_normalization_of,
+        )
+    }
+
+    /// Batch normalization jumping into the user-defined `normalize_batch_sw` hook.
+    ///
+    /// See [`checked::normalize_batch`].
+    pub fn normalize_batch_sw(points: &[SWProjective<H>]) -> Vec<SWAffine<H>> {
+        checked::normalize_batch(
+            points,
+            H::normalize_batch_sw,
+            validation::sw_is_normalization_of,
+        )
+    }
+
+    /// Multi scalar multiplication with 128-bit scalars jumping into the
+    /// user-defined `msm_small_te` hook.
+    ///
+    /// Same checks and errors as `checked_msm`.
+    pub fn msm_small_te(
+        bases: &[EdwardsAffine<H>],
+        scalars: &[u128],
+    ) -> Result<EdwardsProjective<H>, HookError> {
+        checked::msm(
+            bases,
+            scalars,
+            &H::LIMITS,
+            H::msm_small_te,
+            validation::te_projective_is_on_curve,
+        )
+    }
+
+    /// Multi scalar multiplication jumping into the user-defined `msm_small_te` hook
+    /// if all the scalars fit 128 bits, into the `msm_te` hook otherwise.
+    pub fn msm_auto_te(
+        bases: &[EdwardsAffine<H>],
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<EdwardsProjective<H>, HookError> {
+        checked::msm_auto(
+            scalars,
+            |scalars| Self::msm_small_te(bases, scalars),
+            |scalars| Self::checked_msm_te(bases, scalars),
+        )
+    }
+
+    /// Multi scalar multiplication with 128-bit scalars jumping into the
+    /// user-defined `msm_small_sw` hook.
+    ///
+    /// Same checks and errors as `checked_msm`.
+    pub fn msm_small_sw(
+        bases: &[SWAffine<H>],
+        scalars: &[u128],
+    ) -> Result<SWProjective<H>, HookError> {
+        checked::msm(
+            bases,
+            scalars,
+            &H::LIMITS,
+            H::msm_small_sw,
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Multi scalar multiplication jumping into the user-defined `msm_small_sw` hook
+    /// if all the scalars fit 128 bits, into the `msm_sw` hook otherwise.
+    pub fn msm_auto_sw(
+        bases: &[SWAffine<H>],
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<SWProjective<H>, HookError> {
+        checked::msm_auto(
+            scalars,
+            |scalars| Self::msm_small_sw(bases, scalars),
+            |scalars| Self::checked_msm_sw(bases, scalars),
+        )
+    }
+
+    /// Independent multi scalar multiplications jumping into the user-defined
+    /// `multi_msm_te` hook, with a single call for all the jobs.
+    ///
+    /// Each job is checked as by `checked_msm`, with the limits applied to the
+    /// total number of bases. See [`batch::multi_msm`] for the errors.
+    pub fn multi_msm_te(
+        jobs: &[MsmJob<EdwardsAffine<H>, <Self as CurveConfig>::ScalarField>],
+    ) -> Vec<Result<EdwardsProjective<H>, HookError>> {
+        batch::multi_msm(
+            jobs,
+            &H::LIMITS,
+            H::multi_msm_te,
+            validation::te_projective_is_on_curve,
+        )
+    }
+
+    /// Independent multi scalar multiplications jumping into the user-defined
+    /// `multi_msm_sw` hook, with a single call for all the jobs.
+    ///
+    /// Each job is checked as by `checked_msm`, with the limits applied to the
+    /// total number of bases. See [`batch::multi_msm`] for the errors.
+    pub fn multi_msm_sw(
+        jobs: &[MsmJob<SWAffine<H>, <Self as CurveConfig>::ScalarField>],
+    ) -> Vec<Result<SWProjective<H>, HookError>> {
+        batch::multi_msm(
+            jobs,
+            &H::LIMITS,
+            H::multi_msm_sw,
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Multiplication of one base by many scalars jumping into the user-defined
+    /// `batch_mul_te` hook, with a single call for all the scalars.
+    ///
+    /// The products are returned in affine form. See [`batch::batch_mul`] for the
+    /// errors.
+    pub fn batch_mul_te(
+        base: &EdwardsProjective<H>,
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<Vec<EdwardsAffine<H>>, HookError> {
+        batch::batch_mul(
+            scalars,
+            &H::LIMITS,
+            |scalars| H::batch_mul_te(base, scalars),
+            |p| p.is_on_curve(),
+        )
+    }
+
+    /// Multiplication of one base by many scalars jumping into the user-defined
+    /// `batch_mul_sw` hook, with a single call for all the scalars.
+    ///
+    /// The products are returned in affine form. See [`batch::batch_mul`] for the
+    /// errors.
+    pub fn batch_mul_sw(
+        base: &SWProjective<H>,
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<Vec<SWAffine<H>>, HookError> {
+        batch::batch_mul(
+            scalars,
+            &H::LIMITS,
+            |scalars| H::batch_mul_sw(base, scalars),
+            |p| p.is_on_curve(),
+        )
+    }
+}
+
+// The endomorphism is the 2-isogeny with kernel `(ENDO_X0, 0)` given by Velu's
+// formulas, followed by the isomorphism `(x, y) -> (x / u^2, y / u^3)` back to the
+// curve. On the prime order subgroup it acts as the multiplication by
+// `LAMBDA = 8913659658109529928382530854484400854125314752504019737736543920008458395397`,
+// a square root of -2 modulo r.
+
+/// ENDO_X0 = Mont(A) / (3 * Mont(B)), image of the twisted Edwards point `(0, -1)`.
+const ENDO_X0: Fq =
+    MontFp!("42460977304182762931716743824405123254375045638571669698531889431804823178961");
+/// ENDO_T = 3 * ENDO_X0^2 + COEFF_A = 1 / Mont(B)^2
+const ENDO_T: Fq =
+    MontFp!("35484827650731063748396669747216844996598387089274032563585525486049249153249");
+/// ENDO_U^2 = (A * B') / (A' * B), where A' and B' are the coefficients of the isogenous curve.
+const ENDO_U: Fq =
+    MontFp!("32359977515833121656085406038315725110847830519975033340727966304540537229224");
+/// MONT_B_INV = 1 / Mont(B)
+const MONT_B_INV: Fq =
+    MontFp!("41180284393978236561320365279764246793818536543197771097409483252169927600582");
+
+/// a - 1 = 113482231691339203864511368254957623326
+const SUBGROUP_CHECK_A_MINUS_ONE: &[u64] = &[0x4b02f94a9789181e, 0x555fe2004be6928e];
+/// b = 10741319382058138887739339959866629956
+const SUBGROUP_CHECK_B: &[u64] = &[0xf8e2591a23d61f44, 0x0814b3eee55e8f5d];
+
+/// Degree-2 endomorphism of the short Weierstrass form, with `endomorphism^2 = -2`.
+pub fn endomorphism<H: CurveHooks>(p: &SWProjective<H>) -> SWProjective<H> {
+    // With (X, Y, Z) Jacobian coordinates and D = X - ENDO_X0 * Z^2:
+    // x' = (X^2 - ENDO_X0 * X * Z^2 + ENDO_T * Z^4) * D
+    // y' = Y * (D^2 - ENDO_T * Z^4) * D
+    // z' = ENDO_U * D * Z
+    let z2 = p.z.square();
+    let t_z4 = ENDO_T * z2.square();
+    let d = p.x - ENDO_X0 * z2;
+    let x = (p.x.square() - ENDO_X0 * p.x * z2 + t_z4) * d;
+    let y = p.y * (d.square() - t_z4) * d;
+    let z = ENDO_U * d * p.z;
+    SWProjective::<H>::new_unchecked(x, y, z)
+}
+
+/// Map a twisted Edwards point to the short Weierstrass form, via the Montgomery form.
+fn te_to_sw<H: CurveHooks>(p: &EdwardsAffine<H>) -> SWProjective<H> {
+    if p.is_zero() {
+        return SWProjective::<H>::zero();
+    }
+    if p.x.is_zero() {
+        return SWAffine::<H>::new_unchecked(ENDO_X0, Fq::ZERO).into();
+    }
+    // Montgomery: u = (1 + y) / (1 - y), v = u / x
+    // Weierstrass: x = u / Mont(B) + Mont(A) / (3 * Mont(B)), y = v / Mont(B)
+    let w = ((Fq::one() - p.y) * p.x).inverse().unwrap_or_default();
+    let v = (Fq::one() + p.y) * w;
+    let u = v * p.x;
+    SWAffine::<H>::new_unchecked(u * MONT_B_INV + ENDO_X0, v * MONT_B_INV).into()
+}
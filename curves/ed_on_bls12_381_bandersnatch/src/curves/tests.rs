@@ -2,7 +2,10 @@ use crate::CurveHooks;
 
 use ark_algebra_test_templates::*;
 use ark_ed_on_bls12_381_bandersnatch::BandersnatchConfig as ArkConfig;
-use ark_models_ext::CurveConfig;
+use ark_ff::{AdditiveGroup, Field, MontFp, PrimeField, Zero};
+use ark_models_ext::{AffineRepr, CurveConfig, CurveGroup, PrimeGroup};
+use ark_std::{rand::Rng, test_rng, vec::Vec, UniformRand};
+use test_utils::mul_plain;
 
 pub struct TestHooks;
 
@@ -34,7 +37,408 @@ impl CurveHooks for TestHooks {
     fn mul_projective_sw(base: &SWProjective, scalar: &[u64]) -> SWProjective {
         test_utils::mul_projective_sw_generic::<Config, ArkConfig>(base, scalar)
     }
+
+    fn normalize_batch_te(points: &[EdwardsProjective]) -> Vec<EdwardsAffine> {
+        test_utils::normalize_batch_te_generic::<Config, ArkConfig>(points)
+    }
+
+    fn normalize_batch_sw(points: &[SWProjective]) -> Vec<SWAffine> {
+        test_utils::normalize_batch_sw_generic::<Config, ArkConfig>(points)
+    }
 }
 
 test_group!(te; EdwardsProjective; te);
 test_group!(sw; SWProjective; sw);
+
+#[test]
+fn differential() {
+    use test_utils::differential as diff;
+
+    diff::group::<EdwardsProjective, ark_ed_on_bls12_381_bandersnatch::EdwardsProjective>(
+        &diff::te_samples::<ArkConfig>(),
+    );
+    diff::group::<SWProjective, ark_ed_on_bls12_381_bandersnatch::SWProjective>(
+        &diff::sw_samples::<ArkConfig>(),
+    );
+    diff::normalize_batch::<EdwardsProjective, ark_ed_on_bls12_381_bandersnatch::EdwardsProjective>(
+        &diff::te_samples::<ArkConfig>(),
+        Config::normalize_batch_te,
+    );
+    diff::normalize_batch::<SWProjective, ark_ed_on_bls12_381_bandersnatch::SWProjective>(
+        &diff::sw_samples::<ArkConfig>(),
+        Config::normalize_batch_sw,
+    );
+    diff::msm_small::<EdwardsProjective, ark_ed_on_bls12_381_bandersnatch::EdwardsProjective>(
+        &diff::te_samples::<ArkConfig>(),
+        Config::msm_small_te,
+        Config::msm_auto_te,
+    );
+    diff::multi_msm::<EdwardsProjective, ark_ed_on_bls12_381_bandersnatch::EdwardsProjective>(
+        &diff::te_samples::<ArkConfig>(),
+        Config::multi_msm_te,
+    );
+    diff::batch_mul::<EdwardsProjective, ark_ed_on_bls12_381_bandersnatch::EdwardsProjective>(
+        &diff::te_samples::<ArkConfig>(),
+        Config::batch_mul_te,
+    );
+    diff::msm_small::<SWProjective, ark_ed_on_bls12_381_bandersnatch::SWProjective>(
+        &diff::sw_samples::<ArkConfig>(),
+        Config::msm_small_sw,
+        Config::msm_auto_sw,
+    );
+    diff::multi_msm::<SWProjective, ark_ed_on_bls12_381_bandersnatch::SWProjective>(
+        &diff::sw_samples::<ArkConfig>(),
+        Config::multi_msm_sw,
+    );
+    diff::batch_mul::<SWProjective, ark_ed_on_bls12_381_bandersnatch::SWProjective>(
+        &diff::sw_samples::<ArkConfig>(),
+        Config::batch_mul_sw,
+    );
+}
+
+#[test]
+fn hooks_conformance() {
+    use ark_models_ext::metering::{HookOp, LocalMeter, MeteredHooks};
+
+    type Hooks = MeteredHooks<TestHooks, LocalMeter>;
+
+    type Config = crate::BandersnatchConfig<Hooks>;
+
+    test_utils::conformance::te::<Config>(HookOp::MsmTe, HookOp::MulProjectiveTe);
+    test_utils::conformance::sw::<Config>(HookOp::MsmSw, HookOp::MulProjectiveSw);
+    test_utils::conformance::normalize_batch(HookOp::NormalizeBatchTe, Config::normalize_batch_te);
+    test_utils::conformance::normalize_batch(HookOp::NormalizeBatchSw, Config::normalize_batch_sw);
+    test_utils::conformance::msm_small(
+        HookOp::MsmSmallTe,
+        HookOp::MsmTe,
+        Config::msm_small_te,
+        Config::msm_auto_te,
+    );
+    test_utils::conformance::multi_msm(HookOp::MultiMsmTe, Config::multi_msm_te);
+    test_utils::conformance::batch_mul(HookOp::BatchMulTe, Config::batch_mul_te);
+    test_utils::conformance::msm_small(
+        HookOp::MsmSmallSw,
+        HookOp::MsmSw,
+        Config::msm_small_sw,
+        Config::msm_auto_sw,
+    );
+    test_utils::conformance::multi_msm(HookOp::MultiMsmSw, Config::multi_msm_sw);
+    test_utils::conformance::batch_mul(HookOp::BatchMulSw, Config::batch_mul_sw);
+}
+
+#[test]
+fn hook_free_decoding() {
+    use ark_models_ext::metering::{LocalMeter, MeteredHooks};
+    use test_utils::differential as diff;
+
+    type Hooks = MeteredHooks<TestHooks, LocalMeter>;
+
+    test_utils::conformance::decoding::<
+        crate::EdwardsProjective<Hooks>,
+        ark_ed_on_bls12_381_bandersnatch::EdwardsProjective,
+    >(&diff::te_samples::<ArkConfig>());
+    test_utils::conformance::decoding::<
+        crate::SWProjective<Hooks>,
+        ark_ed_on_bls12_381_bandersnatch::SWProjective,
+    >(&diff::sw_samples::<ArkConfig>());
+}
+
+#[test]
+fn no_panic_decoding() {
+    let p = EdwardsAffine::generator();
+    test_utils::no_panic::deserialize(&[p, EdwardsAffine::zero()]);
+    test_utils::no_panic::raw(&[p, EdwardsAffine::zero()]);
+    test_utils::no_panic::deserialize(&[p.into_group(), EdwardsProjective::default()]);
+    test_utils::no_panic::raw(&[p.into_group(), EdwardsProjective::default()]);
+    let p = SWAffine::generator();
+    test_utils::no_panic::deserialize(&[p, SWAffine::zero()]);
+    test_utils::no_panic::raw(&[p, SWAffine::zero()]);
+    test_utils::no_panic::deserialize(&[p.into_group(), SWProjective::default()]);
+    test_utils::no_panic::raw(&[p.into_group(), SWProjective::default()]);
+}
+
+type Fq = <Config as CurveConfig>::BaseField;
+type Fr = <Config as CurveConfig>::ScalarField;
+
+const LAMBDA: Fr =
+    MontFp!("8913659658109529928382530854484400854125314752504019737736543920008458395397");
+
+fn in_subgroup_naive<G: CurveGroup>(p: G) -> bool {
+    mul_plain(p, Fr::MODULUS).is_zero()
+}
+
+/// Random short Weierstrass points, mostly outside the prime order subgroup.
+fn sw_curve_samples() -> Vec<SWAffine> {
+    let mut rng = test_rng();
+    let mut samples = Vec::new();
+    while samples.len() < 10 {
+        let x = Fq::rand(&mut rng);
+        samples.extend(SWAffine::get_point_from_x_unchecked(x, rng.gen()));
+    }
+    samples
+}
+
+/// Twisted Edwards 2-torsion point.
+fn te_torsion() -> EdwardsAffine {
+    EdwardsAffine::new_unchecked(Fq::ZERO, -Fq::ONE)
+}
+
+#[test]
+fn test_endomorphism() {
+    let mut rng = test_rng();
+    let p = SWProjective::rand(&mut rng);
+    let lambda = LAMBDA.into_bigint();
+    assert_eq!(crate::endomorphism(&p), mul_plain(p, lambda));
+    assert_eq!(LAMBDA.square(), -Fr::from(2));
+
+    for p in sw_curve_samples() {
+        let p = p.into_group();
+        let psi2 = crate::endomorphism(&crate::endomorphism(&p));
+        assert_eq!(psi2, -p.double());
+    }
+    assert!(crate::endomorphism(&SWProjective::zero()).is_zero());
+}
+
+#[test]
+fn test_te_to_sw() {
+    let mut rng = test_rng();
+    assert_eq!(
+        crate::curves::te_to_sw(&EdwardsAffine::generator()),
+        SWProjective::generator()
+    );
+    assert!(crate::curves::te_to_sw(&EdwardsAffine::zero()).is_zero());
+    let torsion = crate::curves::te_to_sw(&te_torsion());
+    assert!(!torsion.is_zero() && torsion.double().is_zero());
+
+    let k = Fr::rand(&mut rng).into_bigint();
+    let p = mul_plain(EdwardsProjective::generator(), k).into_affine();
+    let expected = mul_plain(SWProjective::generator(), k);
+    assert_eq!(crate::curves::te_to_sw(&p), expected);
+    let p = (p + te_torsion()).into_affine();
+    assert_eq!(crate::curves::te_to_sw(&p), expected + torsion);
+}
+
+#[test]
+fn test_sw_subgroup_check() {
+    let mut rng = test_rng();
+    let p = SWProjective::rand(&mut rng).into_affine();
+    assert!(p.is_in_correct_subgroup_assuming_on_curve());
+    assert!(SWAffine::zero().is_in_correct_subgroup_assuming_on_curve());
+
+    for p in sw_curve_samples() {
+        // Cofactor points
+        let expected = in_subgroup_naive(p.into_group());
+        assert_eq!(p.is_in_correct_subgroup_assuming_on_curve(), expected);
+        // Low-order points
+        let low_order = mul_plain(p.into_group(), Fr::MODULUS).into_affine();
+        assert_eq!(low_order.is_zero(), expected);
+        assert_eq!(
+            low_order.is_in_correct_subgroup_assuming_on_curve(),
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_te_subgroup_check() {
+    let mut rng = test_rng();
+    assert!(EdwardsAffine::zero().is_in_correct_subgroup_assuming_on_curve());
+    assert!(!te_torsion().is_in_correct_subgroup_assuming_on_curve());
+
+    for _ in 0..10 {
+        let p = EdwardsProjective::rand(&mut rng).into_affine();
+        assert!(p.is_in_correct_subgroup_assuming_on_curve());
+        // Cofactor points
+        let p = (p + te_torsion()).into_affine();
+        assert!(!in_subgroup_naive(p.into_group()));
+        assert!(!p.is_in_correct_subgroup_assuming_on_curve());
+    }
+}
+
+#[test]
+fn test_cofactor_clearing() {
+    let mut rng = test_rng();
+    for p in sw_curve_samples() {
+        let cleared = p.clear_cofactor();
+        assert_eq!(cleared, mul_plain(p.into_group(), [4]).into_affine());
+        assert!(in_subgroup_naive(cleared.into_group()));
+    }
+    for _ in 0..10 {
+        let p = (EdwardsProjective::rand(&mut rng) + te_torsion()).into_affine();
+        let cleared = p.clear_cofactor();
+        assert_eq!(cleared, mul_plain(p.into_group(), [4]).into_affine());
+        assert!(in_subgroup_naive(cleared.into_group()));
+    }
+}
+
+mod faults {
+    use super::TestHooks;
+    use crate::CurveHooks;
+
+    use ark_ff::{One, PrimeField, Zero};
+    use ark_models_ext::{
+        cast, metering::HookOp, short_weierstrass::SWCurveConfig, AffineRepr, CurveConfig,
+        CurveGroup, VariableBaseMSM,
+    };
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use ark_std::{test_rng, vec::Vec, UniformRand};
+    use test_utils::faults::{self, Fault, FaultyHooks};
+
+    type Config = crate::BandersnatchConfig<FaultyHooks<TestHooks>>;
+    type Fq = <Config as CurveConfig>::BaseField;
+    type Fr = <Config as CurveConfig>::ScalarField;
+    type EdwardsAffine = crate::EdwardsAffine<FaultyHooks<TestHooks>>;
+    type EdwardsProjective = crate::EdwardsProjective<FaultyHooks<TestHooks>>;
+    type SWAffine = crate::SWAffine<FaultyHooks<TestHooks>>;
+    type SWProjective = crate::SWProjective<FaultyHooks<TestHooks>>;
+
+    const FAULTS: [Fault; 5] = [
+        Fault::Identity,
+        Fault::Random,
+        Fault::OffCurve,
+        Fault::NonSubgroup,
+        Fault::Fail,
+    ];
+
+    impl<H: CurveHooks> CurveHooks for FaultyHooks<H> {
+        fn msm_te(
+            bases: &[crate::EdwardsAffine<Self>],
+            scalars: &[<crate::BandersnatchConfig<Self> as CurveConfig>::ScalarField],
+        ) -> crate::EdwardsProjective<Self> {
+            let res = H::msm_te(&cast::te_affine_slice(bases), scalars);
+            faults::faulty_te(HookOp::MsmTe, cast::te_projective(&res))
+        }
+
+        fn mul_projective_te(
+            base: &crate::EdwardsProjective<Self>,
+            scalar: &[u64],
+        ) -> crate::EdwardsProjective<Self> {
+            let res = H::mul_projective_te(&cast::te_projective(base), scalar);
+            faults::faulty_te(HookOp::MulProjectiveTe, cast::te_projective(&res))
+        }
+
+        fn msm_sw(
+            bases: &[crate::SWAffine<Self>],
+            scalars: &[<crate::BandersnatchConfig<Self> as CurveConfig>::ScalarField],
+        ) -> crate::SWProjective<Self> {
+            let res = H::msm_sw(&cast::sw_affine_slice(bases), scalars);
+            faults::faulty_sw(HookOp::MsmSw, cast::sw_projective(&res))
+        }
+
+        fn mul_projective_sw(
+            base: &crate::SWProjective<Self>,
+            scalar: &[u64],
+        ) -> crate::SWProjective<Self> {
+            let res = H::mul_projective_sw(&cast::sw_projective(base), scalar);
+            faults::faulty_sw(HookOp::MulProjectiveSw, cast::sw_projective(&res))
+        }
+
+        fn normalize_batch_te(
+            points: &[crate::EdwardsProjective<Self>],
+        ) -> Vec<crate::EdwardsAffine<Self>> {
+            let res = H::normalize_batch_te(&cast::te_projective_slice(points));
+            res.iter()
+                .map(|p| {
+                    let p = cast::te_affine::<_, crate::BandersnatchConfig<Self>>(p);
+                    faults::faulty_te(HookOp::NormalizeBatchTe, p.into_group()).into_affine()
+                })
+                .collect()
+        }
+
+        fn normalize_batch_sw(points: &[crate::SWProjective<Self>]) -> Vec<crate::SWAffine<Self>> {
+            let res = H::normalize_batch_sw(&cast::sw_projective_slice(points));
+            res.iter()
+                .map(|p| {
+                    let p = cast::sw_affine::<_, crate::BandersnatchConfig<Self>>(p);
+                    faults::faulty_sw(HookOp::NormalizeBatchSw, p.into_group()).into_affine()
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn faulty_mul_never_validates_non_subgroup_points() {
+        let mut rng = test_rng();
+        faults::clear();
+        // Twisted Edwards formulas are not complete for this curve, thus a random
+        // point may lead the test hooks to hit an exceptional case. A subgroup point
+        // plus the 2-torsion point `(0, -1)` never does.
+        let torsion = EdwardsAffine::new_unchecked(Fq::zero(), -Fq::one());
+        let te = (EdwardsAffine::generator() + torsion).into_affine();
+        let sw = loop {
+            if let Some(p) = SWAffine::get_point_from_x_unchecked(Fq::rand(&mut rng), false) {
+                if !p.mul_bigint(Fr::MODULUS).is_zero() {
+                    break p;
+                }
+            }
+        };
+        let mut te_buf = Vec::new();
+        te.serialize_compressed(&mut te_buf).unwrap();
+        let mut sw_buf = Vec::new();
+        sw.serialize_compressed(&mut sw_buf).unwrap();
+
+        let te_check = || te.is_in_correct_subgroup_assuming_on_curve();
+        let te_decode = || EdwardsAffine::deserialize_compressed(&te_buf[..]).is_ok();
+        let sw_check = || SWCurveConfig::is_in_correct_subgroup_assuming_on_curve(&sw);
+        let sw_decode = || SWAffine::deserialize_compressed(&sw_buf[..]).is_ok();
+
+        for fault in FAULTS {
+            for nth in [0, 1, 2] {
+                faults::inject(HookOp::MulProjectiveTe, fault, nth);
+                assert!(!te_check() && !te_decode());
+                faults::inject(HookOp::MulProjectiveSw, fault, nth);
+                assert!(!sw_check() && !sw_decode());
+            }
+        }
+        faults::clear();
+    }
+
+    #[test]
+    fn faulty_msm_is_surfaced() {
+        let mut rng = test_rng();
+        let scalars: Vec<_> = (0..4).map(|_| Fr::rand(&mut rng)).collect();
+        let te = [EdwardsAffine::generator(); 4];
+        let sw = [SWAffine::generator(); 4];
+
+        faults::clear();
+        let te_expected = EdwardsProjective::msm(&te, &scalars).unwrap();
+        let sw_expected = SWProjective::msm(&sw, &scalars).unwrap();
+
+        for fault in FAULTS {
+            faults::inject(HookOp::MsmTe, fault, 0);
+            let te_res = EdwardsProjective::msm(&te, &scalars);
+            faults::inject(HookOp::MsmSw, fault, 0);
+            let sw_res = SWProjective::msm(&sw, &scalars);
+            faults::clear();
+            if fault == Fault::OffCurve {
+                // Detected, thus rejected
+                assert_eq!(te_res, Err(te.len()));
+                assert_eq!(sw_res, Err(sw.len()));
+            } else {
+                assert_ne!(te_res, Ok(te_expected), "{fault:?}");
+                assert_ne!(sw_res, Ok(sw_expected), "{fault:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn faulty_normalization_falls_back() {
+        let mut rng = test_rng();
+        let te: Vec<_> = (0..4).map(|_| EdwardsProjective::rand(&mut rng)).collect();
+        let sw: Vec<_> = (0..4).map(|_| SWProjective::rand(&mut rng)).collect();
+
+        faults::clear();
+        let te_expected = EdwardsProjective::normalize_batch(&te);
+        let sw_expected = SWProjective::normalize_batch(&sw);
+
+        for fault in FAULTS {
+            for nth in [0, 2] {
+                faults::inject(HookOp::NormalizeBatchTe, fault, nth);
+                assert_eq!(Config::normalize_batch_te(&te), te_expected, "{fault:?}");
+                faults::inject(HookOp::NormalizeBatchSw, fault, nth);
+                assert_eq!(Config::normalize_batch_sw(&sw), sw_expected, "{fault:?}");
+            }
+        }
+        faults::clear();
+    }
+}
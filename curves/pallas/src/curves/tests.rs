@@ -3,6 +3,7 @@ use crate::CurveHooks;
 use ark_algebra_test_templates::*;
 use ark_models_ext::CurveConfig;
 use ark_pallas::PallasConfig as ArkConfig;
+use ark_std::vec::Vec;
 
 pub struct TestHooks;
 
@@ -18,6 +19,185 @@ impl CurveHooks for TestHooks {
     fn mul_projective(base: &Projective, scalar: &[u64]) -> Projective {
         test_utils::mul_projective_sw_generic::<Config, ArkConfig>(base, scalar)
     }
+
+    fn normalize_batch(points: &[Projective]) -> Vec<Affine> {
+        test_utils::normalize_batch_sw_generic::<Config, ArkConfig>(points)
+    }
 }
 
 test_group!(sw; Projective; sw);
+
+#[test]
+fn differential() {
+    use test_utils::differential as diff;
+
+    diff::group::<Projective, ark_pallas::Projective>(&diff::sw_samples::<ArkConfig>());
+    diff::normalize_batch::<Projective, ark_pallas::Projective>(
+        &diff::sw_samples::<ArkConfig>(),
+        Config::normalize_batch,
+    );
+    diff::msm_small::<Projective, ark_pallas::Projective>(
+        &diff::sw_samples::<ArkConfig>(),
+        Config::msm_small,
+        Config::msm_auto,
+    );
+    diff::multi_msm::<Projective, ark_pallas::Projective>(
+        &diff::sw_samples::<ArkConfig>(),
+        Config::multi_msm,
+    );
+    diff::batch_mul::<Projective, ark_pallas::Projective>(
+        &diff::sw_samples::<ArkConfig>(),
+        Config::batch_mul,
+    );
+}
+
+#[test]
+fn hooks_conformance() {
+    use ark_models_ext::metering::{HookOp, LocalMeter, MeteredHooks};
+
+    type Hooks = MeteredHooks<TestHooks, LocalMeter>;
+
+    test_utils::conformance::sw::<crate::PallasConfig<Hooks>>(
+        HookOp::MsmSw,
+        HookOp::MulProjectiveSw,
+    );
+    test_utils::conformance::normalize_batch(
+        HookOp::NormalizeBatchSw,
+        crate::PallasConfig::<Hooks>::normalize_batch,
+    );
+    test_utils::conformance::msm_small(
+        HookOp::MsmSmallSw,
+        HookOp::MsmSw,
+        crate::PallasConfig::<Hooks>::msm_small,
+        crate::PallasConfig::<Hooks>::msm_auto,
+    );
+    test_utils::conformance::multi_msm(HookOp::MultiMsmSw, crate::PallasConfig::<Hooks>::multi_msm);
+    test_utils::conformance::batch_mul(HookOp::BatchMulSw, crate::PallasConfig::<Hooks>::batch_mul);
+}
+
+#[test]
+fn hook_free_decoding() {
+    use ark_models_ext::metering::{LocalMeter, MeteredHooks};
+    use test_utils::differential as diff;
+
+    type Hooks = MeteredHooks<TestHooks, LocalMeter>;
+
+    test_utils::conformance::decoding::<crate::Projective<Hooks>, ark_pallas::Projective>(
+        &diff::sw_samples::<ArkConfig>(),
+    );
+}
+
+#[test]
+fn no_panic_decoding() {
+    use ark_models_ext::AffineRepr;
+
+    let p = Affine::generator();
+    test_utils::no_panic::deserialize(&[p, Affine::zero()]);
+    test_utils::no_panic::raw(&[p, Affine::zero()]);
+    test_utils::no_panic::deserialize(&[p.into_group(), Projective::default()]);
+    test_utils::no_panic::raw(&[p.into_group(), Projective::default()]);
+}
+
+mod faults {
+    use super::TestHooks;
+    use crate::CurveHooks;
+
+    use ark_models_ext::{
+        cast, checked::HookError, metering::HookOp, AffineRepr, CurveConfig, CurveGroup,
+        VariableBaseMSM,
+    };
+    use ark_std::{test_rng, vec::Vec, UniformRand};
+    use test_utils::faults::{self, Fault, FaultyHooks};
+
+    type Fr = <crate::PallasConfig<FaultyHooks<TestHooks>> as CurveConfig>::ScalarField;
+    type Affine = crate::Affine<FaultyHooks<TestHooks>>;
+    type Projective = crate::Projective<FaultyHooks<TestHooks>>;
+
+    impl<H: CurveHooks> CurveHooks for FaultyHooks<H> {
+        fn msm(
+            bases: &[crate::Affine<Self>],
+            scalars: &[<crate::PallasConfig<Self> as CurveConfig>::ScalarField],
+        ) -> crate::Projective<Self> {
+            let res = H::msm(&cast::sw_affine_slice(bases), scalars);
+            faults::faulty_sw(HookOp::MsmSw, cast::sw_projective(&res))
+        }
+
+        fn mul_projective(
+            base: &crate::Projective<Self>,
+            scalar: &[u64],
+        ) -> crate::Projective<Self> {
+            let res = H::mul_projective(&cast::sw_projective(base), scalar);
+            faults::faulty_sw(HookOp::MulProjectiveSw, cast::sw_projective(&res))
+        }
+
+        fn normalize_batch(points: &[crate::Projective<Self>]) -> Vec<crate::Affine<Self>> {
+            let res = H::normalize_batch(&cast::sw_projective_slice(points));
+            res.iter()
+                .map(|p| {
+                    let p = cast::sw_affine::<_, crate::PallasConfig<Self>>(p);
+                    faults::faulty_sw(HookOp::NormalizeBatchSw, p.into_group()).into_affine()
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn faulty_outputs_are_surfaced() {
+        let mut rng = test_rng();
+        let scalars: Vec<_> = (0..4).map(|_| Fr::rand(&mut rng)).collect();
+        let bases = [Affine::generator(); 4];
+
+        faults::clear();
+        let expected = Projective::msm(&bases, &scalars).unwrap();
+        for fault in [
+            Fault::Identity,
+            Fault::Random,
+            Fault::NonSubgroup,
+            Fault::Fail,
+        ] {
+            faults::inject(HookOp::MsmSw, fault, 0);
+            assert_ne!(Projective::msm(&bases, &scalars), Ok(expected), "{fault:?}");
+        }
+
+        // Detected, thus rejected
+        faults::inject(HookOp::MsmSw, Fault::OffCurve, 0);
+        assert_eq!(Projective::msm(&bases, &scalars), Err(bases.len()));
+        assert_eq!(
+            crate::PallasConfig::<FaultyHooks<TestHooks>>::msm_small(&bases, &[1, 2, 3, 4]),
+            Err(HookError::InvalidOutput)
+        );
+        let base = Projective::from(bases[0]);
+        faults::clear();
+        let expected = base * scalars[0];
+        faults::inject(HookOp::MulProjectiveSw, Fault::OffCurve, 0);
+        assert_eq!(
+            crate::PallasConfig::<FaultyHooks<TestHooks>>::checked_mul(&base, &[7]),
+            Err(HookError::InvalidOutput)
+        );
+        assert_eq!(base * scalars[0], expected);
+        faults::clear();
+    }
+
+    #[test]
+    fn faulty_normalization_falls_back() {
+        let mut rng = test_rng();
+        let points: Vec<_> = (0..4).map(|_| Projective::rand(&mut rng)).collect();
+
+        faults::clear();
+        let expected = Projective::normalize_batch(&points);
+        for fault in [
+            Fault::Identity,
+            Fault::Random,
+            Fault::OffCurve,
+            Fault::NonSubgroup,
+            Fault::Fail,
+        ] {
+            for nth in [0, 2] {
+                faults::inject(HookOp::NormalizeBatchSw, fault, nth);
+                let res = crate::PallasConfig::<FaultyHooks<TestHooks>>::normalize_batch(&points);
+                assert_eq!(res, expected, "{fault:?}");
+            }
+        }
+        faults::clear();
+    }
+}
@@ -3,6 +3,7 @@ use crate::CurveHooks;
 use ark_algebra_test_templates::*;
 use ark_models_ext::CurveConfig;
 use ark_secp256k1::Config as ArkConfig;
+use ark_std::vec::Vec;
 
 pub struct TestHooks;
 
@@ -18,6 +19,87 @@ impl CurveHooks for TestHooks {
     fn mul_projective(base: &Projective, scalar: &[u64]) -> Projective {
         test_utils::mul_projective_sw_generic::<Config, ArkConfig>(base, scalar)
     }
+
+    fn normalize_batch(points: &[Projective]) -> Vec<Affine> {
+        test_utils::normalize_batch_sw_generic::<Config, ArkConfig>(points)
+    }
 }
 
 test_group!(sw; Projective; sw);
+
+#[test]
+fn differential() {
+    use test_utils::differential as diff;
+
+    diff::group::<Projective, ark_secp256k1::Projective>(&diff::sw_samples::<ArkConfig>());
+    diff::normalize_batch::<Projective, ark_secp256k1::Projective>(
+        &diff::sw_samples::<ArkConfig>(),
+        Config::normalize_batch,
+    );
+    diff::msm_small::<Projective, ark_secp256k1::Projective>(
+        &diff::sw_samples::<ArkConfig>(),
+        Config::msm_small,
+        Config::msm_auto,
+    );
+    diff::multi_msm::<Projective, ark_secp256k1::Projective>(
+        &diff::sw_samples::<ArkConfig>(),
+        Config::multi_msm,
+    );
+    diff::batch_mul::<Projective, ark_secp256k1::Projective>(
+        &diff::sw_samples::<ArkConfig>(),
+        Config::batch_mul,
+    );
+}
+
+#[test]
+fn hooks_conformance() {
+    use ark_models_ext::metering::{HookOp, LocalMeter, MeteredHooks};
+
+    type Hooks = MeteredHooks<TestHooks, LocalMeter>;
+
+    test_utils::conformance::sw::<crate::Secp256k1Config<Hooks>>(
+        HookOp::MsmSw,
+        HookOp::MulProjectiveSw,
+    );
+    test_utils::conformance::normalize_batch(
+        HookOp::NormalizeBatchSw,
+        crate::Secp256k1Config::<Hooks>::normalize_batch,
+    );
+    test_utils::conformance::msm_small(
+        HookOp::MsmSmallSw,
+        HookOp::MsmSw,
+        crate::Secp256k1Config::<Hooks>::msm_small,
+        crate::Secp256k1Config::<Hooks>::msm_auto,
+    );
+    test_utils::conformance::multi_msm(
+        HookOp::MultiMsmSw,
+        crate::Secp256k1Config::<Hooks>::multi_msm,
+    );
+    test_utils::conformance::batch_mul(
+        HookOp::BatchMulSw,
+        crate::Secp256k1Config::<Hooks>::batch_mul,
+    );
+}
+
+#[test]
+fn hook_free_decoding() {
+    use ark_models_ext::metering::{LocalMeter, MeteredHooks};
+    use test_utils::differential as diff;
+
+    type Hooks = MeteredHooks<TestHooks, LocalMeter>;
+
+    test_utils::conformance::decoding::<crate::Projective<Hooks>, ark_secp256k1::Projective>(
+        &diff::sw_samples::<ArkConfig>(),
+    );
+}
+
+#[test]
+fn no_panic_decoding() {
+    use ark_models_ext::AffineRepr;
+
+    let p = Affine::generator();
+    test_utils::no_panic::deserialize(&[p, Affine::zero()]);
+    test_utils::no_panic::raw(&[p, Affine::zero()]);
+    test_utils::no_panic::deserialize(&[p.into_group(), Projective::default()]);
+    test_utils::no_panic::raw(&[p.into_group(), Projective::default()]);
+}
@@ -1,13 +1,23 @@
 use ark_models_ext::{
+    batch,
+    batch::MsmJob,
+    cast,
+    checked::{self, HookError},
+    limits::HookLimits,
+    metering::{HookOp, HookUsage, Meter, MeteredHooks},
     models::CurveConfig,
+    scalars,
     short_weierstrass::{self, SWCurveConfig},
+    validation, CurveGroup,
 };
-use ark_std::marker::PhantomData;
+use ark_std::{marker::PhantomData, vec::Vec};
 use ark_vesta::{VestaConfig as ArkConfig, G_GENERATOR_X, G_GENERATOR_Y};
 
 #[cfg(test)]
 mod tests;
 
+pub use ark_models_ext::decoding::{check_subgroup_batch, deserialize_on_curve_only};
+
 pub type Affine<H> = short_weierstrass::Affine<VestaConfig<H>>;
 pub type Projective<H> = short_weierstrass::Projective<VestaConfig<H>>;
 
@@ -16,6 +26,13 @@ pub struct VestaConfig<H: CurveHooks>(PhantomData<fn() -> H>);
 
 /// Hooks for *Vesta*.
 pub trait CurveHooks: 'static + Sized {
+    /// Limits enforced on the inputs before dispatching any call.
+    ///
+    /// Oversized requests never reach the hooks. The `checked_*` entry points of the
+    /// config fail with [`HookError::Limit`], while through the upstream traits
+    /// `msm` fails and projective multiplication yields the identity.
+    const LIMITS: HookLimits = HookLimits::UNLIMITED;
+
     /// Short Weierstrass multi scalar multiplication.
     fn msm(
         bases: &[Affine<Self>],
@@ -24,6 +41,92 @@ pub trait CurveHooks: 'static + Sized {
 
     /// Short Weierstrass projective multiplication.
     fn mul_projective(base: &Projective<Self>, scalar: &[u64]) -> Projective<Self>;
+
+    /// Short Weierstrass batch normalization.
+    ///
+    /// Defaults to the software batch normalization.
+    fn normalize_batch(points: &[Projective<Self>]) -> Vec<Affine<Self>> {
+        <Projective<Self> as CurveGroup>::normalize_batch(points)
+    }
+
+    /// Short Weierstrass multi scalar multiplication with 128-bit scalars.
+    ///
+    /// Defaults to the `msm` hook.
+    fn msm_small(bases: &[Affine<Self>], scalars: &[u128]) -> Projective<Self> {
+        Self::msm(bases, &scalars::from_small(scalars))
+    }
+
+    /// Short Weierstrass independent multi scalar multiplications.
+    ///
+    /// Defaults to one `msm` hook call per job.
+    fn multi_msm(
+        jobs: &[MsmJob<Affine<Self>, <VestaConfig<Self> as CurveConfig>::ScalarField>],
+    ) -> Vec<Projective<Self>> {
+        jobs.iter()
+            .map(|(bases, scalars)| Self::msm(bases, scalars))
+            .collect()
+    }
+
+    /// Short Weierstrass multiplication of one base by many scalars.
+    ///
+    /// Defaults to one `mul_projective` hook call per scalar, followed by the
+    /// `normalize_batch` hook.
+    fn batch_mul(
+        base: &Projective<Self>,
+        scalars: &[<VestaConfig<Self> as CurveConfig>::ScalarField],
+    ) -> Vec<Affine<Self>> {
+        batch::batch_mul_each(
+            scalars,
+            |s| Self::mul_projective(base, s),
+            Self::normalize_batch,
+        )
+    }
+}
+
+impl<H: CurveHooks, M: Meter> CurveHooks for MeteredHooks<H, M> {
+    const LIMITS: HookLimits = H::LIMITS;
+
+    fn msm(
+        bases: &[Affine<Self>],
+        scalars: &[<VestaConfig<Self> as CurveConfig>::ScalarField],
+    ) -> Projective<Self> {
+        M::record(HookOp::MsmSw, HookUsage::msm(scalars));
+        let res = H::msm(&cast::sw_affine_slice(bases), scalars);
+        cast::sw_projective(&res)
+    }
+
+    fn mul_projective(base: &Projective<Self>, scalar: &[u64]) -> Projective<Self> {
+        M::record(HookOp::MulProjectiveSw, HookUsage::mul(scalar));
+        let res = H::mul_projective(&cast::sw_projective(base), scalar);
+        cast::sw_projective(&res)
+    }
+
+    fn normalize_batch(points: &[Projective<Self>]) -> Vec<Affine<Self>> {
+        M::record(HookOp::NormalizeBatchSw, HookUsage::items(points.len()));
+        cast::sw_normalize_batch(points, H::normalize_batch)
+    }
+
+    fn msm_small(bases: &[Affine<Self>], scalars: &[u128]) -> Projective<Self> {
+        M::record(HookOp::MsmSmallSw, HookUsage::msm_small(scalars));
+        let res = H::msm_small(&cast::sw_affine_slice(bases), scalars);
+        cast::sw_projective(&res)
+    }
+
+    fn multi_msm(
+        jobs: &[MsmJob<Affine<Self>, <VestaConfig<Self> as CurveConfig>::ScalarField>],
+    ) -> Vec<Projective<Self>> {
+        M::record(HookOp::MultiMsmSw, HookUsage::multi_msm(jobs));
+        cast::sw_multi_msm(jobs, H::multi_msm)
+    }
+
+    fn batch_mul(
+        base: &Projective<Self>,
+        scalars: &[<VestaConfig<Self> as CurveConfig>::ScalarField],
+    ) -> Vec<Affine<Self>> {
+        M::record(HookOp::BatchMulSw, HookUsage::batch_mul(scalars));
+        let res = H::batch_mul(&cast::sw_projective(base), scalars);
+        cast::sw_affine_slice(&res)
+    }
 }
 
 impl<H: CurveHooks> CurveConfig for VestaConfig<H> {
@@ -41,17 +144,145 @@ impl<H: CurveHooks> SWCurveConfig for VestaConfig<H> {
     const GENERATOR: Affine<H> = Affine::<H>::new_unchecked(G_GENERATOR_X, G_GENERATOR_Y);
 
     /// Multi scalar multiplication jumping into the user-defined `vesta_msm` hook.
+    ///
+    /// Fails on any `checked_msm` failure, see [`checked::upstream_msm`].
     #[inline(always)]
     fn msm(bases: &[Affine<H>], scalars: &[Self::ScalarField]) -> Result<Projective<H>, usize> {
-        if bases.len() != scalars.len() {
-            return Err(bases.len().min(scalars.len()));
-        }
-        Ok(H::msm(bases, scalars))
+        checked::upstream_msm(bases.len(), Self::checked_msm(bases, scalars))
     }
 
     /// Projective multiplication jumping into the user-defined `vesta_mul_projective` hook.
+    ///
+    /// The identity if the scalar exceeds the hooks limits, see
+    /// [`checked::upstream_mul`].
     #[inline(always)]
     fn mul_projective(base: &Projective<H>, scalar: &[u64]) -> Projective<H> {
-        H::mul_projective(base, scalar)
+        checked::upstream_mul(base, scalar, Self::checked_mul(base, scalar))
+    }
+
+    /// Affine multiplication jumping into the user-defined `vesta_mul_projective` hook.
+    #[inline(always)]
+    fn mul_affine(base: &Affine<H>, scalar: &[u64]) -> Projective<H> {
+        <Self as SWCurveConfig>::mul_projective(&(*base).into(), scalar)
+    }
+
+    /// Cofactor clearing, a no-op as the cofactor is one, thus never reaching the
+    /// hooks.
+    #[inline(always)]
+    fn clear_cofactor(item: &Affine<H>) -> Affine<H> {
+        if Self::cofactor_is_one() {
+            return *item;
+        }
+        <Self as SWCurveConfig>::mul_affine(item, Self::COFACTOR).into()
+    }
+
+    /// Subgroup check, always true as the cofactor is one, thus never reaching the
+    /// hooks.
+    #[inline(always)]
+    fn is_in_correct_subgroup_assuming_on_curve(item: &Affine<H>) -> bool {
+        validation::sw_is_in_subgroup_via_order(item)
+    }
+}
+
+impl<H: CurveHooks> VestaConfig<H> {
+    /// Multi scalar multiplication jumping into the user-defined `msm` hook.
+    ///
+    /// Fails if the lengths differ or exceed the hooks limits, or if the hook
+    /// output is not on the curve.
+    pub fn checked_msm(
+        bases: &[Affine<H>],
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<Projective<H>, HookError> {
+        checked::msm(
+            bases,
+            scalars,
+            &H::LIMITS,
+            H::msm,
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Projective multiplication jumping into the user-defined `mul_projective` hook.
+    ///
+    /// Fails if the scalar exceeds the hooks limits or if the hook output is not
+    /// on the curve.
+    pub fn checked_mul(base: &Projective<H>, scalar: &[u64]) -> Result<Projective<H>, HookError> {
+        checked::mul(
+            scalar,
+            &H::LIMITS,
+            |scalar| H::mul_projective(base, scalar),
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Batch normalization jumping into the user-defined `normalize_batch` hook.
+    ///
+    /// See [`checked::normalize_batch`].
+    pub fn normalize_batch(points: &[Projective<H>]) -> Vec<Affine<H>> {
+        checked::normalize_batch(
+            points,
+            H::normalize_batch,
+            validation::sw_is_normalization_of,
+        )
+    }
+
+    /// Multi scalar multiplication with 128-bit scalars jumping into the
+    /// user-defined `msm_small` hook.
+    ///
+    /// Same checks and errors as `checked_msm`.
+    pub fn msm_small(bases: &[Affine<H>], scalars: &[u128]) -> Result<Projective<H>, HookError> {
+        checked::msm(
+            bases,
+            scalars,
+            &H::LIMITS,
+            H::msm_small,
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Multi scalar multiplication jumping into the user-defined `msm_small` hook
+    /// if all the scalars fit 128 bits, into the `msm` hook otherwise.
+    pub fn msm_auto(
+        bases: &[Affine<H>],
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<Projective<H>, HookError> {
+        checked::msm_auto(
+            scalars,
+            |scalars| Self::msm_small(bases, scalars),
+            |scalars| Self::checked_msm(bases, scalars),
+        )
+    }
+
+    /// Independent multi scalar multiplications jumping into the user-defined
+    /// `multi_msm` hook, with a single call for all the jobs.
+    ///
+    /// Each job is checked as by `checked_msm`, with the limits applied to the
+    /// total number of bases. See [`batch::multi_msm`] for the errors.
+    pub fn multi_msm(
+        jobs: &[MsmJob<Affine<H>, <Self as CurveConfig>::ScalarField>],
+    ) -> Vec<Result<Projective<H>, HookError>> {
+        batch::multi_msm(
+            jobs,
+            &H::LIMITS,
+            H::multi_msm,
+            validation::sw_projective_is_on_curve,
+        )
+    }
+
+    /// Multiplication of one base by many scalars jumping into the user-defined
+    /// `batch_mul` hook, with a single call for all the scalars.
+    ///
+    /// The products are returned in affine form. See [`batch::batch_mul`] for the
+    /// errors.
+    pub fn batch_mul(
+        base: &Projective<H>,
+        scalars: &[<Self as CurveConfig>::ScalarField],
+    ) -> Result<Vec<Affine<H>>, HookError> {
+        batch::batch_mul(
+            scalars,
+            &H::LIMITS,
+            |scalars| H::batch_mul(base, scalars),
+            |p| p.is_on_curve(),
+        )
     }
 }
@@ -0,0 +1,84 @@
+//! Deferred pairing checks.
+//!
+//! [`MillerLoopAccumulator`] collects the pairs of a pairing product as they are
+//! produced, possibly across many functions, and evaluates all of them with a
+//! single multi Miller loop hook call.
+
+use ark_ec::pairing::{MillerLoopOutput, Pairing, PairingOutput};
+use ark_std::{ops::Neg, vec::Vec, Zero};
+use derivative::Derivative;
+
+/// Accumulator of the pairs of a pairing product.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""), Default(bound = ""))]
+pub struct MillerLoopAccumulator<E: Pairing> {
+    g1: Vec<E::G1Prepared>,
+    g2: Vec<E::G2Prepared>,
+}
+
+impl<E: Pairing> MillerLoopAccumulator<E> {
+    /// Construct an empty accumulator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of accumulated pairs.
+    pub fn len(&self) -> usize {
+        self.g1.len()
+    }
+
+    /// Whether no pair has been accumulated.
+    pub fn is_empty(&self) -> bool {
+        self.g1.is_empty()
+    }
+
+    /// Accumulate the pair `(a, b)`.
+    pub fn push(&mut self, a: impl Into<E::G1Prepared>, b: impl Into<E::G2Prepared>) {
+        self.g1.push(a.into());
+        self.g2.push(b.into());
+    }
+
+    /// Accumulate all the `pairs`.
+    pub fn extend<A, B>(&mut self, pairs: impl IntoIterator<Item = (A, B)>)
+    where
+        A: Into<E::G1Prepared>,
+        B: Into<E::G2Prepared>,
+    {
+        for (a, b) in pairs {
+            self.push(a, b);
+        }
+    }
+
+    /// Invert the accumulated product, by negating the G1 element of every pair.
+    ///
+    /// Allows to move the accumulated pairs to the other side of a pairing equation.
+    pub fn negate(&mut self)
+    where
+        E::G1Prepared: Neg<Output = E::G1Prepared>,
+    {
+        self.g1 = self.g1.drain(..).map(Neg::neg).collect();
+    }
+
+    /// Multi Miller loop over the accumulated pairs, with a single hook call.
+    pub fn miller_loop(self) -> MillerLoopOutput<E> {
+        E::multi_miller_loop(self.g1, self.g2)
+    }
+
+    /// Pairing product of the accumulated pairs.
+    ///
+    /// `None` if the final exponentiation fails. The empty product is the identity,
+    /// computed without any hook call.
+    pub fn finalize(self) -> Option<PairingOutput<E>> {
+        if self.is_empty() {
+            return Some(PairingOutput::zero());
+        }
+        E::final_exponentiation(self.miller_loop())
+    }
+
+    /// Whether the pairing product of the accumulated pairs is the identity.
+    ///
+    /// An empty accumulator trivially passes the check, without any hook call.
+    pub fn check(self) -> bool {
+        self.finalize().is_some_and(|out| out.is_zero())
+    }
+}
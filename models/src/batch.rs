@@ -0,0 +1,132 @@
+//! Batched hook calls.
+//!
+//! Helpers shared by the curve configs to run many jobs with a single hook call,
+//! applying to each job the same checks as to a standalone call.
+
+use crate::{checked::HookError, limits::HookLimits};
+use ark_ff::{PrimeField, Zero};
+use ark_std::vec::Vec;
+
+/// Multi scalar multiplication job, as a pair of bases and scalars.
+pub type MsmJob<'a, A, F> = (&'a [A], &'a [F]);
+
+/// Independent multi scalar multiplications via a single `hook` call.
+///
+/// Jobs with mismatching lengths fail with [`HookError::LengthMismatch`] and are
+/// not dispatched, as are jobs which would bring the total number of dispatched
+/// bases over the limits, failing with [`HookError::Limit`]. Dispatched jobs fail
+/// with [`HookError::InvalidOutput`] if their output is not valid, or if the hook
+/// returns the wrong number of outputs.
+pub fn multi_msm<A, F, P>(
+    jobs: &[MsmJob<A, F>],
+    limits: &HookLimits,
+    hook: impl FnOnce(&[MsmJob<A, F>]) -> Vec<P>,
+    is_valid: impl Fn(&P) -> bool,
+) -> Vec<Result<P, HookError>> {
+    let mut res: Vec<Result<P, HookError>> = Vec::with_capacity(jobs.len());
+    let mut dispatched = Vec::new();
+    let mut total = 0_usize;
+    for (i, &(bases, scalars)) in jobs.iter().enumerate() {
+        if bases.len() != scalars.len() {
+            res.push(Err(HookError::LengthMismatch {
+                left: bases.len(),
+                right: scalars.len(),
+            }));
+            continue;
+        }
+        let len = total.saturating_add(bases.len());
+        if let Err(err) = limits.check_msm(len) {
+            res.push(Err(err.into()));
+            continue;
+        }
+        total = len;
+        res.push(Err(HookError::InvalidOutput));
+        dispatched.push(i);
+    }
+    if dispatched.is_empty() {
+        return res;
+    }
+
+    let selected: Vec<_> = dispatched.iter().map(|&i| jobs[i]).collect();
+    let outputs = hook(&selected);
+    if outputs.len() != dispatched.len() {
+        return res;
+    }
+    for (i, output) in dispatched.into_iter().zip(outputs) {
+        if is_valid(&output) {
+            res[i] = Ok(output);
+        }
+    }
+    res
+}
+
+/// Multiplications of one base by many scalars via a single `hook` call.
+///
+/// Fails with [`HookError::Limit`] if the number of scalars or any scalar limbs are
+/// over the limits, neither case being dispatched. Fails with
+/// [`HookError::InvalidOutput`] if any output is not valid, or if the hook returns
+/// the wrong number of outputs.
+pub fn batch_mul<F: PrimeField, A>(
+    scalars: &[F],
+    limits: &HookLimits,
+    hook: impl FnOnce(&[F]) -> Vec<A>,
+    is_valid: impl Fn(&A) -> bool,
+) -> Result<Vec<A>, HookError> {
+    limits.check_msm(scalars.len())?;
+    for s in scalars {
+        limits.check_scalar(s.into_bigint().as_ref())?;
+    }
+    let res = hook(scalars);
+    if res.len() != scalars.len() || !res.iter().all(is_valid) {
+        return Err(HookError::InvalidOutput);
+    }
+    Ok(res)
+}
+
+/// Multiplications of one base by many scalars via one `mul` call per scalar,
+/// followed by a single `normalize` call.
+///
+/// Default implementation of the `batch_mul` hooks.
+pub fn batch_mul_each<F: PrimeField, P, A>(
+    scalars: &[F],
+    mul: impl Fn(&[u64]) -> P,
+    normalize: impl FnOnce(&[P]) -> Vec<A>,
+) -> Vec<A> {
+    let res: Vec<_> = scalars
+        .iter()
+        .map(|s| mul(s.into_bigint().as_ref()))
+        .collect();
+    normalize(&res)
+}
+
+/// Final exponentiations via a single `hook` call.
+///
+/// Zero targets are not dispatched and fail with `None`, as do the targets whose
+/// output is not valid. All the targets fail if the hook returns the wrong number
+/// of outputs.
+pub fn final_exponentiation<F: Zero + Copy>(
+    targets: &[F],
+    hook: impl FnOnce(&[F]) -> Vec<F>,
+    is_valid: impl Fn(&F) -> bool,
+) -> Vec<Option<F>> {
+    let mut res = ark_std::vec![None; targets.len()];
+    let (dispatched, selected): (Vec<usize>, Vec<F>) = targets
+        .iter()
+        .enumerate()
+        .filter(|(_, f)| !f.is_zero())
+        .map(|(i, f)| (i, *f))
+        .unzip();
+    if dispatched.is_empty() {
+        return res;
+    }
+    let outputs = hook(&selected);
+    if outputs.len() != dispatched.len() {
+        return res;
+    }
+    for (i, output) in dispatched.into_iter().zip(outputs) {
+        if is_valid(&output) {
+            res[i] = Some(output);
+        }
+    }
+    res
+}
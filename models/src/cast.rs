@@ -0,0 +1,165 @@
+//! Conversions between points of the same curve parameterized by different configs.
+//!
+//! Curve configs of the extension crates are generic over the hooks type, thus the
+//! same point has a distinct type for each hooks implementation. These helpers
+//! move the coordinates as they are, without any check or normalization.
+
+use crate::{batch::MsmJob, short_weierstrass as sw, twisted_edwards as te};
+use ark_std::vec::Vec;
+
+/// Short Weierstrass affine point conversion.
+#[inline(always)]
+pub fn sw_affine<P, Q>(p: &sw::Affine<P>) -> sw::Affine<Q>
+where
+    P: sw::SWCurveConfig,
+    Q: sw::SWCurveConfig<BaseField = P::BaseField>,
+{
+    sw::Affine {
+        x: p.x,
+        y: p.y,
+        infinity: p.infinity,
+    }
+}
+
+/// Short Weierstrass projective point conversion.
+#[inline(always)]
+pub fn sw_projective<P, Q>(p: &sw::Projective<P>) -> sw::Projective<Q>
+where
+    P: sw::SWCurveConfig,
+    Q: sw::SWCurveConfig<BaseField = P::BaseField>,
+{
+    sw::Projective {
+        x: p.x,
+        y: p.y,
+        z: p.z,
+    }
+}
+
+/// Twisted Edwards affine point conversion.
+#[inline(always)]
+pub fn te_affine<P, Q>(p: &te::Affine<P>) -> te::Affine<Q>
+where
+    P: te::TECurveConfig,
+    Q: te::TECurveConfig<BaseField = P::BaseField>,
+{
+    te::Affine { x: p.x, y: p.y }
+}
+
+/// Twisted Edwards projective point conversion.
+#[inline(always)]
+pub fn te_projective<P, Q>(p: &te::Projective<P>) -> te::Projective<Q>
+where
+    P: te::TECurveConfig,
+    Q: te::TECurveConfig<BaseField = P::BaseField>,
+{
+    te::Projective {
+        x: p.x,
+        y: p.y,
+        t: p.t,
+        z: p.z,
+    }
+}
+
+/// Short Weierstrass affine points slice conversion.
+pub fn sw_affine_slice<P, Q>(points: &[sw::Affine<P>]) -> Vec<sw::Affine<Q>>
+where
+    P: sw::SWCurveConfig,
+    Q: sw::SWCurveConfig<BaseField = P::BaseField>,
+{
+    points.iter().map(sw_affine).collect()
+}
+
+/// Twisted Edwards affine points slice conversion.
+pub fn te_affine_slice<P, Q>(points: &[te::Affine<P>]) -> Vec<te::Affine<Q>>
+where
+    P: te::TECurveConfig,
+    Q: te::TECurveConfig<BaseField = P::BaseField>,
+{
+    points.iter().map(te_affine).collect()
+}
+
+/// Short Weierstrass projective points slice conversion.
+pub fn sw_projective_slice<P, Q>(points: &[sw::Projective<P>]) -> Vec<sw::Projective<Q>>
+where
+    P: sw::SWCurveConfig,
+    Q: sw::SWCurveConfig<BaseField = P::BaseField>,
+{
+    points.iter().map(sw_projective).collect()
+}
+
+/// Twisted Edwards projective points slice conversion.
+pub fn te_projective_slice<P, Q>(points: &[te::Projective<P>]) -> Vec<te::Projective<Q>>
+where
+    P: te::TECurveConfig,
+    Q: te::TECurveConfig<BaseField = P::BaseField>,
+{
+    points.iter().map(te_projective).collect()
+}
+
+/// Short Weierstrass batch normalization via `hook`, taking points of another config.
+pub fn sw_normalize_batch<P, Q>(
+    points: &[sw::Projective<P>],
+    hook: impl FnOnce(&[sw::Projective<Q>]) -> Vec<sw::Affine<Q>>,
+) -> Vec<sw::Affine<P>>
+where
+    P: sw::SWCurveConfig,
+    Q: sw::SWCurveConfig<BaseField = P::BaseField>,
+{
+    sw_affine_slice(&hook(&sw_projective_slice(points)))
+}
+
+/// Twisted Edwards batch normalization via `hook`, taking points of another config.
+pub fn te_normalize_batch<P, Q>(
+    points: &[te::Projective<P>],
+    hook: impl FnOnce(&[te::Projective<Q>]) -> Vec<te::Affine<Q>>,
+) -> Vec<te::Affine<P>>
+where
+    P: te::TECurveConfig,
+    Q: te::TECurveConfig<BaseField = P::BaseField>,
+{
+    te_affine_slice(&hook(&te_projective_slice(points)))
+}
+
+/// Short Weierstrass independent multi scalar multiplications via `hook`, taking points of
+/// another config.
+pub fn sw_multi_msm<P, Q, F>(
+    jobs: &[MsmJob<sw::Affine<P>, F>],
+    hook: impl FnOnce(&[MsmJob<sw::Affine<Q>, F>]) -> Vec<sw::Projective<Q>>,
+) -> Vec<sw::Projective<P>>
+where
+    P: sw::SWCurveConfig,
+    Q: sw::SWCurveConfig<BaseField = P::BaseField>,
+{
+    let bases: Vec<Vec<_>> = jobs
+        .iter()
+        .map(|(bases, _)| sw_affine_slice(bases))
+        .collect();
+    let jobs: Vec<_> = bases
+        .iter()
+        .zip(jobs)
+        .map(|(bases, (_, scalars))| (&bases[..], *scalars))
+        .collect();
+    hook(&jobs).iter().map(sw_projective).collect()
+}
+
+/// Twisted Edwards independent multi scalar multiplications via `hook`, taking points of
+/// another config.
+pub fn te_multi_msm<P, Q, F>(
+    jobs: &[MsmJob<te::Affine<P>, F>],
+    hook: impl FnOnce(&[MsmJob<te::Affine<Q>, F>]) -> Vec<te::Projective<Q>>,
+) -> Vec<te::Projective<P>>
+where
+    P: te::TECurveConfig,
+    Q: te::TECurveConfig<BaseField = P::BaseField>,
+{
+    let bases: Vec<Vec<_>> = jobs
+        .iter()
+        .map(|(bases, _)| te_affine_slice(bases))
+        .collect();
+    let jobs: Vec<_> = bases
+        .iter()
+        .zip(jobs)
+        .map(|(bases, (_, scalars))| (&bases[..], *scalars))
+        .collect();
+    hook(&jobs).iter().map(te_projective).collect()
+}
@@ -0,0 +1,168 @@
+//! Checked hook calls.
+//!
+//! Helpers shared by the curve configs to run a single hook call, checking its
+//! inputs against the hooks limits before the call and its output after. Every
+//! failure is reported as a [`HookError`], for the callers which need to tell
+//! them apart.
+//!
+//! The upstream traits can only partially report these failures: [`upstream_msm`]
+//! maps them to the `msm` error, while [`upstream_mul`] returns the identity on a
+//! limit violation. Neither performs more work in software than the limits allow.
+//! [`normalize_batch`], whose callers have no way to report failures, falls back to
+//! the software normalization.
+
+use crate::scalars;
+use crate::{
+    limits::{HookLimits, LimitError},
+    pairs::PairsError,
+    CurveGroup, PrimeGroup,
+};
+use ark_ff::{BitIteratorBE, PrimeField};
+use ark_std::vec::Vec;
+
+/// Failed hook call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HookError {
+    /// Inputs of different lengths, either bases and scalars or G1 and G2 elements.
+    LengthMismatch { left: usize, right: usize },
+    /// Inputs over the hooks limits. The hook is not called.
+    Limit(LimitError),
+    /// Invalid hook output.
+    InvalidOutput,
+}
+
+impl From<LimitError> for HookError {
+    fn from(err: LimitError) -> Self {
+        HookError::Limit(err)
+    }
+}
+
+impl From<PairsError> for HookError {
+    fn from(err: PairsError) -> Self {
+        match err {
+            PairsError::LengthMismatch { g1, g2 } => HookError::LengthMismatch {
+                left: g1,
+                right: g2,
+            },
+            PairsError::Limit(err) => HookError::Limit(err),
+        }
+    }
+}
+
+impl ark_std::fmt::Display for HookError {
+    fn fmt(&self, f: &mut ark_std::fmt::Formatter<'_>) -> ark_std::fmt::Result {
+        match self {
+            HookError::LengthMismatch { left, right } => {
+                write!(f, "inputs length mismatch ({left} and {right})")
+            }
+            HookError::Limit(err) => err.fmt(f),
+            HookError::InvalidOutput => write!(f, "invalid hook output"),
+        }
+    }
+}
+
+/// Multi scalar multiplication via `hook`.
+///
+/// The hook is not called if the lengths differ or exceed the limits.
+pub fn msm<A, S, P>(
+    bases: &[A],
+    scalars: &[S],
+    limits: &HookLimits,
+    hook: impl FnOnce(&[A], &[S]) -> P,
+    is_valid: impl FnOnce(&P) -> bool,
+) -> Result<P, HookError> {
+    if bases.len() != scalars.len() {
+        return Err(HookError::LengthMismatch {
+            left: bases.len(),
+            right: scalars.len(),
+        });
+    }
+    limits.check_msm(bases.len())?;
+    let res = hook(bases, scalars);
+    if !is_valid(&res) {
+        return Err(HookError::InvalidOutput);
+    }
+    Ok(res)
+}
+
+/// Multiplication by `scalar` via `hook`.
+///
+/// The hook is not called if the scalar exceeds the limits.
+pub fn mul<P>(
+    scalar: &[u64],
+    limits: &HookLimits,
+    hook: impl FnOnce(&[u64]) -> P,
+    is_valid: impl FnOnce(&P) -> bool,
+) -> Result<P, HookError> {
+    limits.check_scalar(scalar)?;
+    let res = hook(scalar);
+    if !is_valid(&res) {
+        return Err(HookError::InvalidOutput);
+    }
+    Ok(res)
+}
+
+/// Multi scalar multiplication via `small` if all the scalars fit 128 bits, via
+/// `full` otherwise.
+pub fn msm_auto<F: PrimeField, P>(
+    scalars: &[F],
+    small: impl FnOnce(&[u128]) -> Result<P, HookError>,
+    full: impl FnOnce(&[F]) -> Result<P, HookError>,
+) -> Result<P, HookError> {
+    match scalars::to_small(scalars) {
+        Some(small_scalars) => small(&small_scalars),
+        None => full(scalars),
+    }
+}
+
+/// Upstream `msm` output for the result `res` of a checked hook call over `len`
+/// bases.
+///
+/// A length mismatch is reported as upstream, via the shortest input length. Any
+/// other failure, i.e. a limit violation or an invalid hook output, is reported via
+/// `len`. The multi scalar multiplication is never performed in software.
+pub fn upstream_msm<G>(len: usize, res: Result<G, HookError>) -> Result<G, usize> {
+    res.map_err(|err| match err {
+        HookError::LengthMismatch { left, right } => left.min(right),
+        HookError::Limit(_) | HookError::InvalidOutput => len,
+    })
+}
+
+/// Upstream multiplication output for the result `res` of a checked hook call.
+///
+/// The upstream multiplication can't fail, thus a scalar exceeding the limits is
+/// rejected by returning the identity, without performing any work. On an invalid
+/// hook output the multiplication, whose scalar is within the limits, is performed
+/// in software via double-and-add, never calling the hooks.
+pub fn upstream_mul<G: PrimeGroup>(base: &G, scalar: &[u64], res: Result<G, HookError>) -> G {
+    match res {
+        Ok(res) => res,
+        Err(HookError::Limit(_)) => G::zero(),
+        Err(_) => {
+            let mut res = G::zero();
+            for bit in BitIteratorBE::without_leading_zeros(scalar) {
+                res.double_in_place();
+                if bit {
+                    res += base;
+                }
+            }
+            res
+        }
+    }
+}
+
+/// Batch normalization via `hook`.
+///
+/// Falls back to the software normalization, never calling the hooks, if the hook
+/// output is not the normalization of `points` according to `is_valid`.
+pub fn normalize_batch<G: CurveGroup>(
+    points: &[G],
+    hook: impl FnOnce(&[G]) -> Vec<G::Affine>,
+    is_valid: impl FnOnce(&[G], &[G::Affine]) -> bool,
+) -> Vec<G::Affine> {
+    let res = hook(points);
+    if !is_valid(points, &res) {
+        return G::normalize_batch(points);
+    }
+    res
+}
@@ -0,0 +1,79 @@
+//! Deserialization which never calls the hooks.
+//!
+//! Deserializing with `Validate::Yes` performs a subgroup check, which for the curve
+//! configs of this crate jumps into the hooks. When the deserialization happens
+//! within a hook (e.g. on the host side of a hook implementation) this re-enters
+//! the hooks, possibly with the same value, forever.
+//!
+//! [`deserialize_on_curve_only`] validates the curve equation only, which requires
+//! no scalar multiplication and thus no hook call. The subgroup check is left to
+//! [`check_subgroup_batch`], to be run once outside of the hooks context.
+//!
+//! Curve configs decompressing points through the [`crate::field_hooks`] must do so
+//! only when validating, so that unchecked deserialization stays hook free.
+
+use crate::{short_weierstrass as sw, twisted_edwards as te, AffineRepr};
+use ark_serialize::{CanonicalDeserialize, Compress, Read, SerializationError, Validate};
+
+/// Affine point whose curve equation can be checked without any hook call.
+pub trait OnCurve: AffineRepr + CanonicalDeserialize {
+    /// Check the curve equation.
+    fn is_on_curve(&self) -> bool;
+
+    /// Check the prime order subgroup membership, assuming the point is on the curve.
+    ///
+    /// May jump into the hooks.
+    fn is_in_subgroup(&self) -> bool;
+}
+
+impl<P: sw::SWCurveConfig> OnCurve for sw::Affine<P> {
+    fn is_on_curve(&self) -> bool {
+        sw::Affine::is_on_curve(self)
+    }
+
+    fn is_in_subgroup(&self) -> bool {
+        self.is_in_correct_subgroup_assuming_on_curve()
+    }
+}
+
+impl<P: te::TECurveConfig> OnCurve for te::Affine<P> {
+    fn is_on_curve(&self) -> bool {
+        te::Affine::is_on_curve(self)
+    }
+
+    fn is_in_subgroup(&self) -> bool {
+        self.is_in_correct_subgroup_assuming_on_curve()
+    }
+}
+
+/// Deserialize a point checking that it lies on the curve, but not that it is in
+/// the prime order subgroup.
+///
+/// Never calls the hooks. The returned point must be checked via
+/// [`check_subgroup_batch`] before being used where subgroup membership matters.
+pub fn deserialize_on_curve_only<A: OnCurve, R: Read>(
+    reader: R,
+    compress: Compress,
+) -> Result<A, SerializationError> {
+    let point = A::deserialize_with_mode(reader, compress, Validate::No)?;
+    if !point.is_on_curve() {
+        return Err(SerializationError::InvalidData);
+    }
+    Ok(point)
+}
+
+/// Check that every point is in the prime order subgroup, assuming the points are
+/// on the curve.
+///
+/// The points are checked one by one. Checking a random linear combination of them
+/// instead is not sound whenever the cofactor has small prime factors, as the
+/// torsion components cancel out with non-negligible probability.
+///
+/// Returns the index of the first point outside the subgroup on failure.
+/// Jumps into the hooks, thus must not be called from within a hook.
+pub fn check_subgroup_batch<A: OnCurve>(points: &[A]) -> Result<(), usize> {
+    match points.iter().position(|point| !point.is_in_subgroup()) {
+        Some(index) => Err(index),
+        None => Ok(()),
+    }
+}
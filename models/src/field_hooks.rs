@@ -0,0 +1,44 @@
+//! Hook for field square roots.
+//!
+//! Square roots dominate point decompression. [`FieldHooks`] allows to delegate
+//! them, one field at a time. The hook defaults to the software implementation,
+//! so only the fields worth a hook need to override it.
+//!
+//! The [`sqrt`] entry point validates the roots returned by the hook, falling back
+//! to the software implementation on mismatch.
+//!
+//! A curve config opts in by implementing [`FieldHooks`] for its fields and by
+//! calling [`sqrt`] in place of the plain field method. Only BLS12-381 does so,
+//! for the decompression of its G1 and G2 points.
+
+use ark_ff::Field;
+
+/// Square root hook of the field `F`.
+pub trait FieldHooks<F: Field>: 'static {
+    /// Square root, either one of the two. `None` if `x` is not a square.
+    fn sqrt(x: &F) -> Option<F> {
+        x.sqrt()
+    }
+}
+
+/// Field hooks performing every operation in software.
+pub struct SoftwareFieldHooks;
+
+impl<F: Field> FieldHooks<F> for SoftwareFieldHooks {}
+
+/// Square root jumping into the `sqrt` hook.
+///
+/// A root is checked by squaring it, falling back to the software square root if
+/// the check fails. A missing root is trusted as is: a wrong `None` can only make
+/// a valid encoding rejected, never accept an invalid one, so it is checked via
+/// the Legendre symbol in debug builds only.
+pub fn sqrt<F: Field, H: FieldHooks<F>>(x: &F) -> Option<F> {
+    match H::sqrt(x) {
+        Some(y) if y.square() == *x => Some(y),
+        Some(_) => x.sqrt(),
+        None => {
+            debug_assert!(x.legendre().is_qnr(), "sqrt hook missed a root");
+            None
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! Target group membership.
+//!
+//! Upstream validates the deserialized pairing outputs via an exponentiation by
+//! the group order in software. [`TargetGroup`] allows the pairing configs to
+//! delegate the membership check to a hook, while [`deserialize`] applies it to
+//! the deserialized pairing outputs.
+//!
+//! `PairingOutput` itself keeps the upstream validation. [`GtOutput`] wraps it to
+//! route `Validate::Yes` through [`TargetGroup::is_in_subgroup`], for the pairing
+//! outputs nested within other deserialized types.
+
+use crate::pairing::{Pairing, PairingOutput};
+use ark_ff::{Field, One, Zero};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+use derivative::Derivative;
+
+/// Pairings with a target group membership check.
+pub trait TargetGroup: Pairing {
+    /// Whether `out` belongs to the target group, i.e. the order `r` subgroup of
+    /// the target field multiplicative group.
+    fn is_in_subgroup(out: &PairingOutput<Self>) -> bool;
+}
+
+/// Target group membership check based on the group order.
+///
+/// Checks `f^r = 1`, never calling the hooks.
+pub fn is_in_subgroup_via_order<E: Pairing>(out: &PairingOutput<E>) -> bool {
+    out.0.pow(E::ScalarField::characteristic()).is_one()
+}
+
+/// Pairing output of the inputs rejected by the hooks.
+///
+/// `Pairing::multi_pairing` has no way to fail, thus it maps the Miller loop
+/// outputs rejected by final exponentiation to the zero of the target field. Not
+/// being invertible, it is outside the target group and absorbs any product, so
+/// pairing checks against it fail.
+pub fn rejected<E: Pairing>() -> PairingOutput<E> {
+    PairingOutput(E::TargetField::zero())
+}
+
+/// Deserialize a pairing output.
+///
+/// With `Validate::Yes` the output is checked via [`TargetGroup::is_in_subgroup`]
+/// in place of the upstream check.
+pub fn deserialize<E: TargetGroup>(
+    reader: impl Read,
+    compress: Compress,
+    validate: Validate,
+) -> Result<PairingOutput<E>, SerializationError> {
+    let out =
+        E::TargetField::deserialize_with_mode(reader, compress, validate).map(PairingOutput)?;
+    if validate == Validate::Yes && !E::is_in_subgroup(&out) {
+        return Err(SerializationError::InvalidData);
+    }
+    Ok(out)
+}
+
+/// Pairing output validated via [`TargetGroup::is_in_subgroup`].
+///
+/// Serialized as the plain pairing output.
+#[derive(Derivative)]
+#[derivative(
+    Copy(bound = ""),
+    Clone(bound = ""),
+    PartialEq(bound = ""),
+    Eq(bound = ""),
+    Debug(bound = "")
+)]
+pub struct GtOutput<E: Pairing>(pub PairingOutput<E>);
+
+impl<E: Pairing> From<PairingOutput<E>> for GtOutput<E> {
+    fn from(out: PairingOutput<E>) -> Self {
+        GtOutput(out)
+    }
+}
+
+impl<E: Pairing> CanonicalSerialize for GtOutput<E> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.0.serialize_with_mode(writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.0.serialized_size(compress)
+    }
+}
+
+impl<E: TargetGroup> Valid for GtOutput<E> {
+    fn check(&self) -> Result<(), SerializationError> {
+        match E::is_in_subgroup(&self.0) {
+            true => Ok(()),
+            false => Err(SerializationError::InvalidData),
+        }
+    }
+}
+
+impl<E: TargetGroup> CanonicalDeserialize for GtOutput<E> {
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        deserialize(reader, compress, validate).map(GtOutput)
+    }
+}
@@ -7,5 +7,20 @@ pub use ark_ec::{
     scalar_mul, scalar_mul::*, twisted_edwards, twisted_edwards::*, AffineRepr, CurveGroup,
     PrimeGroup, VariableBaseMSM,
 };
+pub mod accumulator;
+pub mod batch;
+pub mod cast;
+pub mod checked;
+pub mod decoding;
+pub mod field_hooks;
+pub mod gt;
+pub mod limits;
+pub mod metering;
 pub mod models;
+pub mod pairs;
+pub mod precompile;
+pub mod raw;
+pub mod scalars;
+pub mod torus;
+pub mod validation;
 pub use models::*;
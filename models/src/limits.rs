@@ -0,0 +1,110 @@
+//! Limits on the work requested to the hooks.
+//!
+//! The curve configs check the inputs against the [`HookLimits`] declared by the
+//! hooks implementation before dispatching a call, so that the hooks are never
+//! asked to perform an unbounded amount of work. Violations are reported as
+//! [`crate::checked::HookError::Limit`].
+
+/// Per operation limits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HookLimits {
+    /// Max number of bases (and scalars) of a multi scalar multiplication.
+    pub max_msm_len: usize,
+    /// Max number of pairs of a multi Miller loop.
+    pub max_pairs: usize,
+    /// Max number of significant 64-bit limbs of a multiplication scalar.
+    pub max_scalar_limbs: usize,
+}
+
+impl HookLimits {
+    /// No limits.
+    pub const UNLIMITED: Self = Self::new(usize::MAX, usize::MAX, usize::MAX);
+
+    /// Construct new limits.
+    pub const fn new(max_msm_len: usize, max_pairs: usize, max_scalar_limbs: usize) -> Self {
+        HookLimits {
+            max_msm_len,
+            max_pairs,
+            max_scalar_limbs,
+        }
+    }
+
+    /// Check the length of a multi scalar multiplication.
+    pub fn check_msm(&self, len: usize) -> Result<(), LimitError> {
+        if len > self.max_msm_len {
+            return Err(LimitError::MsmLength {
+                len,
+                max: self.max_msm_len,
+            });
+        }
+        Ok(())
+    }
+
+    /// Check the number of pairs of a multi Miller loop.
+    pub fn check_pairs(&self, len: usize) -> Result<(), LimitError> {
+        if len > self.max_pairs {
+            return Err(LimitError::MillerLoopPairs {
+                len,
+                max: self.max_pairs,
+            });
+        }
+        Ok(())
+    }
+
+    /// Check the size of a multiplication scalar.
+    ///
+    /// Most significant zero limbs are not accounted.
+    pub fn check_scalar(&self, scalar: &[u64]) -> Result<(), LimitError> {
+        let len = scalar
+            .iter()
+            .rposition(|&limb| limb != 0)
+            .map_or(0, |i| i + 1);
+        if len > self.max_scalar_limbs {
+            return Err(LimitError::ScalarLimbs {
+                len,
+                max: self.max_scalar_limbs,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Default for HookLimits {
+    fn default() -> Self {
+        Self::UNLIMITED
+    }
+}
+
+/// Limit violation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LimitError {
+    /// Too many multi scalar multiplication bases.
+    MsmLength { len: usize, max: usize },
+    /// Too many multi Miller loop pairs.
+    MillerLoopPairs { len: usize, max: usize },
+    /// Too many multiplication scalar limbs.
+    ScalarLimbs { len: usize, max: usize },
+}
+
+impl LimitError {
+    /// Max value allowed by the violated limit.
+    pub fn max(&self) -> usize {
+        match *self {
+            LimitError::MsmLength { max, .. }
+            | LimitError::MillerLoopPairs { max, .. }
+            | LimitError::ScalarLimbs { max, .. } => max,
+        }
+    }
+}
+
+impl ark_std::fmt::Display for LimitError {
+    fn fmt(&self, f: &mut ark_std::fmt::Formatter<'_>) -> ark_std::fmt::Result {
+        match self {
+            LimitError::MsmLength { len, max } => write!(f, "msm length {len} exceeds {max}"),
+            LimitError::MillerLoopPairs { len, max } => {
+                write!(f, "miller loop pairs {len} exceed {max}")
+            }
+            LimitError::ScalarLimbs { len, max } => write!(f, "scalar limbs {len} exceed {max}"),
+        }
+    }
+}
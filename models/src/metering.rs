@@ -0,0 +1,360 @@
+//! Hooks instrumentation.
+//!
+//! [`MeteredHooks`] wraps a hooks implementation and reports every call, together
+//! with its input size, to a [`Meter`] before forwarding it to the wrapped hooks.
+//! The `CurveHooks` traits of the curve crates are implemented for this type
+//! whenever the inner type implements them.
+//!
+//! A [`CostTable`] can be used to turn the recorded usage into an abstract cost
+//! (e.g. weight), both for a single call and for a whole [`MeterSnapshot`].
+
+use crate::batch::MsmJob;
+use ark_ff::{BigInteger, PrimeField};
+use ark_std::marker::PhantomData;
+
+/// Hook operations tracked by the meter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HookOp {
+    /// Pairing multi Miller loop.
+    MultiMillerLoop,
+    /// Pairing final exponentiation.
+    FinalExponentiation,
+    /// Multi scalar multiplication on G1.
+    MsmG1,
+    /// Multi scalar multiplication on G2.
+    MsmG2,
+    /// Projective multiplication on G1.
+    MulProjectiveG1,
+    /// Projective multiplication on G2.
+    MulProjectiveG2,
+    /// Twisted Edwards multi scalar multiplication.
+    MsmTe,
+    /// Twisted Edwards projective multiplication.
+    MulProjectiveTe,
+    /// Short Weierstrass multi scalar multiplication.
+    MsmSw,
+    /// Short Weierstrass projective multiplication.
+    MulProjectiveSw,
+    /// Batch normalization on G1.
+    NormalizeBatchG1,
+    /// Batch normalization on G2.
+    NormalizeBatchG2,
+    /// Twisted Edwards batch normalization.
+    NormalizeBatchTe,
+    /// Short Weierstrass batch normalization.
+    NormalizeBatchSw,
+    /// Square root in the base field.
+    FieldSqrtFq,
+    /// Square root in the quadratic extension field.
+    FieldSqrtFq2,
+    /// Multi scalar multiplication on G1 with 128-bit scalars.
+    MsmSmallG1,
+    /// Multi scalar multiplication on G2 with 128-bit scalars.
+    MsmSmallG2,
+    /// Twisted Edwards multi scalar multiplication with 128-bit scalars.
+    MsmSmallTe,
+    /// Short Weierstrass multi scalar multiplication with 128-bit scalars.
+    MsmSmallSw,
+    /// Independent multi scalar multiplications on G1.
+    MultiMsmG1,
+    /// Independent multi scalar multiplications on G2.
+    MultiMsmG2,
+    /// Twisted Edwards independent multi scalar multiplications.
+    MultiMsmTe,
+    /// Short Weierstrass independent multi scalar multiplications.
+    MultiMsmSw,
+    /// Multiplication of one base by many scalars on G1.
+    BatchMulG1,
+    /// Multiplication of one base by many scalars on G2.
+    BatchMulG2,
+    /// Twisted Edwards multiplication of one base by many scalars.
+    BatchMulTe,
+    /// Short Weierstrass multiplication of one base by many scalars.
+    BatchMulSw,
+    /// Pairing final exponentiation of many independent Miller loop outputs.
+    FinalExponentiationBatch,
+    /// Pairing target group membership check.
+    GtIsInSubgroup,
+}
+
+impl HookOp {
+    /// Number of tracked operations.
+    pub const COUNT: usize = Self::ALL.len();
+
+    /// All the tracked operations.
+    pub const ALL: [HookOp; 30] = [
+        HookOp::MultiMillerLoop,
+        HookOp::FinalExponentiation,
+        HookOp::MsmG1,
+        HookOp::MsmG2,
+        HookOp::MulProjectiveG1,
+        HookOp::MulProjectiveG2,
+        HookOp::MsmTe,
+        HookOp::MulProjectiveTe,
+        HookOp::MsmSw,
+        HookOp::MulProjectiveSw,
+        HookOp::NormalizeBatchG1,
+        HookOp::NormalizeBatchG2,
+        HookOp::NormalizeBatchTe,
+        HookOp::NormalizeBatchSw,
+        HookOp::FieldSqrtFq,
+        HookOp::FieldSqrtFq2,
+        HookOp::MsmSmallG1,
+        HookOp::MsmSmallG2,
+        HookOp::MsmSmallTe,
+        HookOp::MsmSmallSw,
+        HookOp::MultiMsmG1,
+        HookOp::MultiMsmG2,
+        HookOp::MultiMsmTe,
+        HookOp::MultiMsmSw,
+        HookOp::BatchMulG1,
+        HookOp::BatchMulG2,
+        HookOp::BatchMulTe,
+        HookOp::BatchMulSw,
+        HookOp::FinalExponentiationBatch,
+        HookOp::GtIsInSubgroup,
+    ];
+
+    #[inline(always)]
+    const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Usage of a single hook call.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HookUsage {
+    /// Number of input items (MSM bases, Miller loop pairs, ...).
+    pub items: u64,
+    /// Sum of the bit lengths of the input scalars.
+    pub scalar_bits: u64,
+}
+
+impl HookUsage {
+    /// Usage of a call taking `items` inputs and no scalars.
+    pub fn items(items: usize) -> Self {
+        HookUsage {
+            items: items as u64,
+            scalar_bits: 0,
+        }
+    }
+
+    /// Usage of a multi scalar multiplication.
+    pub fn msm<F: PrimeField>(scalars: &[F]) -> Self {
+        let scalar_bits = scalars
+            .iter()
+            .map(|s| s.into_bigint().num_bits() as u64)
+            .sum();
+        HookUsage {
+            items: scalars.len() as u64,
+            scalar_bits,
+        }
+    }
+
+    /// Usage of many independent multi scalar multiplications.
+    pub fn multi_msm<A, F: PrimeField>(jobs: &[MsmJob<A, F>]) -> Self {
+        jobs.iter().map(|(_, scalars)| Self::msm(scalars)).fold(
+            HookUsage::default(),
+            |acc, usage| HookUsage {
+                items: acc.items.saturating_add(usage.items),
+                scalar_bits: acc.scalar_bits.saturating_add(usage.scalar_bits),
+            },
+        )
+    }
+
+    /// Usage of a multiplication of one base by many scalars.
+    pub fn batch_mul<F: PrimeField>(scalars: &[F]) -> Self {
+        Self::msm(scalars)
+    }
+
+    /// Usage of a multi scalar multiplication with 128-bit scalars.
+    pub fn msm_small(scalars: &[u128]) -> Self {
+        let scalar_bits = scalars.iter().map(|s| 128 - s.leading_zeros() as u64).sum();
+        HookUsage {
+            items: scalars.len() as u64,
+            scalar_bits,
+        }
+    }
+
+    /// Usage of a single scalar multiplication.
+    ///
+    /// The scalar is given as little-endian limbs.
+    pub fn mul(scalar: &[u64]) -> Self {
+        let scalar_bits = scalar
+            .iter()
+            .rposition(|&limb| limb != 0)
+            .map(|i| 64 * i as u64 + 64 - scalar[i].leading_zeros() as u64)
+            .unwrap_or_default();
+        HookUsage {
+            items: 1,
+            scalar_bits,
+        }
+    }
+}
+
+/// Accumulated usage of one operation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OpStats {
+    /// Number of calls.
+    pub calls: u64,
+    /// Total number of input items.
+    pub items: u64,
+    /// Total number of input scalar bits.
+    pub scalar_bits: u64,
+}
+
+/// Accumulated usage of all the operations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MeterSnapshot([OpStats; HookOp::COUNT]);
+
+impl Default for MeterSnapshot {
+    fn default() -> Self {
+        MeterSnapshot([OpStats::default(); HookOp::COUNT])
+    }
+}
+
+impl MeterSnapshot {
+    /// Stats of a single operation.
+    pub fn get(&self, op: HookOp) -> OpStats {
+        self.0[op.index()]
+    }
+
+    /// Account for a new call.
+    pub fn record(&mut self, op: HookOp, usage: HookUsage) {
+        let stats = &mut self.0[op.index()];
+        stats.calls = stats.calls.saturating_add(1);
+        stats.items = stats.items.saturating_add(usage.items);
+        stats.scalar_bits = stats.scalar_bits.saturating_add(usage.scalar_bits);
+    }
+
+    /// Total number of calls, over all the operations.
+    pub fn calls(&self) -> u64 {
+        self.0.iter().map(|s| s.calls).sum()
+    }
+
+    /// Cost of all the recorded calls according to the given table.
+    pub fn cost(&self, table: &CostTable) -> u64 {
+        HookOp::ALL.iter().fold(0u64, |acc, &op| {
+            let stats = self.get(op);
+            let cost = table.get(op);
+            acc.saturating_add(cost.eval(stats.calls, stats.items, stats.scalar_bits))
+        })
+    }
+
+    /// Iterator over the operations stats.
+    pub fn iter(&self) -> impl Iterator<Item = (HookOp, OpStats)> + '_ {
+        HookOp::ALL.iter().map(|&op| (op, self.get(op)))
+    }
+}
+
+/// Linear cost function of a single operation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OpCost {
+    /// Fixed cost paid by every call.
+    pub base: u64,
+    /// Cost of each input item.
+    pub per_item: u64,
+    /// Cost of each input scalar bit.
+    pub per_scalar_bit: u64,
+}
+
+impl OpCost {
+    /// Construct a new cost function.
+    pub const fn new(base: u64, per_item: u64, per_scalar_bit: u64) -> Self {
+        OpCost {
+            base,
+            per_item,
+            per_scalar_bit,
+        }
+    }
+
+    fn eval(&self, calls: u64, items: u64, scalar_bits: u64) -> u64 {
+        self.base
+            .saturating_mul(calls)
+            .saturating_add(self.per_item.saturating_mul(items))
+            .saturating_add(self.per_scalar_bit.saturating_mul(scalar_bits))
+    }
+}
+
+/// Per operation cost functions.
+///
+/// Operations without an explicit entry are free.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CostTable([OpCost; HookOp::COUNT]);
+
+impl Default for CostTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CostTable {
+    /// Table where every operation is free.
+    pub const fn new() -> Self {
+        CostTable([OpCost::new(0, 0, 0); HookOp::COUNT])
+    }
+
+    /// Set the cost function of `op`.
+    pub const fn with(mut self, op: HookOp, cost: OpCost) -> Self {
+        self.0[op.index()] = cost;
+        self
+    }
+
+    /// Cost function of `op`.
+    pub fn get(&self, op: HookOp) -> OpCost {
+        self.0[op.index()]
+    }
+
+    /// Cost of a single call.
+    pub fn cost(&self, op: HookOp, usage: &HookUsage) -> u64 {
+        self.get(op).eval(1, usage.items, usage.scalar_bits)
+    }
+}
+
+/// Sink of the hooks usage.
+pub trait Meter: 'static {
+    /// Record a call to `op`.
+    ///
+    /// Invoked before the call is dispatched to the wrapped hooks, thus an
+    /// implementation can charge the work before it is performed. The call can't
+    /// be refused from here: the work is bounded by the hooks limits (see
+    /// [`crate::limits`]), which are checked before reaching the meter.
+    fn record(op: HookOp, usage: HookUsage);
+}
+
+/// Hooks wrapper reporting every call to the meter `M` and then forwarding it to `H`.
+pub struct MeteredHooks<H, M>(PhantomData<fn() -> (H, M)>);
+
+#[cfg(feature = "std")]
+pub use local::LocalMeter;
+
+#[cfg(feature = "std")]
+mod local {
+    use super::*;
+    use std::cell::RefCell;
+
+    std::thread_local! {
+        static SNAPSHOT: RefCell<MeterSnapshot> = RefCell::new(MeterSnapshot::default());
+    }
+
+    /// Meter accumulating the usage in a thread local [`MeterSnapshot`].
+    pub struct LocalMeter;
+
+    impl LocalMeter {
+        /// Usage recorded by the current thread.
+        pub fn snapshot() -> MeterSnapshot {
+            SNAPSHOT.with(|s| *s.borrow())
+        }
+
+        /// Clear the usage recorded by the current thread.
+        pub fn reset() {
+            SNAPSHOT.with(|s| *s.borrow_mut() = MeterSnapshot::default());
+        }
+    }
+
+    impl Meter for LocalMeter {
+        fn record(op: HookOp, usage: HookUsage) {
+            SNAPSHOT.with(|s| s.borrow_mut().record(op, usage));
+        }
+    }
+}
@@ -4,7 +4,7 @@ use crate::models::{
 };
 use ark_ec::{AffineRepr, CurveGroup};
 use ark_serialize::*;
-use ark_std::vec::Vec;
+use ark_std::{ops::Neg, vec::Vec};
 use derivative::Derivative;
 
 pub type G1Affine<P> = Affine<<P as Bls12Config>::G1Config>;
@@ -50,6 +50,14 @@ impl<P: Bls12Config> G1Prepared<P> {
     }
 }
 
+impl<P: Bls12Config> Neg for G1Prepared<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        G1Prepared(-self.0)
+    }
+}
+
 impl<P: Bls12Config> Default for G1Prepared<P> {
     fn default() -> Self {
         G1Prepared(G1Affine::<P>::generator())
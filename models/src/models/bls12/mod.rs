@@ -12,10 +12,13 @@ use ark_ff::{
     },
     PrimeField,
 };
-use ark_std::marker::PhantomData;
+use ark_std::{marker::PhantomData, vec::Vec};
 use derivative::Derivative;
 
-use crate::models::short_weierstrass::SWCurveConfig;
+use crate::{
+    gt::{self, TargetGroup},
+    models::short_weierstrass::SWCurveConfig,
+};
 
 pub trait Bls12Config: 'static + Sized {
     /// Parameterizes the BLS12 family.
@@ -42,6 +45,22 @@ pub trait Bls12Config: 'static + Sized {
 
     fn final_exponentiation(f: MillerLoopOutput<Bls12<Self>>)
         -> Option<PairingOutput<Bls12<Self>>>;
+
+    /// Final exponentiation of many independent Miller loop outputs.
+    ///
+    /// Defaults to one `final_exponentiation` per output.
+    fn final_exponentiation_batch(
+        f: &[MillerLoopOutput<Bls12<Self>>],
+    ) -> Vec<Option<PairingOutput<Bls12<Self>>>> {
+        f.iter().map(|f| Self::final_exponentiation(*f)).collect()
+    }
+
+    /// Target group membership check.
+    ///
+    /// Defaults to the check based on the group order.
+    fn gt_is_in_subgroup(out: &PairingOutput<Bls12<Self>>) -> bool {
+        gt::is_in_subgroup_via_order(out)
+    }
 }
 
 pub mod g1;
@@ -77,4 +96,29 @@ impl<P: Bls12Config> Pairing for Bls12<P> {
     fn final_exponentiation(f: MillerLoopOutput<Self>) -> Option<PairingOutput<Self>> {
         P::final_exponentiation(f)
     }
+
+    /// Unlike the upstream default, yields [`gt::rejected`] rather than panicking
+    /// if `final_exponentiation` rejects the Miller loop output.
+    fn multi_pairing(
+        a: impl IntoIterator<Item = impl Into<Self::G1Prepared>>,
+        b: impl IntoIterator<Item = impl Into<Self::G2Prepared>>,
+    ) -> PairingOutput<Self> {
+        Self::final_exponentiation(Self::multi_miller_loop(a, b)).unwrap_or_else(gt::rejected)
+    }
+}
+
+impl<P: Bls12Config> Bls12<P> {
+    /// Final exponentiation of many independent Miller loop outputs, e.g. one per
+    /// pairing equation.
+    pub fn final_exponentiation_batch(
+        f: &[MillerLoopOutput<Self>],
+    ) -> Vec<Option<PairingOutput<Self>>> {
+        P::final_exponentiation_batch(f)
+    }
+}
+
+impl<P: Bls12Config> TargetGroup for Bls12<P> {
+    fn is_in_subgroup(out: &PairingOutput<Self>) -> bool {
+        P::gt_is_in_subgroup(out)
+    }
 }
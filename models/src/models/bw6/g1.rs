@@ -4,7 +4,7 @@ use crate::{
     AffineRepr, CurveGroup,
 };
 use ark_serialize::*;
-use ark_std::vec::Vec;
+use ark_std::{ops::Neg, vec::Vec};
 use derivative::Derivative;
 
 pub type G1Affine<P> = Affine<<P as BW6Config>::G1Config>;
@@ -50,6 +50,14 @@ impl<P: BW6Config> G1Prepared<P> {
     }
 }
 
+impl<P: BW6Config> Neg for G1Prepared<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        G1Prepared(-self.0)
+    }
+}
+
 impl<P: BW6Config> Default for G1Prepared<P> {
     fn default() -> Self {
         G1Prepared(G1Affine::<P>::generator())
@@ -1,4 +1,5 @@
 use crate::{
+    gt::{self, TargetGroup},
     models::{short_weierstrass::SWCurveConfig, CurveConfig},
     pairing::{MillerLoopOutput, Pairing, PairingOutput},
 };
@@ -9,7 +10,7 @@ use ark_ff::fields::{
     fp6_2over3::{Fp6, Fp6Config},
     PrimeField,
 };
-use ark_std::marker::PhantomData;
+use ark_std::{marker::PhantomData, vec::Vec};
 use derivative::Derivative;
 
 pub trait BW6Config: 'static + Sized {
@@ -31,10 +32,26 @@ pub trait BW6Config: 'static + Sized {
 
     fn final_exponentiation(f: MillerLoopOutput<BW6<Self>>) -> Option<PairingOutput<BW6<Self>>>;
 
+    /// Final exponentiation of many independent Miller loop outputs.
+    ///
+    /// Defaults to one `final_exponentiation` per output.
+    fn final_exponentiation_batch(
+        f: &[MillerLoopOutput<BW6<Self>>],
+    ) -> Vec<Option<PairingOutput<BW6<Self>>>> {
+        f.iter().map(|f| Self::final_exponentiation(*f)).collect()
+    }
+
     fn multi_miller_loop(
         a: impl IntoIterator<Item = impl Into<G1Prepared<Self>>>,
         b: impl IntoIterator<Item = impl Into<G2Prepared<Self>>>,
     ) -> MillerLoopOutput<BW6<Self>>;
+
+    /// Target group membership check.
+    ///
+    /// Defaults to the check based on the group order.
+    fn gt_is_in_subgroup(out: &PairingOutput<BW6<Self>>) -> bool {
+        gt::is_in_subgroup_via_order(out)
+    }
 }
 
 pub mod g1;
@@ -70,4 +87,29 @@ impl<P: BW6Config> Pairing for BW6<P> {
     ) -> MillerLoopOutput<Self> {
         P::multi_miller_loop(a, b)
     }
+
+    /// Unlike the upstream default, yields [`gt::rejected`] rather than panicking
+    /// if `final_exponentiation` rejects the Miller loop output.
+    fn multi_pairing(
+        a: impl IntoIterator<Item = impl Into<Self::G1Prepared>>,
+        b: impl IntoIterator<Item = impl Into<Self::G2Prepared>>,
+    ) -> PairingOutput<Self> {
+        Self::final_exponentiation(Self::multi_miller_loop(a, b)).unwrap_or_else(gt::rejected)
+    }
+}
+
+impl<P: BW6Config> BW6<P> {
+    /// Final exponentiation of many independent Miller loop outputs, e.g. one per
+    /// pairing equation.
+    pub fn final_exponentiation_batch(
+        f: &[MillerLoopOutput<Self>],
+    ) -> Vec<Option<PairingOutput<Self>>> {
+        P::final_exponentiation_batch(f)
+    }
+}
+
+impl<P: BW6Config> TargetGroup for BW6<P> {
+    fn is_in_subgroup(out: &PairingOutput<Self>) -> bool {
+        P::gt_is_in_subgroup(out)
+    }
 }
@@ -0,0 +1,62 @@
+//! Multi Miller loop inputs.
+//!
+//! The pairing configs hand the multi Miller loop hook two slices of the same
+//! length, free from the pairs with a zero point (whose contribution is one).
+
+use crate::limits::{HookLimits, LimitError};
+use ark_std::vec::Vec;
+
+/// Invalid multi Miller loop inputs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PairsError {
+    /// Different number of G1 and G2 elements.
+    LengthMismatch { g1: usize, g2: usize },
+    /// Too many pairs.
+    Limit(LimitError),
+}
+
+impl From<LimitError> for PairsError {
+    fn from(err: LimitError) -> Self {
+        PairsError::Limit(err)
+    }
+}
+
+impl ark_std::fmt::Display for PairsError {
+    fn fmt(&self, f: &mut ark_std::fmt::Formatter<'_>) -> ark_std::fmt::Result {
+        match self {
+            PairsError::LengthMismatch { g1, g2 } => {
+                write!(f, "{g1} G1 elements paired with {g2} G2 elements")
+            }
+            PairsError::Limit(err) => err.fmt(f),
+        }
+    }
+}
+
+/// Collect the multi Miller loop inputs, dropping the pairs where `is_zero`.
+///
+/// The number of pairs is checked against the limits before dropping any pair,
+/// and at most `max_pairs + 1` elements are collected from each side. For
+/// oversized inputs the lengths reported by [`PairsError::LengthMismatch`] may
+/// thus be truncated.
+pub fn collect<A, B>(
+    g1: impl IntoIterator<Item = A>,
+    g2: impl IntoIterator<Item = B>,
+    limits: &HookLimits,
+    is_zero: impl Fn(&A, &B) -> bool,
+) -> Result<(Vec<A>, Vec<B>), PairsError> {
+    let max = limits.max_pairs.saturating_add(1);
+    let g1: Vec<A> = g1.into_iter().take(max).collect();
+    let g2: Vec<B> = g2.into_iter().take(max).collect();
+    if g1.len() != g2.len() {
+        return Err(PairsError::LengthMismatch {
+            g1: g1.len(),
+            g2: g2.len(),
+        });
+    }
+    limits.check_pairs(g1.len())?;
+    Ok(g1
+        .into_iter()
+        .zip(g2)
+        .filter(|(a, b)| !is_zero(a, b))
+        .unzip())
+}
@@ -0,0 +1,137 @@
+//! Ethereum precompiles input parsing.
+//!
+//! Helpers shared by the precompiles of the pairing friendly curves (EIP-2537,
+//! EIP-2539 and EIP-3026), which only differ by the encoded elements sizes.
+//!
+//! A base field element is encoded as big endian bytes, zero padded on top, and
+//! must be canonical. A point is encoded as `x || y`, the point at infinity being
+//! all zeros. A scalar is encoded as big endian bytes and is not required to be
+//! less than the group order.
+
+use crate::short_weierstrass::{Affine, SWCurveConfig};
+use ark_ff::{BigInteger, PrimeField};
+use ark_std::{vec::Vec, Zero};
+
+/// Precompile failure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Empty input, or input length not matching the operation.
+    InputLength,
+    /// Field element with non-zero top bytes.
+    FieldPadding,
+    /// Field element not less than the modulus.
+    FieldEncoding,
+    /// Point which is neither on the curve nor the point at infinity.
+    NotOnCurve,
+    /// Point outside of the prime order subgroup.
+    NotInSubgroup,
+    /// The hooks limits were exceeded, or the hooks output was rejected.
+    Hook,
+}
+
+impl ark_std::fmt::Display for Error {
+    fn fmt(&self, f: &mut ark_std::fmt::Formatter<'_>) -> ark_std::fmt::Result {
+        let msg = match self {
+            Error::InputLength => "invalid input length",
+            Error::FieldPadding => "invalid field element top bytes",
+            Error::FieldEncoding => "invalid field element encoding",
+            Error::NotOnCurve => "point not on curve",
+            Error::NotInSubgroup => "point not in the correct subgroup",
+            Error::Hook => "hook failure",
+        };
+        f.write_str(msg)
+    }
+}
+
+/// Decode a prime field element from `N` bytes.
+///
+/// The bytes on top of the field element limbs must be zero.
+pub fn decode_fp<F: PrimeField, const N: usize>(bytes: &[u8; N]) -> Result<F, Error> {
+    let mut limbs = F::BigInt::default();
+    let len = limbs.as_ref().len() * 8;
+    let (padding, be) = bytes.split_at(N.saturating_sub(len));
+    if padding.iter().any(|b| *b != 0) {
+        return Err(Error::FieldPadding);
+    }
+    for (limb, chunk) in limbs.as_mut().iter_mut().zip(be.rchunks_exact(8)) {
+        *limb = u64::from_be_bytes(chunk.try_into().expect("chunks of 8 bytes; qed"));
+    }
+    F::from_bigint(limbs).ok_or(Error::FieldEncoding)
+}
+
+/// Encode a prime field element as `N` bytes.
+pub fn encode_fp<F: PrimeField, const N: usize>(fp: &F) -> [u8; N] {
+    let be = fp.into_bigint().to_bytes_be();
+    let mut res = [0; N];
+    res[N - be.len()..].copy_from_slice(&be);
+    res
+}
+
+/// Point with coordinates `(x, y)`, checking the subgroup membership if
+/// `subgroup_check`.
+///
+/// The point at infinity is encoded as `(0, 0)`, which is never on the curve.
+pub fn point<P: SWCurveConfig>(
+    x: P::BaseField,
+    y: P::BaseField,
+    subgroup_check: bool,
+) -> Result<Affine<P>, Error> {
+    if x.is_zero() && y.is_zero() {
+        return Ok(Affine::identity());
+    }
+    let p = Affine::new_unchecked(x, y);
+    if !p.is_on_curve() {
+        return Err(Error::NotOnCurve);
+    }
+    if subgroup_check && !p.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(Error::NotInSubgroup);
+    }
+    Ok(p)
+}
+
+/// Decode the non-empty sequence of `(point, scalar)` pairs of an MSM input, the
+/// points being `N` bytes and the scalars `S` bytes long.
+pub fn msm_input<const N: usize, const S: usize, A, F: PrimeField>(
+    input: &[u8],
+    decode: impl Fn(&[u8; N]) -> Result<A, Error>,
+) -> Result<(Vec<A>, Vec<F>), Error> {
+    pairs::<N, S, _, _>(input, decode, |scalar: &[u8; S]| {
+        Ok(F::from_be_bytes_mod_order(scalar))
+    })
+}
+
+/// Decode the non-empty sequence of `(a, b)` pairs of an input, `a` being `A` bytes
+/// and `b` being `B` bytes long.
+pub fn pairs<const A: usize, const B: usize, L, R>(
+    input: &[u8],
+    decode_a: impl Fn(&[u8; A]) -> Result<L, Error>,
+    decode_b: impl Fn(&[u8; B]) -> Result<R, Error>,
+) -> Result<(Vec<L>, Vec<R>), Error> {
+    let chunks = input.chunks_exact(A + B);
+    if input.is_empty() || !chunks.remainder().is_empty() {
+        return Err(Error::InputLength);
+    }
+    let mut left = Vec::with_capacity(chunks.len());
+    let mut right = Vec::with_capacity(chunks.len());
+    for pair in chunks {
+        let (a, b) = split::<A, B>(pair);
+        left.push(decode_a(a)?);
+        right.push(decode_b(b)?);
+    }
+    Ok((left, right))
+}
+
+/// Input of exactly `N` bytes.
+pub fn exact<const N: usize>(input: &[u8]) -> Result<&[u8; N], Error> {
+    input.try_into().map_err(|_| Error::InputLength)
+}
+
+/// Split `A + B` bytes.
+///
+/// Panics if `bytes` is not `A + B` bytes long.
+pub fn split<const A: usize, const B: usize>(bytes: &[u8]) -> (&[u8; A], &[u8; B]) {
+    let (a, b) = bytes.split_at(A);
+    let a = a.try_into().expect("caller provides A + B bytes; qed");
+    let b = b.try_into().expect("caller provides A + B bytes; qed");
+    (a, b)
+}
@@ -0,0 +1,400 @@
+//! Raw limbs encoding of the hook payloads.
+//!
+//! **Unstable**: the encoding copies the internal representation of the field
+//! elements, i.e. their Montgomery form limbs, thus it is only meant to move
+//! values between two sides (e.g. wasm runtime and host) built against the very
+//! same *Arkworks* version. In exchange the conversions out of and back into the
+//! Montgomery form performed by the canonical serialization are skipped.
+//!
+//! Every payload starts with a header pinning the encoding version, the kind of
+//! value and two identifiers of the curve and of the field it is defined over,
+//! so that values of a different curve or field are rejected instead of being
+//! silently reinterpreted. The identifiers only depend on the curve constants,
+//! thus the same curve parameterized by different hooks shares them. The limbs
+//! are written as little endian 64 bit words.
+//!
+//! Decoding checks that every limb sequence is a canonical Montgomery form, but
+//! performs no curve or subgroup check, like `Validate::No`.
+
+use crate::{short_weierstrass as sw, twisted_edwards as te};
+use ark_ff::{
+    BigInt, CubicExtConfig, CubicExtField, Field, Fp, FpConfig, QuadExtConfig, QuadExtField,
+};
+use ark_std::{marker::PhantomData, vec, vec::Vec};
+
+/// Encoding magic, including the version.
+pub const MAGIC: [u8; 4] = *b"ARL0";
+
+/// Header length in bytes.
+pub const HEADER_LEN: usize = MAGIC.len() + 1 + 2 * 8;
+
+/// Flag marking the kind of a slice of values.
+const SLICE: u8 = 0x80;
+
+/// Kind of the encoded value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Kind {
+    /// Field element.
+    Field = 0,
+    /// Short Weierstrass affine point.
+    SWAffine = 1,
+    /// Short Weierstrass projective point.
+    SWProjective = 2,
+    /// Twisted Edwards affine point.
+    TEAffine = 3,
+    /// Twisted Edwards projective point.
+    TEProjective = 4,
+}
+
+/// Invalid raw encoding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RawError {
+    /// Unknown version, or different kind, curve or field.
+    Header,
+    /// Truncated payload or trailing bytes.
+    Length,
+    /// Limbs which are not a canonical field element, or invalid flag.
+    Value,
+}
+
+impl ark_std::fmt::Display for RawError {
+    fn fmt(&self, f: &mut ark_std::fmt::Formatter<'_>) -> ark_std::fmt::Result {
+        match self {
+            RawError::Header => write!(f, "raw encoding header mismatch"),
+            RawError::Length => write!(f, "raw encoding length mismatch"),
+            RawError::Value => write!(f, "invalid raw encoded value"),
+        }
+    }
+}
+
+/// Fields with a raw limbs representation.
+pub trait RawField: Field {
+    /// Number of 64 bit limbs.
+    const LIMBS: usize;
+
+    /// Append the limbs to `out`.
+    fn write_limbs(&self, out: &mut Vec<u8>);
+
+    /// Read the limbs from the front of `bytes`.
+    fn read_limbs(bytes: &mut &[u8]) -> Result<Self, RawError>;
+}
+
+impl<P: FpConfig<N>, const N: usize> RawField for Fp<P, N> {
+    const LIMBS: usize = N;
+
+    fn write_limbs(&self, out: &mut Vec<u8>) {
+        self.0 .0.iter().for_each(|l| out.extend(l.to_le_bytes()));
+    }
+
+    fn read_limbs(bytes: &mut &[u8]) -> Result<Self, RawError> {
+        let mut limbs = [0; N];
+        for limb in limbs.iter_mut() {
+            *limb = u64::from_le_bytes(take(bytes)?);
+        }
+        let limbs = BigInt(limbs);
+        if limbs >= P::MODULUS {
+            return Err(RawError::Value);
+        }
+        Ok(Fp(limbs, PhantomData))
+    }
+}
+
+impl<P: QuadExtConfig> RawField for QuadExtField<P>
+where
+    P::BaseField: RawField,
+{
+    const LIMBS: usize = 2 * P::BaseField::LIMBS;
+
+    fn write_limbs(&self, out: &mut Vec<u8>) {
+        self.c0.write_limbs(out);
+        self.c1.write_limbs(out);
+    }
+
+    fn read_limbs(bytes: &mut &[u8]) -> Result<Self, RawError> {
+        let c0 = P::BaseField::read_limbs(bytes)?;
+        let c1 = P::BaseField::read_limbs(bytes)?;
+        Ok(QuadExtField::new(c0, c1))
+    }
+}
+
+impl<P: CubicExtConfig> RawField for CubicExtField<P>
+where
+    P::BaseField: RawField,
+{
+    const LIMBS: usize = 3 * P::BaseField::LIMBS;
+
+    fn write_limbs(&self, out: &mut Vec<u8>) {
+        self.c0.write_limbs(out);
+        self.c1.write_limbs(out);
+        self.c2.write_limbs(out);
+    }
+
+    fn read_limbs(bytes: &mut &[u8]) -> Result<Self, RawError> {
+        let c0 = P::BaseField::read_limbs(bytes)?;
+        let c1 = P::BaseField::read_limbs(bytes)?;
+        let c2 = P::BaseField::read_limbs(bytes)?;
+        Ok(CubicExtField::new(c0, c1, c2))
+    }
+}
+
+/// Values with a raw limbs encoding.
+pub trait Raw: Sized {
+    /// Kind of the value.
+    const KIND: Kind;
+
+    /// Identifier of the curve, zero for field elements.
+    fn curve_id() -> u64;
+
+    /// Identifier of the field the value is defined over.
+    fn field_id() -> u64;
+
+    /// Append the value to `out`.
+    fn write(&self, out: &mut Vec<u8>);
+
+    /// Read the value from the front of `bytes`.
+    fn read(bytes: &mut &[u8]) -> Result<Self, RawError>;
+}
+
+impl<F: RawField> Raw for F {
+    const KIND: Kind = Kind::Field;
+
+    fn curve_id() -> u64 {
+        0
+    }
+
+    fn field_id() -> u64 {
+        field_id::<F>()
+    }
+
+    fn write(&self, out: &mut Vec<u8>) {
+        self.write_limbs(out)
+    }
+
+    fn read(bytes: &mut &[u8]) -> Result<Self, RawError> {
+        F::read_limbs(bytes)
+    }
+}
+
+impl<P: sw::SWCurveConfig> Raw for sw::Affine<P>
+where
+    P::BaseField: RawField,
+{
+    const KIND: Kind = Kind::SWAffine;
+
+    fn curve_id() -> u64 {
+        sw_curve_id::<P>()
+    }
+
+    fn field_id() -> u64 {
+        field_id::<P::BaseField>()
+    }
+
+    fn write(&self, out: &mut Vec<u8>) {
+        self.x.write_limbs(out);
+        self.y.write_limbs(out);
+        out.push(self.infinity as u8);
+    }
+
+    fn read(bytes: &mut &[u8]) -> Result<Self, RawError> {
+        let x = P::BaseField::read_limbs(bytes)?;
+        let y = P::BaseField::read_limbs(bytes)?;
+        let infinity = match take(bytes)? {
+            [0] => false,
+            [1] => true,
+            _ => return Err(RawError::Value),
+        };
+        Ok(sw::Affine { x, y, infinity })
+    }
+}
+
+impl<P: sw::SWCurveConfig> Raw for sw::Projective<P>
+where
+    P::BaseField: RawField,
+{
+    const KIND: Kind = Kind::SWProjective;
+
+    fn curve_id() -> u64 {
+        sw_curve_id::<P>()
+    }
+
+    fn field_id() -> u64 {
+        field_id::<P::BaseField>()
+    }
+
+    fn write(&self, out: &mut Vec<u8>) {
+        self.x.write_limbs(out);
+        self.y.write_limbs(out);
+        self.z.write_limbs(out);
+    }
+
+    fn read(bytes: &mut &[u8]) -> Result<Self, RawError> {
+        let x = P::BaseField::read_limbs(bytes)?;
+        let y = P::BaseField::read_limbs(bytes)?;
+        let z = P::BaseField::read_limbs(bytes)?;
+        Ok(sw::Projective { x, y, z })
+    }
+}
+
+impl<P: te::TECurveConfig> Raw for te::Affine<P>
+where
+    P::BaseField: RawField,
+{
+    const KIND: Kind = Kind::TEAffine;
+
+    fn curve_id() -> u64 {
+        te_curve_id::<P>()
+    }
+
+    fn field_id() -> u64 {
+        field_id::<P::BaseField>()
+    }
+
+    fn write(&self, out: &mut Vec<u8>) {
+        self.x.write_limbs(out);
+        self.y.write_limbs(out);
+    }
+
+    fn read(bytes: &mut &[u8]) -> Result<Self, RawError> {
+        let x = P::BaseField::read_limbs(bytes)?;
+        let y = P::BaseField::read_limbs(bytes)?;
+        Ok(te::Affine { x, y })
+    }
+}
+
+impl<P: te::TECurveConfig> Raw for te::Projective<P>
+where
+    P::BaseField: RawField,
+{
+    const KIND: Kind = Kind::TEProjective;
+
+    fn curve_id() -> u64 {
+        te_curve_id::<P>()
+    }
+
+    fn field_id() -> u64 {
+        field_id::<P::BaseField>()
+    }
+
+    fn write(&self, out: &mut Vec<u8>) {
+        self.x.write_limbs(out);
+        self.y.write_limbs(out);
+        self.t.write_limbs(out);
+        self.z.write_limbs(out);
+    }
+
+    fn read(bytes: &mut &[u8]) -> Result<Self, RawError> {
+        let x = P::BaseField::read_limbs(bytes)?;
+        let y = P::BaseField::read_limbs(bytes)?;
+        let t = P::BaseField::read_limbs(bytes)?;
+        let z = P::BaseField::read_limbs(bytes)?;
+        Ok(te::Projective { x, y, t, z })
+    }
+}
+
+/// Encode a value.
+pub fn encode<T: Raw>(value: &T) -> Vec<u8> {
+    let mut out = header::<T>(T::KIND as u8);
+    value.write(&mut out);
+    out
+}
+
+/// Decode a value encoded via [`encode`].
+pub fn decode<T: Raw>(mut bytes: &[u8]) -> Result<T, RawError> {
+    check_header::<T>(&mut bytes, T::KIND as u8)?;
+    let value = T::read(&mut bytes)?;
+    finish(bytes, value)
+}
+
+/// Encode a slice of values, sharing the header.
+pub fn encode_slice<T: Raw>(values: &[T]) -> Vec<u8> {
+    let mut out = header::<T>(T::KIND as u8 | SLICE);
+    out.extend((values.len() as u32).to_le_bytes());
+    values.iter().for_each(|value| value.write(&mut out));
+    out
+}
+
+/// Decode a slice of values encoded via [`encode_slice`].
+pub fn decode_slice<T: Raw>(mut bytes: &[u8]) -> Result<Vec<T>, RawError> {
+    check_header::<T>(&mut bytes, T::KIND as u8 | SLICE)?;
+    let len = u32::from_le_bytes(take(&mut bytes)?) as usize;
+    // The length is untrusted, thus the capacity is bounded by the payload size.
+    let mut values = Vec::with_capacity(len.min(bytes.len()));
+    for _ in 0..len {
+        values.push(T::read(&mut bytes)?);
+    }
+    finish(bytes, values)
+}
+
+fn header<T: Raw>(kind: u8) -> Vec<u8> {
+    let mut out = Vec::with_capacity(HEADER_LEN);
+    out.extend(MAGIC);
+    out.push(kind);
+    out.extend(T::curve_id().to_le_bytes());
+    out.extend(T::field_id().to_le_bytes());
+    out
+}
+
+fn check_header<T: Raw>(bytes: &mut &[u8], kind: u8) -> Result<(), RawError> {
+    let expected = header::<T>(kind);
+    let found = bytes.get(..HEADER_LEN).ok_or(RawError::Length)?;
+    if found != expected {
+        return Err(RawError::Header);
+    }
+    *bytes = &bytes[HEADER_LEN..];
+    Ok(())
+}
+
+fn finish<T>(bytes: &[u8], value: T) -> Result<T, RawError> {
+    match bytes.is_empty() {
+        true => Ok(value),
+        false => Err(RawError::Length),
+    }
+}
+
+fn take<const N: usize>(bytes: &mut &[u8]) -> Result<[u8; N], RawError> {
+    if bytes.len() < N {
+        return Err(RawError::Length);
+    }
+    let (head, tail) = bytes.split_at(N);
+    *bytes = tail;
+    Ok(head.try_into().expect("N bytes; qed"))
+}
+
+/// 64 bit FNV-1a hash.
+fn fnv(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    bytes.into_iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Field identifier, over the characteristic and the extension degree.
+fn field_id<F: Field>() -> u64 {
+    let words = F::characteristic().iter().copied();
+    fnv(words
+        .chain([F::extension_degree()])
+        .flat_map(u64::to_le_bytes))
+}
+
+/// Curve identifier, over the kind and the limbs of the given curve constants.
+fn curve_id<F: RawField>(kind: Kind, constants: &[F]) -> u64 {
+    let mut bytes = vec![kind as u8];
+    constants.iter().for_each(|c| c.write_limbs(&mut bytes));
+    fnv(bytes)
+}
+
+fn sw_curve_id<P: sw::SWCurveConfig>() -> u64
+where
+    P::BaseField: RawField,
+{
+    let g = P::GENERATOR;
+    curve_id(Kind::SWAffine, &[P::COEFF_A, P::COEFF_B, g.x, g.y])
+}
+
+fn te_curve_id<P: te::TECurveConfig>() -> u64
+where
+    P::BaseField: RawField,
+{
+    let g = P::GENERATOR;
+    curve_id(Kind::TEAffine, &[P::COEFF_A, P::COEFF_D, g.x, g.y])
+}
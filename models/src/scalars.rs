@@ -0,0 +1,31 @@
+//! Small scalars.
+//!
+//! Batch verification combines its equations with short random coefficients,
+//! for which a full width multi scalar multiplication wastes about half of the
+//! work. These helpers move the scalars between the field and the 128-bit
+//! representations, as taken by the `msm_small` hooks.
+
+use ark_ff::PrimeField;
+use ark_std::vec::Vec;
+
+/// Scalars as 128-bit integers, if all of them fit.
+pub fn to_small<F: PrimeField>(scalars: &[F]) -> Option<Vec<u128>> {
+    scalars
+        .iter()
+        .map(|scalar| {
+            let bigint = scalar.into_bigint();
+            let limbs = bigint.as_ref();
+            if limbs.iter().skip(2).any(|&limb| limb != 0) {
+                return None;
+            }
+            let lo = limbs.first().copied().unwrap_or_default() as u128;
+            let hi = limbs.get(1).copied().unwrap_or_default() as u128;
+            Some(lo | hi << 64)
+        })
+        .collect()
+}
+
+/// Scalars as field elements.
+pub fn from_small<F: PrimeField>(scalars: &[u128]) -> Vec<F> {
+    scalars.iter().map(|&scalar| F::from(scalar)).collect()
+}
@@ -0,0 +1,155 @@
+//! Compressed encoding of the pairing target group elements.
+//!
+//! The pairing target group lies in the cyclotomic subgroup of a quadratic
+//! extension `F[w] / (w^2 - b)` (`Fp12` over `Fp6` for BLS12, `Fp6` over `Fp3`
+//! for BW6), where every element `f = c0 + c1 w` is unitary. Such an element is
+//! uniquely identified by the base field element `g = (1 + c0) / c1`, being
+//! `f = (g + w) / (g - w)`. This is the `T2` torus representation, half the size
+//! of the extension element.
+//!
+//! The identity (`c1 = 0`) is encoded as `g = 0`, which would otherwise stand for
+//! `-1`, an element of order two which never belongs to the target group.
+//!
+//! [`TorusOutput`] serializes a [`PairingOutput`] in this form with `Compress::Yes`,
+//! and as the plain extension element with `Compress::No`. In both cases the target
+//! group membership is checked via [`TargetGroup`].
+
+use crate::{
+    gt::{self, TargetGroup},
+    pairing::{Pairing, PairingOutput},
+};
+use ark_ff::{AdditiveGroup, Field, One, QuadExtConfig, QuadExtField, Zero};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+use derivative::Derivative;
+
+/// Torus representation of a unitary quadratic extension element.
+///
+/// `None` if `f` is not unitary or is `-1`.
+pub fn compress<P: QuadExtConfig>(f: &QuadExtField<P>) -> Option<P::BaseField> {
+    if !crate::validation::is_unitary(f) {
+        return None;
+    }
+    if f.c1.is_zero() {
+        return f.c0.is_one().then(P::BaseField::zero);
+    }
+    Some((P::BaseField::one() + f.c0) * f.c1.inverse()?)
+}
+
+/// Unitary quadratic extension element from its torus representation.
+///
+/// Every base field element is a valid representation.
+pub fn decompress<P: QuadExtConfig>(g: &P::BaseField) -> QuadExtField<P> {
+    if g.is_zero() {
+        return QuadExtField::one();
+    }
+    let g2 = g.square();
+    let b = P::NONRESIDUE;
+    // Never zero, as `b` is not a square.
+    let d = (g2 - b)
+        .inverse()
+        .expect("the nonresidue is not a square; qed");
+    QuadExtField::new((g2 + b) * d, g.double() * d)
+}
+
+/// Write the torus representation of a pairing output.
+///
+/// Fails with `InvalidData` if the output is not unitary.
+pub fn encode<E, P>(out: &PairingOutput<E>, writer: impl Write) -> Result<(), SerializationError>
+where
+    E: Pairing<TargetField = QuadExtField<P>>,
+    P: QuadExtConfig,
+{
+    compress(&out.0)
+        .ok_or(SerializationError::InvalidData)?
+        .serialize_compressed(writer)
+}
+
+/// Read a pairing output from its torus representation.
+///
+/// The decoded element is unitary by construction. With `Validate::Yes` it is
+/// also checked to belong to the target group via [`TargetGroup::is_in_subgroup`].
+pub fn decode<E, P>(
+    reader: impl Read,
+    validate: Validate,
+) -> Result<PairingOutput<E>, SerializationError>
+where
+    E: TargetGroup<TargetField = QuadExtField<P>>,
+    P: QuadExtConfig,
+{
+    let g = P::BaseField::deserialize_with_mode(reader, Compress::Yes, validate)?;
+    let out = PairingOutput(decompress(&g));
+    if validate == Validate::Yes && !E::is_in_subgroup(&out) {
+        return Err(SerializationError::InvalidData);
+    }
+    Ok(out)
+}
+
+/// Pairing output serialized in torus form when compressed.
+#[derive(Derivative)]
+#[derivative(
+    Copy(bound = ""),
+    Clone(bound = ""),
+    PartialEq(bound = ""),
+    Eq(bound = ""),
+    Debug(bound = "")
+)]
+pub struct TorusOutput<E: Pairing>(pub PairingOutput<E>);
+
+impl<E: Pairing> From<PairingOutput<E>> for TorusOutput<E> {
+    fn from(out: PairingOutput<E>) -> Self {
+        TorusOutput(out)
+    }
+}
+
+impl<E, P> CanonicalSerialize for TorusOutput<E>
+where
+    E: Pairing<TargetField = QuadExtField<P>>,
+    P: QuadExtConfig,
+{
+    fn serialize_with_mode<W: Write>(
+        &self,
+        writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        match compress {
+            Compress::Yes => encode(&self.0, writer),
+            Compress::No => self.0.serialize_with_mode(writer, compress),
+        }
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        match compress {
+            Compress::Yes => P::BaseField::zero().serialized_size(compress),
+            Compress::No => self.0.serialized_size(compress),
+        }
+    }
+}
+
+impl<E: TargetGroup> Valid for TorusOutput<E> {
+    fn check(&self) -> Result<(), SerializationError> {
+        match E::is_in_subgroup(&self.0) {
+            true => Ok(()),
+            false => Err(SerializationError::InvalidData),
+        }
+    }
+}
+
+impl<E, P> CanonicalDeserialize for TorusOutput<E>
+where
+    E: TargetGroup<TargetField = QuadExtField<P>>,
+    P: QuadExtConfig,
+{
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        match compress {
+            Compress::Yes => decode(reader, validate).map(TorusOutput),
+            Compress::No => gt::deserialize(reader, compress, validate).map(TorusOutput),
+        }
+    }
+}
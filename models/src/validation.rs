@@ -0,0 +1,115 @@
+//! Validation of the values returned by the hooks.
+//!
+//! The hooks run outside of the caller's control, thus the curve configs perform
+//! a few cheap sanity checks on their outputs. None of these checks requires a
+//! hook call.
+
+use crate::{short_weierstrass as sw, twisted_edwards as te};
+use ark_ff::{BigInteger, Field, One, PrimeField, QuadExtConfig, QuadExtField, Zero};
+
+/// Check that a short Weierstrass point in Jacobian coordinates lies on the curve.
+///
+/// Checks `Y^2 = X^3 + a X Z^4 + b Z^6`, so no inversion is required.
+/// Any point with `Z = 0` is the point at infinity.
+pub fn sw_projective_is_on_curve<P: sw::SWCurveConfig>(p: &sw::Projective<P>) -> bool {
+    if p.z.is_zero() {
+        return true;
+    }
+    let z2 = p.z.square();
+    let z4 = z2.square();
+    let z6 = z4 * z2;
+    let mut rhs = p.x.square() * p.x + P::COEFF_B * z6;
+    if !P::COEFF_A.is_zero() {
+        rhs += P::mul_by_a(p.x * z4);
+    }
+    p.y.square() == rhs
+}
+
+/// Check that a twisted Edwards point in extended coordinates lies on the curve.
+///
+/// Checks `Z != 0`, `X Y = T Z` and `a X^2 + Y^2 = Z^2 + d T^2`.
+pub fn te_projective_is_on_curve<P: te::TECurveConfig>(p: &te::Projective<P>) -> bool {
+    if p.z.is_zero() || p.x * p.y != p.t * p.z {
+        return false;
+    }
+    P::mul_by_a(p.x.square()) + p.y.square() == p.z.square() + P::COEFF_D * p.t.square()
+}
+
+/// Check that `affine` is the normalization of the short Weierstrass `points`.
+///
+/// Checks `X = x Z^2` and `Y = y Z^3` for every point, so no inversion is required.
+pub fn sw_is_normalization_of<P: sw::SWCurveConfig>(
+    points: &[sw::Projective<P>],
+    affine: &[sw::Affine<P>],
+) -> bool {
+    points.len() == affine.len()
+        && points.iter().zip(affine).all(|(p, a)| {
+            if p.z.is_zero() || a.infinity {
+                return p.z.is_zero() && a.infinity;
+            }
+            let z2 = p.z.square();
+            p.x == a.x * z2 && p.y == a.y * z2 * p.z
+        })
+}
+
+/// Check that `affine` is the normalization of the twisted Edwards `points`.
+///
+/// Checks `X = x Z` and `Y = y Z` for every point, so no inversion is required.
+pub fn te_is_normalization_of<P: te::TECurveConfig>(
+    points: &[te::Projective<P>],
+    affine: &[te::Affine<P>],
+) -> bool {
+    points.len() == affine.len()
+        && points
+            .iter()
+            .zip(affine)
+            .all(|(p, a)| !p.z.is_zero() && p.x == a.x * p.z && p.y == a.y * p.z)
+}
+
+/// Check that a quadratic extension element is unitary, i.e. `f * conj(f) = 1`.
+///
+/// Every final exponentiation output (and thus every pairing target group
+/// element) is unitary, while zero and a random element are not.
+pub fn is_unitary<P: QuadExtConfig>(f: &QuadExtField<P>) -> bool {
+    let mut conj = *f;
+    conj.conjugate_in_place();
+    (*f * conj).is_one()
+}
+
+/// Subgroup membership check for short Weierstrass points based on the group order.
+///
+/// Checks `[r - 1]P = -P`, which unlike `[r]P = 0` can't be satisfied by a hook
+/// returning the identity. The scalar is split in two halves each smaller than `r`,
+/// as some hooks reduce the scalar modulo `r` before the multiplication
+/// (see <https://github.com/arkworks-rs/algebra/issues/948>).
+pub fn sw_is_in_subgroup_via_order<P: sw::SWCurveConfig>(p: &sw::Affine<P>) -> bool {
+    if P::cofactor_is_one() {
+        return true;
+    }
+    let (hi, lo) = order_minus_one_halves::<P::ScalarField>();
+    let res = P::mul_affine(p, hi.as_ref()) + P::mul_affine(p, lo.as_ref());
+    res == -*p
+}
+
+/// Subgroup membership check for twisted Edwards points based on the group order.
+///
+/// See [`sw_is_in_subgroup_via_order`].
+pub fn te_is_in_subgroup_via_order<P: te::TECurveConfig>(p: &te::Affine<P>) -> bool {
+    if P::cofactor_is_one() {
+        return true;
+    }
+    let (hi, lo) = order_minus_one_halves::<P::ScalarField>();
+    let res = P::mul_affine(p, hi.as_ref()) + P::mul_affine(p, lo.as_ref());
+    res == -*p
+}
+
+/// Split `r - 1` into its most and least significant halves of limbs.
+fn order_minus_one_halves<F: PrimeField>() -> (F::BigInt, F::BigInt) {
+    let mut hi = F::MODULUS;
+    hi.sub_with_borrow(&F::BigInt::from(1u64));
+    let mut lo = hi;
+    let half = hi.as_ref().len() / 2;
+    hi.as_mut()[..half].iter_mut().for_each(|limb| *limb = 0);
+    lo.as_mut()[half..].iter_mut().for_each(|limb| *limb = 0);
+    (hi, lo)
+}
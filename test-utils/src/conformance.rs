@@ -0,0 +1,352 @@
+//! Hooks dispatch conformance.
+//!
+//! Checks that every public scalar multiplication (and batch normalization) entry
+//! point of a curve config reaches the hooks, and that it does so exactly once.
+//! Also checks that the hook free deserialization never reaches them.
+//!
+//! The checked config must be instantiated with `MeteredHooks<_, LocalMeter>`,
+//! as the calls are counted via the [`LocalMeter`] of the current thread.
+//!
+//! Cofactor clearing and subgroup checks are expected to never reach the hooks
+//! for curves with cofactor one (as there is nothing to compute). Otherwise,
+//! subgroup checks are expected to reach them at least once, while cofactor
+//! clearing may not (e.g. when the cofactor is small).
+
+use crate::mul_plain;
+use ark_ec::{
+    pairing::{MillerLoopOutput, Pairing, PairingOutput},
+    short_weierstrass::{Affine as SWAffine, Projective as SWProjective, SWCurveConfig},
+    twisted_edwards::{Affine as TEAffine, Projective as TEProjective, TECurveConfig},
+    AffineRepr, CurveConfig, CurveGroup,
+};
+use ark_ff::{PrimeField, UniformRand};
+use ark_models_ext::{
+    accumulator::MillerLoopAccumulator,
+    batch::MsmJob,
+    checked::HookError,
+    decoding::{check_subgroup_batch, deserialize_on_curve_only, OnCurve},
+    gt::{GtOutput, TargetGroup},
+    metering::{HookOp, LocalMeter},
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
+use ark_std::{
+    rand::{rngs::StdRng, SeedableRng},
+    vec::Vec,
+};
+
+/// Number of hook calls performed by `f`, which must all be calls to `op`.
+fn calls(op: HookOp, what: &str, f: impl FnOnce()) -> u64 {
+    LocalMeter::reset();
+    f();
+    let snapshot = LocalMeter::snapshot();
+    let calls = snapshot.get(op).calls;
+    assert_eq!(snapshot.calls(), calls, "{what}: unexpected hook operation");
+    calls
+}
+
+fn check_group<G: CurveGroup>(cofactor_is_one: bool, msm: HookOp, mul: HookOp) {
+    let mut rng = StdRng::seed_from_u64(0);
+    let p = G::Affine::generator();
+    let s = G::ScalarField::rand(&mut rng);
+    let limbs = s.into_bigint();
+
+    let once = |op, what: &str, f: &dyn Fn()| assert_eq!(calls(op, what, f), 1, "{what}");
+
+    once(mul, "Projective::mul_bigint", &|| {
+        p.into_group().mul_bigint(limbs);
+    });
+    once(mul, "Affine::mul_bigint", &|| {
+        let _ = p.mul_bigint(limbs);
+    });
+    once(mul, "Projective * ScalarField", &|| {
+        let _ = p.into_group() * s;
+    });
+    once(mul, "Affine * ScalarField", &|| {
+        let _ = p * s;
+    });
+    once(mul, "Projective *= ScalarField", &|| {
+        let mut q = p.into_group();
+        q *= s;
+    });
+    once(mul, "mul_by_cofactor_to_group", &|| {
+        let _ = p.mul_by_cofactor_to_group();
+    });
+    once(msm, "VariableBaseMSM::msm", &|| {
+        G::msm(&[p, p], &[s, s]).unwrap();
+    });
+
+    let clear = calls(mul, "clear_cofactor", || {
+        let _ = p.clear_cofactor();
+    });
+    if cofactor_is_one {
+        assert_eq!(clear, 0, "clear_cofactor");
+    }
+}
+
+/// Check the short Weierstrass config `C`, whose hooks report `msm` and `mul`.
+pub fn sw<C: SWCurveConfig>(msm: HookOp, mul: HookOp) {
+    check_group::<SWProjective<C>>(C::cofactor_is_one(), msm, mul);
+
+    let p = SWAffine::<C>::generator();
+    let subgroup = calls(mul, "is_in_correct_subgroup_assuming_on_curve", || {
+        assert!(p.is_in_correct_subgroup_assuming_on_curve());
+    });
+    assert_eq!(subgroup == 0, C::cofactor_is_one(), "subgroup check");
+}
+
+/// Check the twisted Edwards config `C`, whose hooks report `msm` and `mul`.
+pub fn te<C: TECurveConfig>(msm: HookOp, mul: HookOp) {
+    let cofactor_is_one = <C as CurveConfig>::cofactor_is_one();
+    check_group::<TEProjective<C>>(cofactor_is_one, msm, mul);
+
+    let p = TEAffine::<C>::generator();
+    let subgroup = calls(mul, "is_in_correct_subgroup_assuming_on_curve", || {
+        assert!(p.is_in_correct_subgroup_assuming_on_curve());
+    });
+    assert_eq!(subgroup == 0, cofactor_is_one, "subgroup check");
+}
+
+/// Check that the batch normalization performed by `normalize` reaches the hooks
+/// exactly once, reporting `op`.
+pub fn normalize_batch<G: CurveGroup>(op: HookOp, normalize: impl Fn(&[G]) -> Vec<G::Affine>) {
+    let p = G::generator();
+    let points = [p, p.double(), G::zero()];
+    let res = calls(op, "normalize_batch", || {
+        assert_eq!(normalize(&points), G::normalize_batch(&points));
+    });
+    assert_eq!(res, 1, "normalize_batch");
+}
+
+/// Check that the multi scalar multiplication with 128-bit scalars performed by
+/// `msm_small` reaches the hooks exactly once, reporting `small`, and that the one
+/// performed by `msm_auto` does the same only if all the scalars fit, reporting
+/// `msm` otherwise.
+pub fn msm_small<G: CurveGroup>(
+    small: HookOp,
+    msm: HookOp,
+    msm_small: impl Fn(&[G::Affine], &[u128]) -> Result<G, HookError>,
+    msm_auto: impl Fn(&[G::Affine], &[G::ScalarField]) -> Result<G, HookError>,
+) {
+    let mut rng = StdRng::seed_from_u64(0);
+    let p = G::Affine::generator();
+    let s = G::ScalarField::rand(&mut rng);
+    let once = |op, what: &str, f: &dyn Fn()| assert_eq!(calls(op, what, f), 1, "{what}");
+
+    once(small, "msm_small", &|| {
+        msm_small(&[p, p], &[u128::MAX, 1]).unwrap();
+    });
+    once(small, "msm_auto", &|| {
+        msm_auto(&[p, p], &[u128::MAX.into(), 1u64.into()]).unwrap();
+    });
+    once(msm, "msm_auto", &|| {
+        msm_auto(&[p, p], &[s, 1u64.into()]).unwrap();
+    });
+}
+
+/// Check that the independent multi scalar multiplications performed by `multi_msm`
+/// reach the hooks exactly once, whatever the number of jobs.
+pub fn multi_msm<G: CurveGroup>(
+    op: HookOp,
+    multi_msm: impl Fn(&[MsmJob<G::Affine, G::ScalarField>]) -> Vec<Result<G, HookError>>,
+) {
+    let mut rng = StdRng::seed_from_u64(0);
+    let p = [G::Affine::generator(); 2];
+    let s = [
+        G::ScalarField::rand(&mut rng),
+        G::ScalarField::rand(&mut rng),
+    ];
+    for count in [1, 3] {
+        let jobs = ark_std::vec![(&p[..], &s[..]); count];
+        assert_eq!(
+            calls(op, "multi_msm", || {
+                multi_msm(&jobs).into_iter().for_each(|res| {
+                    res.unwrap();
+                })
+            }),
+            1,
+            "multi_msm"
+        );
+    }
+}
+
+/// Check that the multiplications of one base by many scalars performed by
+/// `batch_mul` reach the hooks exactly once.
+pub fn batch_mul<G: CurveGroup>(
+    op: HookOp,
+    batch_mul: impl Fn(&G, &[G::ScalarField]) -> Result<Vec<G::Affine>, HookError>,
+) {
+    let mut rng = StdRng::seed_from_u64(0);
+    let p = G::generator();
+    let s: Vec<G::ScalarField> = (0..3).map(|_| G::ScalarField::rand(&mut rng)).collect();
+    let once = |what: &str, f: &dyn Fn()| assert_eq!(calls(op, what, f), 1, "{what}");
+
+    once("batch_mul", &|| {
+        batch_mul(&p, &s).unwrap();
+    });
+}
+
+/// Check that a pairing check via the [`MillerLoopAccumulator`] reaches the multi
+/// Miller loop and the final exponentiation hooks exactly once each, whatever the
+/// number of accumulated pairs, and never reaches them if there is none.
+pub fn miller_loop_accumulator<E: Pairing>() {
+    let p = E::G1Affine::generator();
+    let q = E::G2Affine::generator();
+
+    LocalMeter::reset();
+    assert!(
+        MillerLoopAccumulator::<E>::new().check(),
+        "empty accumulator"
+    );
+    assert_eq!(LocalMeter::snapshot().calls(), 0, "empty accumulator");
+
+    for count in [1, 4] {
+        LocalMeter::reset();
+        let mut acc = MillerLoopAccumulator::<E>::new();
+        acc.extend((0..count).map(|_| (p, q)));
+        acc.check();
+        let snapshot = LocalMeter::snapshot();
+        assert_eq!(
+            snapshot.get(HookOp::MultiMillerLoop).calls,
+            1,
+            "miller loop"
+        );
+        assert_eq!(
+            snapshot.get(HookOp::FinalExponentiation).calls,
+            1,
+            "final exp"
+        );
+        assert_eq!(
+            snapshot.calls(),
+            2,
+            "accumulator: unexpected hook operation"
+        );
+    }
+}
+
+/// Check that the final exponentiation of many Miller loop outputs performed by
+/// `batch` reaches the hooks exactly once.
+pub fn final_exponentiation_batch<E: Pairing>(
+    batch: impl Fn(&[MillerLoopOutput<E>]) -> Vec<Option<PairingOutput<E>>>,
+) {
+    let mut rng = StdRng::seed_from_u64(0);
+    let targets: Vec<_> = (0..3)
+        .map(|_| MillerLoopOutput(E::TargetField::rand(&mut rng)))
+        .collect();
+    let once = |what: &str, f: &dyn Fn()| {
+        let op = HookOp::FinalExponentiationBatch;
+        assert_eq!(calls(op, what, f), 1, "{what}")
+    };
+
+    once("final_exponentiation_batch", &|| {
+        batch(&targets);
+    });
+}
+
+/// Check that the target group membership check reaches the hooks exactly once
+/// for a pairing output, and never for an element which is not unitary. Also
+/// checks that [`GtOutput`] deserialization reaches it only when validating.
+pub fn gt_is_in_subgroup<E: TargetGroup>() {
+    let out = E::pairing(E::G1Affine::generator(), E::G2Affine::generator());
+    let not_unitary = PairingOutput::<E>(E::TargetField::from(2u64));
+    let op = HookOp::GtIsInSubgroup;
+
+    assert_eq!(
+        calls(op, "gt_is_in_subgroup", || assert!(E::is_in_subgroup(&out))),
+        1
+    );
+    assert_eq!(
+        calls(op, "gt_is_in_subgroup", || assert!(!E::is_in_subgroup(
+            &not_unitary
+        ))),
+        0
+    );
+
+    let mut buf = Vec::new();
+    out.serialize_compressed(&mut buf).unwrap();
+    for (validate, expected) in [(Validate::Yes, 1), (Validate::No, 0)] {
+        let res = calls(op, "GtOutput::deserialize", || {
+            let res = GtOutput::<E>::deserialize_with_mode(&buf[..], Compress::Yes, validate);
+            assert_eq!(res.unwrap().0, out);
+        });
+        assert_eq!(res, expected, "GtOutput::deserialize");
+    }
+}
+
+/// Check that the validated decompression of a point of `A` takes exactly one
+/// square root via the hooks, reporting `op`, and that the unchecked one never
+/// reaches the hooks.
+///
+/// The subgroup check of the validated decompression may reach other hooks.
+pub fn decompression<A: AffineRepr>(op: HookOp) {
+    let p = A::generator();
+    let mut buf = Vec::new();
+    p.serialize_compressed(&mut buf).unwrap();
+
+    LocalMeter::reset();
+    let res = A::deserialize_with_mode(&buf[..], Compress::Yes, Validate::Yes);
+    assert_eq!(res.unwrap(), p);
+    assert_eq!(
+        LocalMeter::snapshot().get(op).calls,
+        1,
+        "Affine::deserialize"
+    );
+
+    let res = calls(op, "Affine::deserialize_unchecked", || {
+        let res = A::deserialize_with_mode(&buf[..], Compress::Yes, Validate::No);
+        assert_eq!(res.unwrap(), p);
+    });
+    assert_eq!(res, 0, "Affine::deserialize_unchecked");
+}
+
+/// Check the hook free deserialization of the `Ext` group over the upstream `Ark`
+/// group samples.
+///
+/// [`deserialize_on_curve_only`] must never reach the hooks, even for malformed
+/// inputs, while [`check_subgroup_batch`] must agree with the naive subgroup check.
+pub fn decoding<Ext, Ark>(samples: &[Ark::Affine])
+where
+    Ext: CurveGroup,
+    Ext::Affine: OnCurve,
+    Ark: CurveGroup,
+{
+    let mut points = Vec::new();
+    for sample in samples {
+        for compress in [Compress::Yes, Compress::No] {
+            let mut buf = Vec::new();
+            sample.serialize_with_mode(&mut buf, compress).unwrap();
+
+            LocalMeter::reset();
+            let res = deserialize_on_curve_only::<Ext::Affine, _>(&buf[..], compress);
+            assert_eq!(LocalMeter::snapshot().calls(), 0, "hook called");
+            let point = res.expect("valid point");
+            let mut encoded = Vec::new();
+            point.serialize_with_mode(&mut encoded, compress).unwrap();
+            assert_eq!(encoded, buf, "deserialization mismatch");
+            if compress == Compress::No {
+                points.push(point);
+            }
+
+            if sample.is_zero() {
+                continue;
+            }
+            // Flip a bit of the first coordinate
+            let i = buf.len() / 4;
+            buf[i] ^= 1;
+            LocalMeter::reset();
+            let res = deserialize_on_curve_only::<Ext::Affine, _>(&buf[..], compress);
+            assert_eq!(LocalMeter::snapshot().calls(), 0, "hook called");
+            if compress == Compress::No {
+                assert!(res.is_err(), "off curve point accepted");
+            }
+        }
+    }
+
+    let expected = samples
+        .iter()
+        .position(|p| !mul_plain(p.into_group(), Ark::ScalarField::MODULUS).is_zero());
+    assert_eq!(check_subgroup_batch(&points).err(), expected);
+    for (i, point) in points.iter().enumerate() {
+        let expected = mul_plain(samples[i].into_group(), Ark::ScalarField::MODULUS).is_zero();
+        assert_eq!(check_subgroup_batch(&[*point]).is_ok(), expected);
+    }
+}
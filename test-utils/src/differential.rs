@@ -0,0 +1,633 @@
+//! Differential testing against upstream *Arkworks*.
+//!
+//! Every check runs the same operation on an extension curve group (`Ext`) and on
+//! the corresponding upstream group (`Ark`), and compares the serialized outputs
+//! byte for byte. Points are sampled on the upstream curve and moved to the
+//! extension curve as they are.
+//!
+//! The samples include the edge cases: identity, generator, points outside the
+//! prime order subgroup and low-order points. The scalars include zero, one,
+//! `r - 1`, `r` and the max value representable by the scalar limbs.
+//!
+//! Subgroup checks are exercised by the deserialization with validation.
+
+use crate::{mul_plain, TryTransmute};
+use ark_ec::{
+    pairing::{MillerLoopOutput, Pairing, PairingOutput},
+    short_weierstrass::{Affine as SWAffine, SWCurveConfig},
+    twisted_edwards::{Affine as TEAffine, TECurveConfig},
+    AffineRepr, CurveGroup,
+};
+use ark_ff::{BigInteger, Field, PrimeField, QuadExtConfig, QuadExtField, UniformRand};
+use ark_models_ext::{
+    accumulator::MillerLoopAccumulator,
+    batch::MsmJob,
+    checked::HookError,
+    gt::{self, TargetGroup},
+    raw::{self, Raw, RawError, RawField},
+    torus::{self, TorusOutput},
+    validation::is_unitary,
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
+use ark_std::{
+    ops::Neg,
+    rand::{rngs::StdRng, Rng, SeedableRng},
+    vec,
+    vec::Vec,
+    One, Zero,
+};
+
+/// Number of random samples of each kind.
+const SAMPLES: usize = 4;
+
+fn rng() -> StdRng {
+    StdRng::seed_from_u64(0)
+}
+
+fn bytes<T: CanonicalSerialize>(value: &T, compress: Compress) -> Vec<u8> {
+    let mut buf = Vec::new();
+    value.serialize_with_mode(&mut buf, compress).unwrap();
+    buf
+}
+
+fn assert_same<E: CanonicalSerialize, A: CanonicalSerialize>(ext: &E, ark: &A, what: &str) {
+    assert_eq!(
+        bytes(ext, Compress::No),
+        bytes(ark, Compress::No),
+        "{what} mismatch"
+    );
+}
+
+fn to_ext<Ext: CurveGroup, Ark: CurveGroup>(p: &Ark::Affine) -> Ext::Affine {
+    p.try_transmute().expect("same encoding")
+}
+
+fn in_subgroup<G: CurveGroup>(p: &G::Affine) -> bool {
+    mul_plain(p.into_group(), G::ScalarField::MODULUS).is_zero()
+}
+
+/// Scalars as little-endian limbs, including the edge cases.
+fn scalars<F: PrimeField>(rng: &mut impl Rng) -> Vec<Vec<u64>> {
+    let r = F::MODULUS;
+    let mut r_minus_one = r;
+    r_minus_one.sub_with_borrow(&F::BigInt::from(1u64));
+    let mut res = vec![
+        vec![0],
+        vec![1],
+        r_minus_one.as_ref().to_vec(),
+        r.as_ref().to_vec(),
+        vec![u64::MAX; r.as_ref().len()],
+    ];
+    res.extend((0..SAMPLES).map(|_| F::rand(rng).into_bigint().as_ref().to_vec()));
+    res
+}
+
+/// Prime order subgroup samples: identity, generator and random points.
+pub fn subgroup_samples<G: CurveGroup>() -> Vec<G::Affine> {
+    let mut rng = rng();
+    let mut res = vec![G::Affine::zero(), G::Affine::generator()];
+    res.extend((0..SAMPLES).map(|_| G::rand(&mut rng).into_affine()));
+    res
+}
+
+/// Short Weierstrass samples.
+///
+/// On top of the subgroup samples, includes random curve points and the low-order
+/// points obtained by clearing their prime order component.
+pub fn sw_samples<P: SWCurveConfig>() -> Vec<SWAffine<P>> {
+    let mut rng = rng();
+    let mut res = subgroup_samples::<ark_ec::short_weierstrass::Projective<P>>();
+    if P::cofactor_is_one() {
+        return res;
+    }
+    let mut curve = Vec::new();
+    while curve.len() < SAMPLES {
+        let x = P::BaseField::rand(&mut rng);
+        curve.extend(SWAffine::<P>::get_point_from_x_unchecked(x, rng.gen()));
+    }
+    let low_order = curve
+        .iter()
+        .map(|p| mul_plain(p.into_group(), P::ScalarField::MODULUS).into_affine())
+        .collect::<Vec<_>>();
+    res.extend(curve);
+    res.extend(low_order);
+    res
+}
+
+/// Twisted Edwards samples.
+///
+/// On top of the subgroup samples, includes the 2-torsion point `(0, -1)` and its
+/// sum with the random subgroup points. Arbitrary curve points are avoided, as for
+/// curves with incomplete addition formulas they may hit exceptional cases.
+pub fn te_samples<P: TECurveConfig>() -> Vec<TEAffine<P>> {
+    let mut res = subgroup_samples::<ark_ec::twisted_edwards::Projective<P>>();
+    let torsion = TEAffine::<P>::new_unchecked(P::BaseField::zero(), -P::BaseField::one());
+    let shifted = res[2..]
+        .iter()
+        .map(|p| (*p + torsion).into_affine())
+        .collect::<Vec<_>>();
+    res.push(torsion);
+    res.extend(shifted);
+    res
+}
+
+/// Compare scalar multiplication.
+///
+/// Projective multiplication is checked for every sample, while affine
+/// multiplication and multiplication by a scalar field element only for subgroup
+/// samples, as upstream may use the GLV method (valid only in the subgroup) for
+/// the former but not for the latter.
+pub fn mul<Ext, Ark>(samples: &[Ark::Affine])
+where
+    Ext: CurveGroup<ScalarField = Ark::ScalarField>,
+    Ark: CurveGroup,
+{
+    let mut rng = rng();
+    let scalars = scalars::<Ark::ScalarField>(&mut rng);
+    for ark in samples {
+        let ext = to_ext::<Ext, Ark>(ark);
+        for scalar in &scalars {
+            let res = ext.into_group().mul_bigint(scalar);
+            let expected = ark.into_group().mul_bigint(scalar);
+            assert_same(&res, &expected, "projective mul");
+        }
+        if !in_subgroup::<Ark>(ark) {
+            continue;
+        }
+        for scalar in &scalars {
+            assert_same(
+                &ext.mul_bigint(scalar),
+                &ark.mul_bigint(scalar),
+                "affine mul",
+            );
+        }
+        let scalar = Ark::ScalarField::rand(&mut rng);
+        assert_same(&(ext * scalar), &(*ark * scalar), "scalar field mul");
+    }
+}
+
+/// Compare multi scalar multiplication.
+pub fn msm<Ext, Ark>(samples: &[Ark::Affine])
+where
+    Ext: CurveGroup<ScalarField = Ark::ScalarField>,
+    Ark: CurveGroup,
+{
+    let mut rng = rng();
+    let ext_samples: Vec<_> = samples.iter().map(to_ext::<Ext, Ark>).collect();
+    let mut scalars: Vec<Ark::ScalarField> =
+        vec![Zero::zero(), 1u64.into(), -Ark::ScalarField::from(1u64)];
+    while scalars.len() < samples.len() {
+        scalars.push(Ark::ScalarField::rand(&mut rng));
+    }
+    scalars.truncate(samples.len());
+
+    for len in [0, 1, samples.len()] {
+        let res = Ext::msm(&ext_samples[..len], &scalars[..len]).unwrap();
+        let expected = Ark::msm(&samples[..len], &scalars[..len]).unwrap();
+        assert_same(&res, &expected, "msm");
+    }
+    // Length mismatch
+    let res = Ext::msm(&ext_samples, &scalars[1..]).map(|_| ());
+    let expected = Ark::msm(samples, &scalars[1..]).map(|_| ());
+    assert_eq!(res, expected, "msm length mismatch");
+}
+
+/// Compare the multi scalar multiplication with 128-bit scalars performed by
+/// `msm_small`, and the one picking the scalars width performed by `msm_auto`.
+pub fn msm_small<Ext, Ark>(
+    samples: &[Ark::Affine],
+    msm_small: impl Fn(&[Ext::Affine], &[u128]) -> Result<Ext, HookError>,
+    msm_auto: impl Fn(&[Ext::Affine], &[Ext::ScalarField]) -> Result<Ext, HookError>,
+) where
+    Ext: CurveGroup<ScalarField = Ark::ScalarField>,
+    Ark: CurveGroup,
+{
+    let mut rng = rng();
+    let ext_samples: Vec<_> = samples.iter().map(to_ext::<Ext, Ark>).collect();
+    let mut small = vec![0, 1, u128::MAX];
+    while small.len() < samples.len() {
+        small.push(rng.gen());
+    }
+    small.truncate(samples.len());
+    let scalars: Vec<Ark::ScalarField> = small.iter().map(|&s| s.into()).collect();
+    let mut full = scalars.clone();
+    full[0] = Ark::ScalarField::rand(&mut rng);
+
+    for len in [0, 1, samples.len()] {
+        let expected = Ark::msm(&samples[..len], &scalars[..len]).unwrap();
+        let res = msm_small(&ext_samples[..len], &small[..len]).unwrap();
+        assert_same(&res, &expected, "msm small");
+        let res = msm_auto(&ext_samples[..len], &scalars[..len]).unwrap();
+        assert_same(&res, &expected, "msm auto");
+        let expected = Ark::msm(&samples[..len], &full[..len]).unwrap();
+        let res = msm_auto(&ext_samples[..len], &full[..len]).unwrap();
+        assert_same(&res, &expected, "msm auto");
+    }
+    // Length mismatch
+    let res = msm_small(&ext_samples, &small[1..]).map(|_| ());
+    let expected = Err(HookError::LengthMismatch {
+        left: samples.len(),
+        right: samples.len() - 1,
+    });
+    assert_eq!(res, expected, "msm small length mismatch");
+}
+
+/// Compare independent multi scalar multiplications performed via `multi_msm`,
+/// including an empty job and a job with mismatching lengths.
+pub fn multi_msm<Ext, Ark>(
+    samples: &[Ark::Affine],
+    multi_msm: impl Fn(&[MsmJob<Ext::Affine, Ext::ScalarField>]) -> Vec<Result<Ext, HookError>>,
+) where
+    Ext: CurveGroup<ScalarField = Ark::ScalarField>,
+    Ark: CurveGroup,
+{
+    let mut rng = rng();
+    let ext_samples: Vec<_> = samples.iter().map(to_ext::<Ext, Ark>).collect();
+    let scalars: Vec<Ark::ScalarField> = (0..samples.len())
+        .map(|_| Ark::ScalarField::rand(&mut rng))
+        .collect();
+    let n = samples.len();
+    let ranges = [(0, n), (0, 0), (1, n), (0, n - 1), (n - 1, n)];
+    let jobs: Vec<_> = ranges
+        .iter()
+        .map(|&(start, end)| (&ext_samples[start..end], &scalars[start..end]))
+        .chain([(&ext_samples[..], &scalars[1..])])
+        .collect();
+    let res = multi_msm(&jobs);
+    assert_eq!(res.len(), jobs.len(), "multi msm outputs count");
+    for (&(start, end), res) in ranges.iter().zip(&res) {
+        let expected = Ark::msm(&samples[start..end], &scalars[start..end]).unwrap();
+        assert_same(res.as_ref().unwrap(), &expected, "multi msm");
+    }
+    let expected = HookError::LengthMismatch {
+        left: n,
+        right: n - 1,
+    };
+    let res = res[ranges.len()].as_ref().err().copied();
+    assert_eq!(res, Some(expected), "multi msm length mismatch");
+}
+
+/// Compare the multiplications of one base by many scalars performed via
+/// `batch_mul` with the upstream `ScalarMul::batch_mul`.
+///
+/// Only subgroup samples are checked, as upstream may use the GLV method for the
+/// single multiplications but not for the batched ones.
+pub fn batch_mul<Ext, Ark>(
+    samples: &[Ark::Affine],
+    batch_mul: impl Fn(&Ext, &[Ext::ScalarField]) -> Result<Vec<Ext::Affine>, HookError>,
+) where
+    Ext: CurveGroup<ScalarField = Ark::ScalarField>,
+    Ark: CurveGroup,
+{
+    let mut rng = rng();
+    let mut scalars: Vec<Ark::ScalarField> =
+        vec![Zero::zero(), 1u64.into(), -Ark::ScalarField::from(1u64)];
+    scalars.extend((0..SAMPLES).map(|_| Ark::ScalarField::rand(&mut rng)));
+    for ark in samples.iter().filter(|p| in_subgroup::<Ark>(p)) {
+        let ext = to_ext::<Ext, Ark>(ark).into_group();
+        for len in [0, 1, scalars.len()] {
+            let res = batch_mul(&ext, &scalars[..len]).unwrap();
+            let expected = ark.into_group().batch_mul(&scalars[..len]);
+            assert_eq!(res.len(), expected.len(), "batch mul outputs count");
+            for (res, expected) in res.iter().zip(&expected) {
+                assert_same(res, expected, "batch mul");
+            }
+        }
+    }
+}
+
+/// Compare serialization and deserialization, with and without validation.
+pub fn serialization<Ext, Ark>(samples: &[Ark::Affine])
+where
+    Ext: CurveGroup,
+    Ark: CurveGroup,
+{
+    for ark in samples {
+        let ext = to_ext::<Ext, Ark>(ark);
+        for compress in [Compress::Yes, Compress::No] {
+            let buf = bytes(ark, compress);
+            assert_eq!(bytes(&ext, compress), buf, "serialization mismatch");
+            for validate in [Validate::Yes, Validate::No] {
+                let res = Ext::Affine::deserialize_with_mode(&buf[..], compress, validate);
+                let expected = Ark::Affine::deserialize_with_mode(&buf[..], compress, validate);
+                match (res, expected) {
+                    (Ok(res), Ok(expected)) => assert_same(&res, &expected, "deserialization"),
+                    (Err(_), Err(_)) => (),
+                    (res, expected) => panic!(
+                        "deserialization mismatch: {:?} vs {:?}",
+                        res.is_ok(),
+                        expected.is_ok()
+                    ),
+                }
+            }
+        }
+    }
+}
+
+/// Compare cofactor clearing.
+pub fn cofactor_clearing<Ext, Ark>(samples: &[Ark::Affine])
+where
+    Ext: CurveGroup,
+    Ark: CurveGroup,
+{
+    for ark in samples {
+        let ext = to_ext::<Ext, Ark>(ark);
+        assert_same(
+            &ext.clear_cofactor(),
+            &ark.clear_cofactor(),
+            "cofactor clearing",
+        );
+    }
+}
+
+/// Compare the batch normalization performed by `normalize` with the upstream one.
+///
+/// The normalized points are the doubles of the samples and the sums of the
+/// consecutive samples, so that their `Z` coordinate is not one.
+pub fn normalize_batch<Ext, Ark>(
+    samples: &[Ark::Affine],
+    normalize: impl Fn(&[Ext]) -> Vec<Ext::Affine>,
+) where
+    Ext: CurveGroup,
+    Ark: CurveGroup,
+{
+    let ext_samples: Vec<_> = samples.iter().map(to_ext::<Ext, Ark>).collect();
+    let mut points: Vec<Ext> = ext_samples
+        .iter()
+        .map(|p| p.into_group().double())
+        .collect();
+    points.extend(ext_samples.windows(2).map(|w| w[0] + w[1]));
+    let mut expected: Vec<Ark> = samples.iter().map(|p| p.into_group().double()).collect();
+    expected.extend(samples.windows(2).map(|w| w[0] + w[1]));
+
+    for len in [0, 1, points.len()] {
+        assert_same(
+            &normalize(&points[..len]),
+            &Ark::normalize_batch(&expected[..len]),
+            "batch normalization",
+        );
+    }
+}
+
+/// Check the raw limbs encoding of the samples and of their doubles, as single
+/// values and as a slice, against the upstream values.
+///
+/// Truncated, extended, corrupted and mismatching kind payloads are rejected.
+pub fn raw<Ext, Ark>(samples: &[Ark::Affine])
+where
+    Ext: CurveGroup + Raw,
+    Ext::Affine: Raw,
+    Ark: CurveGroup,
+{
+    let points: Vec<Ext::Affine> = samples.iter().map(to_ext::<Ext, Ark>).collect();
+    for (p, ark) in points.iter().zip(samples) {
+        let buf = raw::encode(p);
+        assert_same(
+            &raw::decode::<Ext::Affine>(&buf).unwrap(),
+            ark,
+            "raw affine",
+        );
+        assert_eq!(raw::decode::<Ext>(&buf).err(), Some(RawError::Header));
+        assert_eq!(
+            raw::decode::<Ext::Affine>(&buf[..buf.len() - 1]).err(),
+            Some(RawError::Length)
+        );
+        assert_eq!(
+            raw::decode::<Ext::Affine>(&[&buf[..], &[0]].concat()).err(),
+            Some(RawError::Length)
+        );
+
+        let double = p.into_group().double();
+        let mut buf = raw::encode(&double);
+        assert_same(
+            &raw::decode::<Ext>(&buf).unwrap(),
+            &ark.into_group().double(),
+            "raw projective",
+        );
+        buf[raw::HEADER_LEN..].fill(0xff);
+        assert_eq!(raw::decode::<Ext>(&buf).err(), Some(RawError::Value));
+    }
+
+    let res = raw::decode_slice::<Ext::Affine>(&raw::encode_slice(&points)).unwrap();
+    assert_same(&res, &samples.to_vec(), "raw slice");
+}
+
+/// Check the raw limbs encoding of random field elements against the upstream ones.
+pub fn raw_field<Ext: RawField, Ark: Field>() {
+    let mut rng = rng();
+    for _ in 0..SAMPLES {
+        let ark = Ark::rand(&mut rng);
+        let ext: Ext = ark.try_transmute().expect("same encoding");
+        assert_same(
+            &raw::decode::<Ext>(&raw::encode(&ext)).unwrap(),
+            &ark,
+            "raw field",
+        );
+    }
+}
+
+/// Run all the group checks.
+pub fn group<Ext, Ark>(samples: &[Ark::Affine])
+where
+    Ext: CurveGroup<ScalarField = Ark::ScalarField> + Raw,
+    Ext::Affine: Raw,
+    Ark: CurveGroup,
+{
+    mul::<Ext, Ark>(samples);
+    msm::<Ext, Ark>(samples);
+    serialization::<Ext, Ark>(samples);
+    cofactor_clearing::<Ext, Ark>(samples);
+    raw::<Ext, Ark>(samples);
+}
+
+/// Compare pairing, multi pairing and its Miller loop / final exponentiation steps
+/// over the subgroup samples.
+pub fn pairing<Ext: Pairing, Ark: Pairing>() {
+    let g1 = subgroup_samples::<Ark::G1>();
+    let g2 = subgroup_samples::<Ark::G2>();
+    let ext_g1: Vec<Ext::G1Affine> = g1.iter().map(to_ext::<Ext::G1, Ark::G1>).collect();
+    let ext_g2: Vec<Ext::G2Affine> = g2.iter().map(to_ext::<Ext::G2, Ark::G2>).collect();
+
+    for (ext_p, ark_p) in ext_g1.iter().zip(&g1) {
+        for (ext_q, ark_q) in ext_g2.iter().zip(&g2) {
+            let res = Ext::pairing(*ext_p, *ext_q);
+            let expected = Ark::pairing(*ark_p, *ark_q);
+            assert_same(&res, &expected, "pairing");
+        }
+    }
+
+    let res = Ext::multi_pairing(ext_g1.clone(), ext_g2.clone());
+    let expected = Ark::multi_pairing(g1.clone(), g2.clone());
+    assert_same(&res, &expected, "multi pairing");
+
+    let res = Ext::multi_miller_loop(ext_g1, ext_g2);
+    let expected = Ark::multi_miller_loop(g1, g2);
+    assert_same(&res.0, &expected.0, "multi Miller loop");
+    let res = Ext::final_exponentiation(res).map(|out| out.0);
+    let expected = Ark::final_exponentiation(expected).map(|out| out.0);
+    assert_same(&res, &expected, "final exponentiation");
+}
+
+/// Compare the pairing products evaluated via the [`MillerLoopAccumulator`] with
+/// the upstream multi pairing, and check that the accumulated pairing equations
+/// hold if and only if they are balanced.
+pub fn miller_loop_accumulator<Ext: Pairing, Ark: Pairing>()
+where
+    Ext::G1Prepared: Neg<Output = Ext::G1Prepared>,
+{
+    let g1 = subgroup_samples::<Ark::G1>();
+    let g2 = subgroup_samples::<Ark::G2>();
+    let ext_g1: Vec<Ext::G1Affine> = g1.iter().map(to_ext::<Ext::G1, Ark::G1>).collect();
+    let ext_g2: Vec<Ext::G2Affine> = g2.iter().map(to_ext::<Ext::G2, Ark::G2>).collect();
+
+    let mut acc = MillerLoopAccumulator::<Ext>::new();
+    acc.push(ext_g1[0], ext_g2[0]);
+    acc.extend(ext_g1[1..].iter().zip(&ext_g2[1..]));
+    assert_eq!(acc.len(), g1.len());
+    let res = acc.finalize().unwrap();
+    let expected = Ark::multi_pairing(g1, g2);
+    assert_same(&res, &expected, "accumulated multi pairing");
+
+    // e(P, Q) = e(-P, -Q)
+    let (p, q) = (ext_g1[2], ext_g2[2]);
+    let mut acc = MillerLoopAccumulator::<Ext>::new();
+    assert!(acc.clone().check(), "empty accumulator");
+    acc.push(-p.into_group(), -q.into_group());
+    acc.negate();
+    acc.push(p, q);
+    assert!(acc.clone().check(), "balanced equation");
+    acc.push(p, q);
+    assert!(!acc.check(), "unbalanced equation");
+}
+
+/// Compare the final exponentiation of many Miller loop outputs performed via
+/// `batch` with the upstream final exponentiation of each of them, zero included.
+pub fn final_exponentiation_batch<Ext: Pairing, Ark: Pairing>(
+    batch: impl Fn(&[MillerLoopOutput<Ext>]) -> Vec<Option<PairingOutput<Ext>>>,
+) {
+    let g1 = subgroup_samples::<Ark::G1>();
+    let g2 = subgroup_samples::<Ark::G2>();
+    let mut targets = Vec::new();
+    let mut expected = Vec::new();
+    for (p, q) in g1.iter().zip(&g2) {
+        let ext_p = to_ext::<Ext::G1, Ark::G1>(p);
+        let ext_q = to_ext::<Ext::G2, Ark::G2>(q);
+        targets.push(Ext::multi_miller_loop([ext_p], [ext_q]));
+        expected.push(Ark::final_exponentiation(Ark::multi_miller_loop(
+            [*p],
+            [*q],
+        )));
+    }
+    targets.insert(1, MillerLoopOutput(Zero::zero()));
+    expected.insert(1, None);
+
+    for len in [0, 1, targets.len()] {
+        let res = batch(&targets[..len]);
+        assert_eq!(res.len(), len, "final exponentiation batch outputs count");
+        for (res, expected) in res.iter().zip(&expected) {
+            let res = res.map(|out| out.0);
+            let expected = expected.map(|out| out.0);
+            assert_same(&res, &expected, "final exponentiation batch");
+        }
+    }
+}
+
+/// Check the torus encoding of the target group elements, round tripping the
+/// upstream pairing outputs (identity included) through it.
+///
+/// The compressed encoding must be half the size of the plain one, and the plain
+/// encoding of [`TorusOutput`] must match the upstream one.
+pub fn torus<Ext, Ark, P>()
+where
+    Ext: TargetGroup<TargetField = QuadExtField<P>>,
+    Ark: Pairing,
+    P: QuadExtConfig,
+{
+    let g1 = subgroup_samples::<Ark::G1>();
+    let g2 = subgroup_samples::<Ark::G2>();
+    let mut outputs: Vec<_> = g1
+        .iter()
+        .zip(&g2)
+        .map(|(p, q)| Ark::pairing(*p, *q))
+        .collect();
+    outputs.push(PairingOutput::zero());
+
+    for ark in outputs {
+        let buf = bytes(&ark, Compress::No);
+        let ext = PairingOutput::<Ext>::deserialize_uncompressed(&buf[..]).unwrap();
+        let torus = TorusOutput(ext);
+        assert_eq!(bytes(&torus, Compress::No), buf, "torus plain encoding");
+        let compressed = bytes(&torus, Compress::Yes);
+        assert_eq!(compressed.len(), torus.compressed_size());
+        assert_eq!(2 * compressed.len(), buf.len(), "torus encoding size");
+        for validate in [Validate::Yes, Validate::No] {
+            let res =
+                TorusOutput::<Ext>::deserialize_with_mode(&compressed[..], Compress::Yes, validate);
+            assert_eq!(res.unwrap(), torus, "torus round trip");
+        }
+    }
+
+    // Not unitary
+    let f = QuadExtField::<P>::new(P::BaseField::one(), P::BaseField::one());
+    let res = TorusOutput(PairingOutput::<Ext>(f)).serialize_compressed(Vec::new());
+    assert!(res.is_err(), "torus encoding of a non unitary element");
+
+    // Unitary, but outside of the target group
+    let mut rng = rng();
+    let g = P::BaseField::rand(&mut rng);
+    let buf = bytes(&g, Compress::Yes);
+    let res = TorusOutput::<Ext>::deserialize_with_mode(&buf[..], Compress::Yes, Validate::Yes);
+    assert!(res.is_err(), "torus subgroup check");
+    let res = TorusOutput::<Ext>::deserialize_with_mode(&buf[..], Compress::Yes, Validate::No);
+    assert!(is_unitary(&res.unwrap().0 .0), "torus decoding");
+
+    // Non canonical
+    let buf = vec![0xff; buf.len()];
+    let res = TorusOutput::<Ext>::deserialize_with_mode(&buf[..], Compress::Yes, Validate::No);
+    assert!(res.is_err(), "torus non canonical encoding");
+}
+
+/// Compare the validation of the deserialized target group elements with the
+/// upstream one, over pairing outputs (identity included), unitary elements
+/// outside of the target group and random elements.
+pub fn gt_subgroup<Ext, Ark, P>()
+where
+    Ext: TargetGroup<TargetField = QuadExtField<P>>,
+    Ark: Pairing,
+    P: QuadExtConfig,
+{
+    let mut rng = rng();
+    let g1 = subgroup_samples::<Ark::G1>();
+    let g2 = subgroup_samples::<Ark::G2>();
+    let mut samples: Vec<Vec<u8>> = g1
+        .iter()
+        .zip(&g2)
+        .map(|(p, q)| bytes(&Ark::pairing(*p, *q), Compress::No))
+        .collect();
+    samples.push(bytes(&PairingOutput::<Ark>::zero(), Compress::No));
+    for _ in 0..SAMPLES {
+        let unitary = torus::decompress::<P>(&P::BaseField::rand(&mut rng));
+        samples.push(bytes(&unitary, Compress::No));
+        samples.push(bytes(&Ark::TargetField::rand(&mut rng), Compress::No));
+    }
+
+    for buf in samples {
+        for validate in [Validate::Yes, Validate::No] {
+            let res = gt::deserialize::<Ext>(&buf[..], Compress::No, validate);
+            let wrapped =
+                gt::GtOutput::<Ext>::deserialize_with_mode(&buf[..], Compress::No, validate);
+            assert_eq!(res.as_ref().ok(), wrapped.as_ref().ok().map(|out| &out.0));
+            let expected =
+                PairingOutput::<Ark>::deserialize_with_mode(&buf[..], Compress::No, validate);
+            match (res, expected) {
+                (Ok(res), Ok(expected)) => assert_same(&res, &expected, "target group"),
+                (Err(_), Err(_)) => (),
+                (res, expected) => panic!(
+                    "target group validation mismatch: {:?} vs {:?}",
+                    res.is_ok(),
+                    expected.is_ok()
+                ),
+            }
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! Fault injection.
+//!
+//! [`FaultyHooks`] wraps a hooks implementation and, once a fault has been armed
+//! via [`inject`], replaces the output of the chosen operation with a malformed
+//! one. This allows to exercise the error handling paths of the curve configs and
+//! of the downstream verifiers.
+//!
+//! The `CurveHooks` traits are defined by the curve crates, thus these are in
+//! charge of implementing them for [`FaultyHooks`], forwarding each call to the
+//! inner hooks and passing the output through [`faulty_sw`], [`faulty_te`] or
+//! [`faulty_target`].
+//!
+//! The armed fault is thread local, so concurrent tests don't interfere.
+
+extern crate std;
+
+use crate::mul_plain;
+use ark_ec::{
+    short_weierstrass::{Affine as SWAffine, Projective as SWProjective, SWCurveConfig},
+    twisted_edwards::{Affine as TEAffine, Projective as TEProjective, TECurveConfig},
+    CurveConfig, PrimeGroup,
+};
+use ark_ff::{Field, PrimeField, UniformRand};
+use ark_models_ext::metering::HookOp;
+use ark_std::{
+    marker::PhantomData,
+    rand::{rngs::StdRng, SeedableRng},
+    One, Zero,
+};
+use std::cell::RefCell;
+
+/// Malformed hook output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fault {
+    /// The group identity (or one for the target field).
+    Identity,
+    /// A random subgroup point (or a random field element for the target field).
+    Random,
+    /// A point which doesn't satisfy the curve equation (or a non-unitary element
+    /// for the target field).
+    OffCurve,
+    /// A curve point outside the prime order subgroup (or a unitary element outside
+    /// the pairing target group).
+    ///
+    /// For curves with cofactor one every curve point is in the subgroup, thus this
+    /// is equivalent to [`Fault::Random`].
+    NonSubgroup,
+    /// The value returned by the test hooks on failure, i.e. the type's default.
+    Fail,
+}
+
+/// Hooks wrapper replacing the output of the armed operation with a [`Fault`].
+pub struct FaultyHooks<H>(PhantomData<fn() -> H>);
+
+struct Armed {
+    op: HookOp,
+    fault: Fault,
+    nth: usize,
+    calls: usize,
+}
+
+std::thread_local! {
+    static ARMED: RefCell<Option<Armed>> = const { RefCell::new(None) };
+    static RNG: RefCell<StdRng> = RefCell::new(StdRng::seed_from_u64(0));
+}
+
+/// Arm `fault` on the `nth` call to `op`, counting from 1, or on every call if `nth` is 0.
+///
+/// Replaces any previously armed fault.
+pub fn inject(op: HookOp, fault: Fault, nth: usize) {
+    ARMED.with(|armed| {
+        *armed.borrow_mut() = Some(Armed {
+            op,
+            fault,
+            nth,
+            calls: 0,
+        })
+    });
+}
+
+/// Disarm the current fault.
+pub fn clear() {
+    ARMED.with(|armed| *armed.borrow_mut() = None);
+}
+
+/// Account for a call to `op`, returning the fault to apply (if any).
+fn take(op: HookOp) -> Option<Fault> {
+    ARMED.with(|armed| {
+        let mut armed = armed.borrow_mut();
+        let armed = armed.as_mut().filter(|armed| armed.op == op)?;
+        armed.calls += 1;
+        (armed.nth == 0 || armed.nth == armed.calls).then_some(armed.fault)
+    })
+}
+
+fn rand<T: UniformRand>() -> T {
+    RNG.with(|rng| T::rand(&mut *rng.borrow_mut()))
+}
+
+/// Check if `p` is in the prime order subgroup, never jumping into the hooks.
+fn in_subgroup<G: PrimeGroup>(p: G) -> bool {
+    mul_plain(p, G::ScalarField::MODULUS).is_zero()
+}
+
+/// Apply the fault armed for `op` (if any) to a short Weierstrass hook output.
+pub fn faulty_sw<C: SWCurveConfig>(op: HookOp, res: SWProjective<C>) -> SWProjective<C> {
+    let Some(fault) = take(op) else {
+        return res;
+    };
+    match fault {
+        Fault::Identity => SWProjective::zero(),
+        Fault::Random => mul_plain(
+            SWProjective::generator(),
+            rand::<C::ScalarField>().into_bigint(),
+        ),
+        Fault::OffCurve => loop {
+            let p = SWAffine::<C>::new_unchecked(rand(), rand());
+            if !p.is_on_curve() {
+                break SWProjective::new_unchecked(p.x, p.y, One::one());
+            }
+        },
+        Fault::NonSubgroup => loop {
+            let Some(p) = SWAffine::<C>::get_point_from_x_unchecked(rand(), false) else {
+                continue;
+            };
+            let p = SWProjective::from(p);
+            if C::cofactor_is_one() || !in_subgroup(p) {
+                break p;
+            }
+        },
+        Fault::Fail => SWProjective::default(),
+    }
+}
+
+/// Apply the fault armed for `op` (if any) to a twisted Edwards hook output.
+pub fn faulty_te<C: TECurveConfig>(op: HookOp, res: TEProjective<C>) -> TEProjective<C> {
+    let Some(fault) = take(op) else {
+        return res;
+    };
+    match fault {
+        Fault::Identity => TEProjective::zero(),
+        Fault::Random => mul_plain(
+            TEProjective::generator(),
+            rand::<C::ScalarField>().into_bigint(),
+        ),
+        Fault::OffCurve => loop {
+            let p = TEAffine::<C>::new_unchecked(rand(), rand());
+            if !p.is_on_curve() {
+                break TEProjective::new_unchecked(p.x, p.y, p.x * p.y, One::one());
+            }
+        },
+        Fault::NonSubgroup => loop {
+            let Some(p) = TEAffine::<C>::get_point_from_y_unchecked(rand(), false) else {
+                continue;
+            };
+            let p = TEProjective::from(p);
+            if <C as CurveConfig>::cofactor_is_one() || !in_subgroup(p) {
+                break p;
+            }
+        },
+        Fault::Fail => TEProjective::default(),
+    }
+}
+
+/// Apply the fault armed for `op` (if any) to a pairing target field hook output.
+pub fn faulty_target<F: Field>(op: HookOp, res: F) -> F {
+    let Some(fault) = take(op) else {
+        return res;
+    };
+    match fault {
+        Fault::Identity => F::one(),
+        Fault::Random | Fault::OffCurve => rand(),
+        Fault::NonSubgroup => {
+            // `f^(p^(k/2) - 1)` is unitary, but almost surely not in the target group.
+            let f: F = rand();
+            let conj = f.frobenius_map(F::extension_degree() as usize / 2);
+            conj * f.inverse().unwrap_or_default()
+        }
+        Fault::Fail => F::default(),
+    }
+}
@@ -9,11 +9,18 @@ use ark_ec::{
     pairing::{MillerLoopOutput, Pairing},
     short_weierstrass::{Affine as SWAffine, Projective as SWProjective, SWCurveConfig},
     twisted_edwards::{Affine as TEAffine, Projective as TEProjective, TECurveConfig},
-    CurveConfig, VariableBaseMSM,
+    CurveConfig, CurveGroup, PrimeGroup, VariableBaseMSM,
 };
+use ark_ff::BitIteratorBE;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::vec::Vec;
 
+pub mod conformance;
+pub mod differential;
+pub mod faults;
+pub mod no_panic;
+pub mod vectors;
+
 trait TryTransmute {
     fn try_transmute<U: CanonicalDeserialize>(self) -> Result<U, ()>;
 }
@@ -26,6 +33,18 @@ impl<T: CanonicalSerialize> TryTransmute for T {
     }
 }
 
+/// Double-and-add multiplication, never jumping into the hooks.
+pub fn mul_plain<G: PrimeGroup>(base: G, scalar: impl AsRef<[u64]>) -> G {
+    let mut res = G::zero();
+    for bit in BitIteratorBE::without_leading_zeros(scalar) {
+        res.double_in_place();
+        if bit {
+            res += base;
+        }
+    }
+    res
+}
+
 macro_rules! default_on_fail {
     ($($body:tt)*) => {
         let fallible = || {
@@ -37,12 +56,12 @@ macro_rules! default_on_fail {
 
 /// On any error returns `Pairing::TargetField::default()`.
 pub fn multi_miller_loop_generic<ExtPairing: Pairing, ArkPairing: Pairing>(
-    g1: impl Iterator<Item = ExtPairing::G1Prepared>,
-    g2: impl Iterator<Item = ExtPairing::G2Prepared>,
+    g1: &[ExtPairing::G1Prepared],
+    g2: &[ExtPairing::G2Prepared],
 ) -> ExtPairing::TargetField {
     default_on_fail! {
-        let g1: Vec<ArkPairing::G1Affine> = g1.collect::<Vec<_>>().try_transmute()?;
-        let g2: Vec<ArkPairing::G2Affine> = g2.collect::<Vec<_>>().try_transmute()?;
+        let g1: Vec<ArkPairing::G1Affine> = g1.to_vec().try_transmute()?;
+        let g2: Vec<ArkPairing::G2Affine> = g2.to_vec().try_transmute()?;
         let res = ArkPairing::multi_miller_loop(g1, g2).0;
         res.try_transmute()
     }
@@ -109,3 +128,25 @@ pub fn mul_projective_te_generic<ExtConfig: TECurveConfig, ArkConfig: TECurveCon
         res.try_transmute()
     }
 }
+
+/// On any error returns an empty vector.
+pub fn normalize_batch_sw_generic<ExtConfig: SWCurveConfig, ArkConfig: SWCurveConfig>(
+    points: &[SWProjective<ExtConfig>],
+) -> Vec<SWAffine<ExtConfig>> {
+    default_on_fail! {
+        let points: Vec<SWProjective<ArkConfig>> = points.try_transmute()?;
+        let res = SWProjective::<ArkConfig>::normalize_batch(&points);
+        res.try_transmute()
+    }
+}
+
+/// On any error returns an empty vector.
+pub fn normalize_batch_te_generic<ExtConfig: TECurveConfig, ArkConfig: TECurveConfig>(
+    points: &[TEProjective<ExtConfig>],
+) -> Vec<TEAffine<ExtConfig>> {
+    default_on_fail! {
+        let points: Vec<TEProjective<ArkConfig>> = points.try_transmute()?;
+        let res = TEProjective::<ArkConfig>::normalize_batch(&points);
+        res.try_transmute()
+    }
+}
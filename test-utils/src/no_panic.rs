@@ -0,0 +1,175 @@
+//! Panic freedom of the deserialization.
+//!
+//! [`deserialize`] feeds truncated, oversized, mutated and random inputs, derived
+//! from the encodings of the given samples, to `deserialize_with_mode` in every
+//! mode. [`raw`] does the same for [`raw::decode`] and [`raw::decode_slice`], and
+//! [`parse`] for any byte parser, such as the precompiles inputs parsing. Malformed
+//! inputs must be rejected with an error, never with a panic.
+
+extern crate std;
+
+use ark_models_ext::raw::{self, Raw, RawError};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Validate,
+};
+use ark_std::{
+    any::type_name,
+    fmt::Debug,
+    rand::{rngs::StdRng, Rng, SeedableRng},
+    vec,
+    vec::Vec,
+};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Number of mutated and of random inputs for each sample and mode.
+const INPUTS: usize = 16;
+
+/// Deserialize `bytes`, returning the result and the number of unread bytes.
+///
+/// Panics with the offending input if the deserialization panics.
+fn decode<T: CanonicalDeserialize>(
+    bytes: &[u8],
+    compress: Compress,
+    validate: Validate,
+) -> (Result<T, SerializationError>, usize) {
+    let mut reader = bytes;
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        T::deserialize_with_mode(&mut reader, compress, validate)
+    }));
+    match res {
+        Ok(res) => (res, reader.len()),
+        Err(_) => panic!(
+            "{} deserialization panicked (compress: {}, validate: {}) on {bytes:02x?}",
+            type_name::<T>(),
+            compress == Compress::Yes,
+            validate == Validate::Yes,
+        ),
+    }
+}
+
+/// Check that the deserialization of `T` never panics on inputs derived from the
+/// encodings of `samples`.
+///
+/// The samples encodings must be accepted in every mode, also when followed by
+/// extra bytes which are left unread, while their truncations must be rejected.
+pub fn deserialize<T: CanonicalSerialize + CanonicalDeserialize>(samples: &[T]) {
+    let mut rng = StdRng::seed_from_u64(0);
+    for sample in samples {
+        for compress in [Compress::Yes, Compress::No] {
+            let mut buf = Vec::new();
+            sample.serialize_with_mode(&mut buf, compress).unwrap();
+            let len = buf.len();
+
+            for validate in [Validate::Yes, Validate::No] {
+                let (res, _) = decode::<T>(&buf, compress, validate);
+                let mut encoded = Vec::new();
+                res.expect("valid encoding rejected")
+                    .serialize_with_mode(&mut encoded, compress)
+                    .unwrap();
+                assert_eq!(encoded, buf, "deserialization mismatch");
+
+                for n in 0..len {
+                    let (res, _) = decode::<T>(&buf[..n], compress, validate);
+                    assert!(res.is_err(), "truncated encoding accepted");
+                }
+
+                let mut oversized = buf.clone();
+                oversized.extend([0xff; 8]);
+                let (res, unread) = decode::<T>(&oversized, compress, validate);
+                assert!(res.is_ok(), "oversized encoding rejected");
+                assert_eq!(unread, 8, "extra bytes consumed");
+
+                fuzz(&buf, &mut rng, |input| {
+                    let _ = decode::<T>(input, compress, validate);
+                });
+            }
+        }
+    }
+}
+
+/// Feed `f` with mutated and random inputs derived from the non-empty `buf`.
+fn fuzz(buf: &[u8], rng: &mut StdRng, mut f: impl FnMut(&[u8])) {
+    let len = buf.len();
+    for _ in 0..INPUTS {
+        let mut mutated = buf.to_vec();
+        mutated[rng.gen_range(0..len)] ^= rng.gen_range(1..=u8::MAX);
+        f(&mutated);
+
+        let mut random = vec![0; rng.gen_range(0..2 * len + 1)];
+        rng.fill(&mut random[..]);
+        f(&random);
+        let mut random = vec![0; len];
+        rng.fill(&mut random[..]);
+        f(&random);
+    }
+}
+
+/// Run `f` on `bytes`, panicking with the offending input if `f` panics.
+fn catch<R>(name: &str, bytes: &[u8], f: impl FnOnce(&[u8]) -> R) -> R {
+    match catch_unwind(AssertUnwindSafe(|| f(bytes))) {
+        Ok(res) => res,
+        Err(_) => panic!("{name} panicked on {bytes:02x?}"),
+    }
+}
+
+/// Check that the raw decoding of `T` never panics on inputs derived from the raw
+/// encodings of `samples`, one by one and as a slice.
+///
+/// The samples encodings must be decoded back to the samples, while their
+/// truncations and extensions must be rejected.
+pub fn raw<T: Raw + PartialEq + Debug>(samples: &[T]) {
+    let mut rng = StdRng::seed_from_u64(0);
+    let name = type_name::<T>();
+    let decode = |bytes: &[u8]| catch(name, bytes, raw::decode::<T>);
+    let decode_slice = |bytes: &[u8]| catch(name, bytes, raw::decode_slice::<T>);
+
+    for sample in samples {
+        let buf = raw::encode(sample);
+        assert_eq!(decode(&buf).as_ref(), Ok(sample), "raw decoding mismatch");
+        for n in 0..buf.len() {
+            assert!(
+                decode(&buf[..n]).is_err(),
+                "truncated raw encoding accepted"
+            );
+        }
+        let oversized = [&buf[..], &[0xff; 8]].concat();
+        assert_eq!(decode(&oversized), Err(RawError::Length));
+        fuzz(&buf, &mut rng, |input| {
+            let _ = decode(input);
+        });
+    }
+
+    let buf = raw::encode_slice(samples);
+    assert_eq!(
+        decode_slice(&buf).as_deref(),
+        Ok(samples),
+        "raw decoding mismatch"
+    );
+    for n in 0..buf.len() {
+        assert!(
+            decode_slice(&buf[..n]).is_err(),
+            "truncated raw encoding accepted"
+        );
+    }
+    fuzz(&buf, &mut rng, |input| {
+        let _ = decode_slice(input);
+    });
+}
+
+/// Check that `parse`, named `name`, never panics on inputs derived from the
+/// non-empty `samples` inputs.
+///
+/// Nothing is assumed about the outcome, the accepted inputs being up to the
+/// parser.
+pub fn parse<R>(name: &str, samples: &[Vec<u8>], parse: impl Fn(&[u8]) -> R) {
+    let mut rng = StdRng::seed_from_u64(0);
+    for buf in samples {
+        for n in 0..=buf.len() {
+            catch(name, &buf[..n], &parse);
+        }
+        catch(name, &[&buf[..], &[0xff; 8]].concat(), &parse);
+        fuzz(buf, &mut rng, |input| {
+            catch(name, input, &parse);
+        });
+    }
+}
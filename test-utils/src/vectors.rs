@@ -0,0 +1,88 @@
+//! Precompile test vectors.
+//!
+//! The vectors are JSON arrays of objects in the layout of the EIP-2537 reference
+//! vectors: a hex `Input`, a `Name` and either a hex `Expected` output or an
+//! `ExpectedError` message.
+//!
+//! Besides the EIP-2537 reference vectors, the same layout holds the regression
+//! snapshots of the precompiles lacking vendored reference vectors, whose outputs
+//! are computed with the upstream implementations.
+
+extern crate std;
+
+use ark_models_ext::precompile::Error;
+use ark_std::{fmt::Debug, vec::Vec};
+use std::{format, string::String};
+
+/// Test vector.
+pub struct Vector<E> {
+    /// Vector name.
+    pub name: String,
+    /// Precompile input.
+    pub input: Vec<u8>,
+    /// Precompile output, or failure.
+    pub expected: Result<Vec<u8>, E>,
+}
+
+fn field<'a>(object: &'a str, key: &str) -> Option<&'a str> {
+    let start = object.find(&format!("\"{key}\": \""))? + key.len() + 5;
+    let len = object[start..].find('"')?;
+    Some(&object[start..start + len])
+}
+
+fn hex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+/// Parse the vectors of `json`, mapping the error messages via `error`.
+///
+/// Panics if there is no vector.
+pub fn parse<E>(json: &str, error: impl Fn(&str) -> E) -> Vec<Vector<E>> {
+    let vectors: Vec<_> = json
+        .split('{')
+        .skip(1)
+        .map(|object| Vector {
+            name: field(object, "Name").unwrap().into(),
+            input: hex(field(object, "Input").unwrap()),
+            expected: match field(object, "Expected") {
+                Some(expected) => Ok(hex(expected)),
+                None => Err(error(field(object, "ExpectedError").unwrap())),
+            },
+        })
+        .collect();
+    assert!(!vectors.is_empty());
+    vectors
+}
+
+/// Check `op` against the vectors of `json`, mapping the error messages via
+/// `error`.
+pub fn check<E: Debug + PartialEq, const N: usize>(
+    json: &str,
+    error: impl Fn(&str) -> E,
+    op: impl Fn(&[u8]) -> Result<[u8; N], E>,
+) {
+    for vector in parse(json, error) {
+        let res = op(&vector.input).map(Vec::from);
+        assert_eq!(res, vector.expected, "{}", vector.name);
+    }
+}
+
+/// Precompile error displayed as `msg`.
+///
+/// Panics if no error is displayed as `msg`.
+pub fn error(msg: &str) -> Error {
+    [
+        Error::InputLength,
+        Error::FieldPadding,
+        Error::FieldEncoding,
+        Error::NotOnCurve,
+        Error::NotInSubgroup,
+        Error::Hook,
+    ]
+    .into_iter()
+    .find(|e| format!("{e}") == msg)
+    .unwrap_or_else(|| panic!("unknown error: {msg}"))
+}